@@ -0,0 +1,329 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::error::CommandError;
+use crate::types::AppConfig;
+
+const ACTIONS_DIR_NAME: &str = "actions";
+const USAGE_FILE_NAME: &str = "usage.json";
+// Поля, привязанные к конкретному пользователю/машине, в пак не попадают
+const PACK_EXCLUDED_FIELDS: [&str; 3] = ["id", "hotkey", "profile"];
+// Поля, которые хранятся только локально и не приходят с сервера при синхронизации
+const LOCAL_ONLY_FIELDS: [&str; 2] = ["language", "completion_sound"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ActionUsageEntry {
+    pub action_id: String,
+    pub count: u64,
+    pub last_used_at: String,
+}
+
+fn resolve_actions_dir(app: &AppHandle) -> Result<PathBuf> {
+    let base_dir = app
+        .path()
+        .app_local_data_dir()
+        .or_else(|_| app.path().app_config_dir())
+        .or_else(|_| std::env::current_dir())
+        .map_err(|error| anyhow!("Failed to resolve actions directory: {error}"))?;
+    Ok(base_dir.join(ACTIONS_DIR_NAME))
+}
+
+async fn usage_file_path(app: &AppHandle) -> Result<PathBuf> {
+    let dir = resolve_actions_dir(app)?;
+    fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("create actions directory at {}", dir.display()))?;
+    Ok(dir.join(USAGE_FILE_NAME))
+}
+
+async fn read_usage(app: &AppHandle) -> Result<Vec<ActionUsageEntry>> {
+    let path = usage_file_path(app).await?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("read action usage from {}", path.display()))?;
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    match serde_json::from_str::<Vec<ActionUsageEntry>>(&contents) {
+        Ok(entries) => Ok(entries),
+        Err(error) => {
+            eprintln!("[actions] Failed to parse action usage file: {error}");
+            Ok(Vec::new())
+        }
+    }
+}
+
+async fn write_usage(app: &AppHandle, entries: &[ActionUsageEntry]) -> Result<()> {
+    let path = usage_file_path(app).await?;
+    let serialized = serde_json::to_string_pretty(entries).context("serialize action usage")?;
+    fs::write(&path, serialized)
+        .await
+        .with_context(|| format!("write action usage to {}", path.display()))
+}
+
+/// Returns the `id` of an action stored as an opaque JSON object in the config.
+pub fn action_id(action: &Value) -> Option<&str> {
+    action.get("id").and_then(|value| value.as_str())
+}
+
+pub fn find_action<'a>(config: &'a AppConfig, id: &str) -> Option<&'a Value> {
+    config
+        .actions
+        .iter()
+        .find(|action| action_id(action) == Some(id))
+}
+
+/// Actions without an explicit `enabled` flag are treated as enabled.
+pub fn is_action_enabled(action: &Value) -> bool {
+    action
+        .get("enabled")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+pub fn set_action_enabled(actions: &mut [Value], id: &str, enabled: bool) -> Result<()> {
+    let action = actions
+        .iter_mut()
+        .find(|action| action_id(action) == Some(id))
+        .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+    let map = action
+        .as_object_mut()
+        .ok_or_else(|| CommandError::invalid_input(format!("Action {id} has an invalid format")))?;
+    map.insert("enabled".into(), Value::Bool(enabled));
+    Ok(())
+}
+
+/// Transcription language of an action; `None` means the global `speech_language` applies.
+pub fn action_language(action: &Value) -> Option<String> {
+    action
+        .get("language")
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn set_action_language(actions: &mut [Value], id: &str, language: Option<String>) -> Result<()> {
+    let action = actions
+        .iter_mut()
+        .find(|action| action_id(action) == Some(id))
+        .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+    let map = action
+        .as_object_mut()
+        .ok_or_else(|| CommandError::invalid_input(format!("Action {id} has an invalid format")))?;
+    match language.map(|value| value.trim().to_ascii_lowercase()).filter(|value| !value.is_empty()) {
+        Some(language) => {
+            map.insert("language".into(), Value::String(language));
+        }
+        None => {
+            map.remove("language");
+        }
+    }
+    Ok(())
+}
+
+/// `None` removes the override so the global completion sound is played again.
+pub fn set_action_completion_sound(actions: &mut [Value], id: &str, sound: Option<String>) -> Result<()> {
+    let action = actions
+        .iter_mut()
+        .find(|action| action_id(action) == Some(id))
+        .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+    let map = action
+        .as_object_mut()
+        .ok_or_else(|| CommandError::invalid_input(format!("Action {id} has an invalid format")))?;
+    match sound {
+        Some(sound) => {
+            map.insert("completion_sound".into(), Value::String(sound));
+        }
+        None => {
+            map.remove("completion_sound");
+        }
+    }
+    Ok(())
+}
+
+/// Removes the accelerator of every action, both in the flat list and inside groups.
+/// Returns how many actions had one.
+pub fn clear_action_hotkeys(actions: &mut [Value], groups: &mut [Value]) -> usize {
+    let group_actions = groups
+        .iter_mut()
+        .filter_map(|group| group.get_mut("actions").and_then(|value| value.as_array_mut()))
+        .flat_map(|list| list.iter_mut());
+    let mut cleared = 0;
+    for action in actions.iter_mut().chain(group_actions) {
+        let Some(map) = action.as_object_mut() else {
+            continue;
+        };
+        let had_hotkey = map
+            .remove("hotkey")
+            .and_then(|value| value.as_str().map(|hotkey| !hotkey.trim().is_empty()))
+            .unwrap_or(false);
+        if had_hotkey {
+            cleared += 1;
+        }
+    }
+    cleared
+}
+
+/// Keeps local-only fields of actions when the list is replaced by a server sync.
+pub fn carry_local_fields(previous: &[Value], next: &mut [Value]) {
+    for action in next.iter_mut() {
+        let Some(id) = action_id(action).map(str::to_string) else {
+            continue;
+        };
+        let Some(old) = previous.iter().find(|old| action_id(old) == Some(id.as_str())) else {
+            continue;
+        };
+        let Some(map) = action.as_object_mut() else {
+            continue;
+        };
+        for field in LOCAL_ONLY_FIELDS {
+            if map.contains_key(field) {
+                continue;
+            }
+            if let Some(value) = old.get(field) {
+                map.insert(field.to_string(), value.clone());
+            }
+        }
+    }
+}
+
+/// Moves the listed actions to the front in the given order.
+/// Actions missing from `ids` keep their relative order after them.
+pub fn reorder_actions(actions: &mut Vec<Value>, ids: &[String]) -> Result<()> {
+    let mut remaining = std::mem::take(actions);
+    let mut ordered = Vec::with_capacity(remaining.len());
+    for id in ids {
+        let index = remaining
+            .iter()
+            .position(|action| action_id(action) == Some(id.as_str()))
+            .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+        ordered.push(remaining.remove(index));
+    }
+    ordered.extend(remaining);
+    *actions = ordered;
+    Ok(())
+}
+
+/// Serializes actions into a shareable pack without ids, hotkeys and profile bindings.
+pub fn export_actions(actions: &[Value]) -> Result<String> {
+    let pack: Vec<Value> = actions
+        .iter()
+        .map(|action| {
+            let mut exported = action.clone();
+            if let Some(map) = exported.as_object_mut() {
+                for field in PACK_EXCLUDED_FIELDS {
+                    map.remove(field);
+                }
+            }
+            exported
+        })
+        .collect();
+    serde_json::to_string_pretty(&pack).context("serialize actions pack")
+}
+
+/// Parses and validates an actions pack, assigning fresh ids to every action.
+pub fn parse_actions_pack(json: &str) -> Result<Vec<Value>> {
+    let parsed: Value = serde_json::from_str(json).context("parse actions pack")?;
+    let items = parsed
+        .as_array()
+        .ok_or_else(|| anyhow!("Actions pack must be a JSON array"))?;
+    let mut imported = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let mut map = item
+            .as_object()
+            .cloned()
+            .ok_or_else(|| anyhow!("Action #{} must be an object", index + 1))?;
+        let name = map
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+        if name.is_empty() {
+            return Err(anyhow!("Action #{} has no name", index + 1));
+        }
+        if !map.get("prompt").map(Value::is_string).unwrap_or(false) {
+            return Err(anyhow!("Action \"{name}\" has no prompt"));
+        }
+        for field in PACK_EXCLUDED_FIELDS {
+            map.remove(field);
+        }
+        map.insert("id".into(), Value::String(Uuid::new_v4().to_string()));
+        map.insert("name".into(), Value::String(name));
+        imported.push(Value::Object(map));
+    }
+    Ok(imported)
+}
+
+pub fn import_actions(actions: &mut Vec<Value>, imported: Vec<Value>, merge: bool) {
+    if merge {
+        actions.extend(imported);
+    } else {
+        *actions = imported;
+    }
+}
+
+pub async fn record_usage(app: &AppHandle, action_id: &str) -> Result<ActionUsageEntry> {
+    let trimmed = action_id.trim();
+    if trimmed.is_empty() {
+        return Err(CommandError::invalid_input("Action id cannot be empty").into());
+    }
+    let mut entries = read_usage(app).await.unwrap_or_default();
+    let now = Utc::now().to_rfc3339();
+    let entry = match entries.iter_mut().find(|entry| entry.action_id == trimmed) {
+        Some(existing) => {
+            existing.count += 1;
+            existing.last_used_at = now;
+            existing.clone()
+        }
+        None => {
+            let created = ActionUsageEntry {
+                action_id: trimmed.to_string(),
+                count: 1,
+                last_used_at: now,
+            };
+            entries.push(created.clone());
+            created
+        }
+    };
+    write_usage(app, &entries).await?;
+    Ok(entry)
+}
+
+/// Usage entries for actions that still exist in the config, most recent first.
+pub async fn recent_actions(app: &AppHandle, config: &AppConfig, limit: usize) -> Result<Vec<ActionUsageEntry>> {
+    let mut entries = known_usage(app, config).await?;
+    entries.sort_by(|lhs, rhs| rhs.last_used_at.cmp(&lhs.last_used_at));
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+/// Usage entries for actions that still exist in the config, highest count first.
+pub async fn most_used_actions(app: &AppHandle, config: &AppConfig, limit: usize) -> Result<Vec<ActionUsageEntry>> {
+    let mut entries = known_usage(app, config).await?;
+    entries.sort_by(|lhs, rhs| {
+        rhs.count
+            .cmp(&lhs.count)
+            .then_with(|| rhs.last_used_at.cmp(&lhs.last_used_at))
+    });
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+async fn known_usage(app: &AppHandle, config: &AppConfig) -> Result<Vec<ActionUsageEntry>> {
+    let entries = read_usage(app).await?;
+    Ok(entries
+        .into_iter()
+        .filter(|entry| find_action(config, &entry.action_id).is_some())
+        .collect())
+}
@@ -1,5 +1,6 @@
 //! Module for completion-sound playback via native APIs.
 
+use serde::Serialize;
 use tauri::AppHandle;
 
 use crate::{logging, resources};
@@ -12,14 +13,201 @@ const SND_ASYNC: u32 = 0x0001;
 #[cfg(target_os = "windows")]
 const SND_NODEFAULT: u32 = 0x0002;
 
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[allow(dead_code)]
+struct WaveOutCaps {
+    w_mid: u16,
+    w_pid: u16,
+    v_driver_version: u32,
+    sz_pname: [u16; 32],
+    dw_formats: u32,
+    w_channels: u16,
+    w_reserved1: u16,
+    dw_support: u32,
+}
+
 #[cfg(target_os = "windows")]
 #[link(name = "winmm")]
 extern "system" {
     fn PlaySoundW(pszSound: *const u16, hmod: *mut std::ffi::c_void, fdwSound: u32) -> i32;
+    fn waveOutSetVolume(hwo: *mut std::ffi::c_void, dwVolume: u32) -> u32;
+    fn waveOutGetNumDevs() -> u32;
+    fn waveOutGetDevCapsW(uDeviceID: usize, pwoc: *mut WaveOutCaps, cbwoc: u32) -> u32;
+    fn mciSendStringW(
+        lpstrCommand: *const u16,
+        lpstrReturnString: *mut u16,
+        uReturnLength: u32,
+        hwndCallback: *mut std::ffi::c_void,
+    ) -> u32;
+}
+
+pub const DEFAULT_COMPLETION_SOUND: &str = "completion.wav";
+
+/// Output device as reported by the OS; `name` is what `sound_output_device` stores.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioOutputDevice {
+    pub id: String,
+    pub name: String,
+}
+
+/// Нормализует имя устройства из конфига: пустая строка = системное устройство по умолчанию.
+fn requested_device(device: Option<&str>) -> Option<&str> {
+    device.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(target_os = "windows")]
+pub fn output_devices() -> Result<Vec<AudioOutputDevice>, String> {
+    let count = unsafe { waveOutGetNumDevs() };
+    let mut devices = Vec::new();
+    for index in 0..count {
+        let mut caps: WaveOutCaps = unsafe { std::mem::zeroed() };
+        let result = unsafe {
+            waveOutGetDevCapsW(
+                index as usize,
+                &mut caps,
+                std::mem::size_of::<WaveOutCaps>() as u32,
+            )
+        };
+        if result != 0 {
+            logging::log_message(&format!("[Audio] waveOutGetDevCaps failed for device {index}: {result}"));
+            continue;
+        }
+        let len = caps.sz_pname.iter().position(|ch| *ch == 0).unwrap_or(caps.sz_pname.len());
+        devices.push(AudioOutputDevice {
+            id: index.to_string(),
+            name: String::from_utf16_lossy(&caps.sz_pname[..len]),
+        });
+    }
+    Ok(devices)
+}
+
+#[cfg(target_os = "macos")]
+pub fn output_devices() -> Result<Vec<AudioOutputDevice>, String> {
+    use std::process::Command;
+
+    let output = Command::new("system_profiler")
+        .args(["SPAudioDataType", "-json"])
+        .output()
+        .map_err(|error| format!("Failed to run system_profiler: {error}"))?;
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("Failed to parse system_profiler output: {error}"))?;
+    let devices = parsed["SPAudioDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group["_items"].as_array())
+        .flatten()
+        .filter(|item| item.get("coreaudio_device_output").is_some())
+        .filter_map(|item| item["_name"].as_str())
+        .map(|name| AudioOutputDevice {
+            id: name.to_string(),
+            name: name.to_string(),
+        })
+        .collect();
+    Ok(devices)
+}
+
+#[cfg(target_os = "linux")]
+pub fn output_devices() -> Result<Vec<AudioOutputDevice>, String> {
+    use std::process::Command;
+
+    let output = Command::new("pactl")
+        .args(["list", "short", "sinks"])
+        .output()
+        .map_err(|error| format!("Failed to run pactl: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "pactl exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // Формат строки: "<index>\t<name>\t<driver>\t<format>\t<state>"
+    let devices = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split('\t');
+            let id = columns.next()?.trim();
+            let name = columns.next()?.trim();
+            (!name.is_empty()).then(|| AudioOutputDevice {
+                id: id.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .collect();
+    Ok(devices)
+}
+
+#[cfg(target_os = "windows")]
+fn mci_send(command: &str) -> Result<(), String> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    let wide: Vec<u16> = OsStr::new(command)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let result = unsafe { mciSendStringW(wide.as_ptr(), std::ptr::null_mut(), 0, std::ptr::null_mut()) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!("MCI command '{command}' failed with code {result}"))
+    }
+}
+
+/// PlaySound умеет играть только на устройстве по умолчанию, поэтому для выбранного
+/// устройства используем MCI waveaudio с явным `output`.
+#[cfg(target_os = "windows")]
+fn play_on_device(path: &str, device_index: u32, volume: f32) -> Result<(), String> {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+
+    static NEXT_ALIAS: AtomicU32 = AtomicU32::new(0);
+    let alias = format!("winky_sound_{}", NEXT_ALIAS.fetch_add(1, Ordering::Relaxed));
+
+    let level = (volume * f32::from(u16::MAX)) as u32;
+    unsafe {
+        waveOutSetVolume(device_index as usize as *mut std::ffi::c_void, level | (level << 16));
+    }
+
+    let path = path.to_string();
+    let (ready_tx, ready_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let opened = mci_send(&format!("open \"{path}\" type waveaudio alias {alias}"))
+            .and_then(|_| {
+                mci_send(&format!("set {alias} output {device_index}")).inspect_err(|_| {
+                    let _ = mci_send(&format!("close {alias}"));
+                })
+            });
+        let ok = opened.is_ok();
+        let _ = ready_tx.send(opened);
+        if ok {
+            if let Err(error) = mci_send(&format!("play {alias} wait")) {
+                logging::log_message(&format!("[Audio] {error}"));
+            }
+            let _ = mci_send(&format!("close {alias}"));
+        }
+    });
+    ready_rx
+        .recv()
+        .map_err(|_| "Sound playback thread exited unexpectedly".to_string())?
+}
+
+/// Volume is clamped to 0.0..=1.0; `None` plays at full volume.
+fn clamp_volume(volume: Option<f32>) -> f32 {
+    volume.unwrap_or(1.0).clamp(0.0, 1.0)
 }
 
 #[cfg(target_os = "windows")]
-pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String> {
+pub fn play_sound_sync(
+    app: &AppHandle,
+    sound_name: &str,
+    volume: Option<f32>,
+    device: Option<&str>,
+) -> Result<(), String> {
     use std::ffi::OsStr;
     use std::os::windows::ffi::OsStrExt;
     use std::thread;
@@ -28,6 +216,35 @@ pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String>
     let path = resources::resolve_sound_path(app, sound_name)
         .ok_or_else(|| format!("Sound {sound_name} not found"))?;
 
+    if let Some(device) = requested_device(device) {
+        let index = output_devices()?
+            .into_iter()
+            .find(|candidate| candidate.name == device)
+            .and_then(|candidate| candidate.id.parse::<u32>().ok());
+        match index {
+            Some(index) => match play_on_device(&path, index, clamp_volume(volume)) {
+                Ok(()) => {
+                    let message = format!("[Audio] Playing sound on {device}: {path}");
+                    logging::log_message(&message);
+                    println!("{}", message);
+                    return Ok(());
+                }
+                Err(error) => logging::log_message(&format!(
+                    "[Audio] Failed to play on {device}, falling back to default device: {error}"
+                )),
+            },
+            None => logging::log_message(&format!(
+                "[Audio] Output device {device} not found, using default device"
+            )),
+        }
+    }
+
+    // Громкость PlaySound задаётся для всего процесса, поэтому выставляем её каждый раз
+    let level = (clamp_volume(volume) * f32::from(u16::MAX)) as u32;
+    unsafe {
+        waveOutSetVolume(std::ptr::null_mut(), level | (level << 16));
+    }
+
     let wide: Vec<u16> = OsStr::new(&path)
         .encode_wide()
         .chain(std::iter::once(0))
@@ -65,13 +282,27 @@ pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String>
 }
 
 #[cfg(target_os = "macos")]
-pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String> {
+pub fn play_sound_sync(
+    app: &AppHandle,
+    sound_name: &str,
+    volume: Option<f32>,
+    device: Option<&str>,
+) -> Result<(), String> {
     use std::process::Command;
 
     let path = resources::resolve_sound_path(app, sound_name)
         .ok_or_else(|| format!("Sound {sound_name} not found"))?;
 
+    if let Some(device) = requested_device(device) {
+        // afplay не умеет выбирать устройство вывода
+        logging::log_message(&format!(
+            "[Audio] Output device selection is not supported on macOS, playing on default instead of {device}"
+        ));
+    }
+
     Command::new("afplay")
+        .arg("-v")
+        .arg(clamp_volume(volume).to_string())
         .arg(&path)
         .spawn()
         .map_err(|error| format!("Failed to play sound: {error}"))?;
@@ -83,15 +314,31 @@ pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String>
 }
 
 #[cfg(target_os = "linux")]
-pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String> {
+pub fn play_sound_sync(
+    app: &AppHandle,
+    sound_name: &str,
+    volume: Option<f32>,
+    device: Option<&str>,
+) -> Result<(), String> {
     use std::process::Command;
 
     let path = resources::resolve_sound_path(app, sound_name)
         .ok_or_else(|| format!("Sound {sound_name} not found"))?;
 
-    let players = ["paplay", "aplay", "play"];
-    for player in players {
-        if Command::new(player).arg(&path).spawn().is_ok() {
+    let volume = clamp_volume(volume);
+    let mut paplay_args = vec![format!("--volume={}", (volume * 65536.0) as u32)];
+    // Устройство выбирается только через paplay; aplay/play играют на устройстве по умолчанию
+    if let Some(device) = requested_device(device) {
+        paplay_args.push(format!("--device={device}"));
+    }
+    let players: [(&str, Vec<String>); 3] = [
+        ("paplay", paplay_args),
+        // aplay не умеет менять громкость, играет как есть
+        ("aplay", Vec::new()),
+        ("play", vec!["-v".to_string(), volume.to_string()]),
+    ];
+    for (player, args) in players {
+        if Command::new(player).args(&args).arg(&path).spawn().is_ok() {
             let message = format!("[Audio] Playing sound via {player}: {path}");
             logging::log_message(&message);
             println!("{}", message);
@@ -103,3 +350,219 @@ pub fn play_sound_sync(app: &AppHandle, sound_name: &str) -> Result<(), String>
     logging::log_message(&message);
     Err("No audio player found (tried paplay, aplay, play)".into())
 }
+
+/// Volume factor applied to other applications while ducking is active.
+#[cfg(target_os = "windows")]
+const DUCKED_VOLUME_FACTOR: f32 = 0.2;
+
+#[cfg(target_os = "windows")]
+static DUCKED_SESSIONS: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashMap<u32, f32>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+/// Интерфейсы WASAPI для аудиосессий, которых нет в winapi 0.3 (audiopolicy.h).
+#[cfg(target_os = "windows")]
+#[allow(non_snake_case, clippy::upper_case_acronyms)]
+mod audio_policy {
+    use winapi::ctypes::{c_float, c_int};
+    use winapi::shared::guiddef::{GUID, LPCGUID};
+    use winapi::shared::minwindef::{BOOL, DWORD};
+    use winapi::shared::winerror::HRESULT;
+    use winapi::um::audiosessiontypes::AudioSessionState;
+    use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+    use winapi::um::winnt::{LPCWSTR, LPWSTR};
+    use winapi::RIDL;
+
+    RIDL! {#[uuid(0x87ce5498, 0x68d6, 0x44e5, 0x92, 0x15, 0x6d, 0xa4, 0x7e, 0xf8, 0x83, 0xd8)]
+    interface ISimpleAudioVolume(ISimpleAudioVolumeVtbl): IUnknown(IUnknownVtbl) {
+        fn SetMasterVolume(fLevel: c_float, EventContext: LPCGUID,) -> HRESULT,
+        fn GetMasterVolume(pfLevel: *mut c_float,) -> HRESULT,
+        fn SetMute(bMute: BOOL, EventContext: LPCGUID,) -> HRESULT,
+        fn GetMute(pbMute: *mut BOOL,) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0xf4b1a599, 0x7266, 0x4319, 0xa8, 0xca, 0xe7, 0x0a, 0xcb, 0x11, 0xe8, 0xcd)]
+    interface IAudioSessionControl(IAudioSessionControlVtbl): IUnknown(IUnknownVtbl) {
+        fn GetState(pRetVal: *mut AudioSessionState,) -> HRESULT,
+        fn GetDisplayName(pRetVal: *mut LPWSTR,) -> HRESULT,
+        fn SetDisplayName(Value: LPCWSTR, EventContext: LPCGUID,) -> HRESULT,
+        fn GetIconPath(pRetVal: *mut LPWSTR,) -> HRESULT,
+        fn SetIconPath(Value: LPCWSTR, EventContext: LPCGUID,) -> HRESULT,
+        fn GetGroupingParam(pRetVal: *mut GUID,) -> HRESULT,
+        fn SetGroupingParam(Override: LPCGUID, EventContext: LPCGUID,) -> HRESULT,
+        // IAudioSessionEvents не используется, поэтому передаётся как IUnknown
+        fn RegisterAudioSessionNotification(NewNotifications: *mut IUnknown,) -> HRESULT,
+        fn UnregisterAudioSessionNotification(NewNotifications: *mut IUnknown,) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0xbfb7ff88, 0x7239, 0x4fc9, 0x8f, 0xa2, 0x07, 0xc9, 0x50, 0xbe, 0x9c, 0x6d)]
+    interface IAudioSessionControl2(IAudioSessionControl2Vtbl):
+        IAudioSessionControl(IAudioSessionControlVtbl) {
+        fn GetSessionIdentifier(pRetVal: *mut LPWSTR,) -> HRESULT,
+        fn GetSessionInstanceIdentifier(pRetVal: *mut LPWSTR,) -> HRESULT,
+        fn GetProcessId(pRetVal: *mut DWORD,) -> HRESULT,
+        fn IsSystemSoundsSession() -> HRESULT,
+        fn SetDuckingPreference(optOut: BOOL,) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0xe2f5bb11, 0x0570, 0x40ca, 0xac, 0xdd, 0x3a, 0xa0, 0x12, 0x77, 0xde, 0xe8)]
+    interface IAudioSessionEnumerator(IAudioSessionEnumeratorVtbl): IUnknown(IUnknownVtbl) {
+        fn GetCount(SessionCount: *mut c_int,) -> HRESULT,
+        fn GetSession(SessionCount: c_int, Session: *mut *mut IAudioSessionControl,) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0xbfa971f1, 0x4d5e, 0x40bb, 0x93, 0x5e, 0x96, 0x70, 0x39, 0xbf, 0xbe, 0xe4)]
+    interface IAudioSessionManager(IAudioSessionManagerVtbl): IUnknown(IUnknownVtbl) {
+        fn GetAudioSessionControl(
+            AudioSessionGuid: LPCGUID,
+            StreamFlags: DWORD,
+            SessionControl: *mut *mut IAudioSessionControl,
+        ) -> HRESULT,
+        fn GetSimpleAudioVolume(
+            AudioSessionGuid: LPCGUID,
+            StreamFlags: DWORD,
+            AudioVolume: *mut *mut ISimpleAudioVolume,
+        ) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0x77aa99a0, 0x1bd6, 0x484f, 0x8b, 0xc7, 0x2c, 0x65, 0x4c, 0x9a, 0x9b, 0x6f)]
+    interface IAudioSessionManager2(IAudioSessionManager2Vtbl):
+        IAudioSessionManager(IAudioSessionManagerVtbl) {
+        fn GetSessionEnumerator(SessionEnum: *mut *mut IAudioSessionEnumerator,) -> HRESULT,
+        // Уведомления о сессиях и приглушении не используются, поэтому передаются как IUnknown
+        fn RegisterSessionNotification(SessionNotification: *mut IUnknown,) -> HRESULT,
+        fn UnregisterSessionNotification(SessionNotification: *mut IUnknown,) -> HRESULT,
+        fn RegisterDuckNotification(sessionID: LPCWSTR, duckNotification: *mut IUnknown,) -> HRESULT,
+        fn UnregisterDuckNotification(duckNotification: *mut IUnknown,) -> HRESULT,
+    }}
+}
+
+/// Lowers (or restores) the volume of every other application's audio session
+/// on the default output device. Original levels are remembered per process.
+#[cfg(target_os = "windows")]
+pub fn duck_other_sessions(enable: bool) -> Result<(), String> {
+    use winapi::shared::winerror::{FAILED, RPC_E_CHANGED_MODE};
+    use audio_policy::{
+        IAudioSessionControl, IAudioSessionControl2, IAudioSessionEnumerator, IAudioSessionManager2,
+        ISimpleAudioVolume,
+    };
+    use winapi::um::combaseapi::{CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL};
+    use winapi::um::mmdeviceapi::{
+        eMultimedia, eRender, CLSID_MMDeviceEnumerator, IMMDevice, IMMDeviceEnumerator,
+    };
+    use winapi::um::objbase::COINIT_MULTITHREADED;
+    use winapi::Interface;
+
+    let own_pid = std::process::id();
+    let mut saved = DUCKED_SESSIONS
+        .lock()
+        .map_err(|_| "Audio ducking state is poisoned".to_string())?;
+    if enable && !saved.is_empty() {
+        return Ok(());
+    }
+    if !enable && saved.is_empty() {
+        return Ok(());
+    }
+
+    unsafe {
+        let init = CoInitializeEx(std::ptr::null_mut(), COINIT_MULTITHREADED);
+        let should_uninit = !FAILED(init);
+        if FAILED(init) && init != RPC_E_CHANGED_MODE {
+            return Err(format!("CoInitializeEx failed: 0x{init:08X}"));
+        }
+
+        let result = (|| -> Result<(), String> {
+            let mut enumerator: *mut IMMDeviceEnumerator = std::ptr::null_mut();
+            let hr = CoCreateInstance(
+                &CLSID_MMDeviceEnumerator,
+                std::ptr::null_mut(),
+                CLSCTX_ALL,
+                &IMMDeviceEnumerator::uuidof(),
+                &mut enumerator as *mut _ as *mut _,
+            );
+            if FAILED(hr) {
+                return Err(format!("Failed to create device enumerator: 0x{hr:08X}"));
+            }
+            let mut device: *mut IMMDevice = std::ptr::null_mut();
+            let hr = (*enumerator).GetDefaultAudioEndpoint(eRender, eMultimedia, &mut device);
+            (*enumerator).Release();
+            if FAILED(hr) {
+                return Err(format!("Failed to get default output device: 0x{hr:08X}"));
+            }
+            let mut manager: *mut IAudioSessionManager2 = std::ptr::null_mut();
+            let hr = (*device).Activate(
+                &IAudioSessionManager2::uuidof(),
+                CLSCTX_ALL,
+                std::ptr::null_mut(),
+                &mut manager as *mut _ as *mut _,
+            );
+            (*device).Release();
+            if FAILED(hr) {
+                return Err(format!("Failed to open audio session manager: 0x{hr:08X}"));
+            }
+            let mut sessions: *mut IAudioSessionEnumerator = std::ptr::null_mut();
+            let hr = (*manager).GetSessionEnumerator(&mut sessions);
+            (*manager).Release();
+            if FAILED(hr) {
+                return Err(format!("Failed to enumerate audio sessions: 0x{hr:08X}"));
+            }
+            let mut count = 0;
+            (*sessions).GetCount(&mut count);
+            for index in 0..count {
+                let mut control: *mut IAudioSessionControl = std::ptr::null_mut();
+                if FAILED((*sessions).GetSession(index, &mut control)) || control.is_null() {
+                    continue;
+                }
+                let mut control2: *mut IAudioSessionControl2 = std::ptr::null_mut();
+                let hr = (*control).QueryInterface(
+                    &IAudioSessionControl2::uuidof(),
+                    &mut control2 as *mut _ as *mut _,
+                );
+                if FAILED(hr) {
+                    (*control).Release();
+                    continue;
+                }
+                let mut pid = 0u32;
+                (*control2).GetProcessId(&mut pid);
+                (*control2).Release();
+                if pid == own_pid || pid == 0 {
+                    (*control).Release();
+                    continue;
+                }
+                let mut volume: *mut ISimpleAudioVolume = std::ptr::null_mut();
+                let hr = (*control).QueryInterface(
+                    &ISimpleAudioVolume::uuidof(),
+                    &mut volume as *mut _ as *mut _,
+                );
+                (*control).Release();
+                if FAILED(hr) {
+                    continue;
+                }
+                if enable {
+                    let mut level = 0.0f32;
+                    if !FAILED((*volume).GetMasterVolume(&mut level)) {
+                        saved.insert(pid, level);
+                        (*volume).SetMasterVolume(level * DUCKED_VOLUME_FACTOR, std::ptr::null());
+                    }
+                } else if let Some(level) = saved.get(&pid) {
+                    (*volume).SetMasterVolume(*level, std::ptr::null());
+                }
+                (*volume).Release();
+            }
+            (*sessions).Release();
+            if !enable {
+                saved.clear();
+            }
+            Ok(())
+        })();
+
+        if should_uninit {
+            CoUninitialize();
+        }
+        result
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn duck_other_sessions(_enable: bool) -> Result<(), String> {
+    Err("Audio ducking is only supported on Windows".into())
+}
@@ -0,0 +1,132 @@
+//! Короткая запись с микрофона через ffmpeg, чтобы проверить, что он действительно слышит звук.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::error::CommandError;
+use crate::transcription;
+
+pub const MIN_DURATION_MS: u32 = 200;
+pub const MAX_DURATION_MS: u32 = 10_000;
+const SAMPLE_RATE: &str = "16000";
+/// Peak level (0..1) above which the clip counts as containing signal, about -40 dBFS.
+/// A blocked or muted microphone delivers digital silence, far below this.
+const SIGNAL_PEAK_THRESHOLD: f32 = 0.01;
+/// Extra time for ffmpeg to open the device on top of the recording itself.
+const STARTUP_GRACE: Duration = Duration::from_secs(10);
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioSelfTestResult {
+    /// Device that was recorded; `None` means the system default.
+    pub device: Option<String>,
+    pub duration_ms: u32,
+    /// Levels are normalized to 0..1 of full scale.
+    pub peak: f32,
+    pub rms: f32,
+    pub signal_detected: bool,
+}
+
+fn ffmpeg_command(ffmpeg: &str) -> Command {
+    let mut command = Command::new(ffmpeg);
+    command.args(["-hide_banner", "-nostdin"]);
+    #[cfg(windows)]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+/// DirectShow has no "default" device alias, so the first capture device is used.
+#[cfg(windows)]
+async fn input_args(ffmpeg: &str, device: Option<&str>) -> Result<Vec<String>> {
+    let name = match device {
+        Some(name) => name.to_string(),
+        None => {
+            let output = ffmpeg_command(ffmpeg)
+                .args(["-list_devices", "true", "-f", "dshow", "-i", "dummy"])
+                .output()
+                .await
+                .map_err(|error| anyhow!("Failed to run ffmpeg: {error}"))?;
+            // ffmpeg печатает список в stderr и завершается с ошибкой из-за входа `dummy`
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .filter(|line| line.contains("(audio)"))
+                .find_map(|line| line.split('"').nth(1).map(str::to_string))
+                .ok_or_else(|| anyhow!("No microphone was found"))?
+        }
+    };
+    Ok(vec!["-f".into(), "dshow".into(), "-i".into(), format!("audio={name}")])
+}
+
+#[cfg(target_os = "macos")]
+async fn input_args(_ffmpeg: &str, device: Option<&str>) -> Result<Vec<String>> {
+    let input = format!(":{}", device.unwrap_or("default"));
+    Ok(vec!["-f".into(), "avfoundation".into(), "-i".into(), input])
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+async fn input_args(_ffmpeg: &str, device: Option<&str>) -> Result<Vec<String>> {
+    let input = device.unwrap_or("default").to_string();
+    Ok(vec!["-f".into(), "pulse".into(), "-i".into(), input])
+}
+
+/// Peak and RMS of signed 16-bit little-endian PCM, normalized to 0..1.
+fn levels(pcm: &[u8]) -> (f32, f32) {
+    let mut peak = 0f32;
+    let mut sum_squares = 0f64;
+    let mut count = 0usize;
+    for chunk in pcm.chunks_exact(2) {
+        let sample = i16::from_le_bytes([chunk[0], chunk[1]]) as f32 / i16::MAX as f32;
+        peak = peak.max(sample.abs());
+        sum_squares += (sample as f64) * (sample as f64);
+        count += 1;
+    }
+    if count == 0 {
+        return (0.0, 0.0);
+    }
+    (peak.min(1.0), (sum_squares / count as f64).sqrt() as f32)
+}
+
+/// Records `duration_ms` from `device` (or the default input) and measures its level.
+pub async fn run(device: Option<&str>, duration_ms: u32) -> Result<AudioSelfTestResult> {
+    let ffmpeg = transcription::ffmpeg_path()
+        .await
+        .ok_or_else(|| CommandError::not_found("ffmpeg is required for the microphone test but was not found"))?;
+    let device = device.map(str::trim).filter(|name| !name.is_empty());
+    let seconds = format!("{:.3}", duration_ms as f64 / 1000.0);
+
+    let mut command = ffmpeg_command(ffmpeg);
+    command
+        .args(["-loglevel", "error"])
+        .args(input_args(ffmpeg, device).await?)
+        .args(["-t", seconds.as_str(), "-ac", "1", "-ar", SAMPLE_RATE, "-f", "s16le", "-"])
+        .kill_on_drop(true);
+
+    let timeout = Duration::from_millis(duration_ms as u64) + STARTUP_GRACE;
+    let output = tokio::time::timeout(timeout, command.output())
+        .await
+        .map_err(|_| anyhow!("The microphone did not respond in time"))?
+        .map_err(|error| anyhow!("Failed to run ffmpeg: {error}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Recording failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let (peak, rms) = levels(&output.stdout);
+    Ok(AudioSelfTestResult {
+        device: device.map(str::to_string),
+        duration_ms,
+        peak,
+        rms,
+        signal_detected: peak >= SIGNAL_PEAK_THRESHOLD,
+    })
+}
@@ -27,6 +27,39 @@ impl AuthQueue {
         guard.clear();
         drained
     }
+
+    /// Snapshot of the queue for debugging; tokens are replaced by their length.
+    pub async fn peek_redacted(&self) -> Vec<AuthDeepLinkPayload> {
+        self.pending
+            .lock()
+            .await
+            .iter()
+            .cloned()
+            .map(redact_payload)
+            .collect()
+    }
+}
+
+fn redact_token(token: &str) -> String {
+    format!("<redacted, {} chars>", token.chars().count())
+}
+
+fn redact_payload(payload: AuthDeepLinkPayload) -> AuthDeepLinkPayload {
+    match payload {
+        AuthDeepLinkPayload::Success {
+            provider,
+            tokens,
+            user,
+        } => AuthDeepLinkPayload::Success {
+            provider,
+            tokens: AuthTokensPayload {
+                access: redact_token(&tokens.access),
+                refresh: tokens.refresh.as_deref().map(redact_token),
+            },
+            user,
+        },
+        other => other,
+    }
 }
 
 pub async fn handle_deep_link(app: AppHandle, queue: Arc<AuthQueue>, url: String) {
@@ -1,14 +1,73 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use flate2::Crc;
+use serde::Serialize;
 use serde_json::{Map, Value};
 use tauri::{AppHandle, Manager};
 use tokio::fs;
 use tokio::sync::RwLock;
 
-use crate::constants::CONFIG_FILE_NAME;
+use crate::actions;
+use crate::constants::{CONFIG_CHECKSUM_SUFFIX, CONFIG_FILE_NAME};
+use crate::logging;
 use crate::types::{AppConfig, AuthTokens, WindowPosition};
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigUpdateResult {
+    pub config: AppConfig,
+    pub changed_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigIntegrity {
+    pub matches: bool,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(CONFIG_CHECKSUM_SUFFIX);
+    path.with_file_name(name)
+}
+
+fn compute_checksum(contents: &str) -> String {
+    let mut crc = Crc::new();
+    crc.update(contents.as_bytes());
+    format!("{:08x}", crc.sum())
+}
+
+async fn read_checksum(path: &Path) -> Option<String> {
+    fs::read_to_string(checksum_path(path))
+        .await
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn write_config_file(path: &Path, serialized: &str) -> Result<()> {
+    fs::write(path, serialized).await.context("write config")?;
+    let checksum_file = checksum_path(path);
+    fs::write(&checksum_file, compute_checksum(serialized))
+        .await
+        .with_context(|| format!("write config checksum to {}", checksum_file.display()))
+}
+
+async fn backup_corrupt_config(path: &Path) -> Result<PathBuf> {
+    let backup = path.with_file_name(format!(
+        "config.corrupt-{}.json",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::rename(path, &backup)
+        .await
+        .with_context(|| format!("back up corrupt config to {}", backup.display()))?;
+    Ok(backup)
+}
+
 #[derive(Debug)]
 pub struct ConfigState {
     inner: RwLock<AppConfig>,
@@ -28,14 +87,44 @@ impl ConfigState {
         let path = dir;
         let config = if Path::new(&path).exists() {
             let contents = fs::read_to_string(&path).await?;
-            let mut config: AppConfig = serde_json::from_str(&contents).unwrap_or_default();
+            // Несовпадение контрольной суммы только логируем: конфиг мог поправить сам пользователь
+            match read_checksum(&path).await {
+                Some(expected) if expected != compute_checksum(&contents) => {
+                    logging::log_message(&format!(
+                        "[config] Checksum mismatch for {}: file was modified outside the app or is corrupted",
+                        path.display()
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    if let Err(error) = fs::write(checksum_path(&path), compute_checksum(&contents)).await {
+                        logging::log_message(&format!("[config] Failed to write config checksum: {error}"));
+                    }
+                }
+            }
+            let mut config: AppConfig = match serde_json::from_str(&contents) {
+                Ok(config) => config,
+                Err(error) => {
+                    // Битый конфиг не перезаписываем молча: сохраняем копию для восстановления
+                    // Неудачная копия не должна мешать запуску: всё равно переходим на значения по умолчанию
+                    let backup = match backup_corrupt_config(&path).await {
+                        Ok(backup) => format!("Backed up to {}", backup.display()),
+                        Err(backup_error) => format!("Backup failed: {backup_error:#}"),
+                    };
+                    logging::log_message(&format!(
+                        "[config] Failed to parse {}: {error}. {backup}; fell back to defaults",
+                        path.display()
+                    ));
+                    AppConfig::default()
+                }
+            };
             config.normalize();
             config
         } else {
             let mut config = AppConfig::default();
             config.normalize();
             let serialized = serde_json::to_string_pretty(&config)?;
-            fs::write(&path, serialized).await?;
+            write_config_file(&path, &serialized).await?;
             config
         };
 
@@ -62,11 +151,32 @@ impl ConfigState {
         Ok(normalized)
     }
 
-    pub async fn update(&self, partial: Value) -> Result<AppConfig> {
+    pub async fn update(&self, partial: Value) -> Result<ConfigUpdateResult> {
         let mut guard = self.inner.write().await;
-        let mut current = serde_json::to_value(&*guard)?;
+        let before = serde_json::to_value(&*guard)?;
+        let mut current = before.clone();
         merge_values(&mut current, partial);
         let mut next: AppConfig = serde_json::from_value(current)?;
+        actions::carry_local_fields(&guard.actions, &mut next.actions);
+        next.normalize();
+        let after = serde_json::to_value(&next)?;
+        let mut changed_paths = Vec::new();
+        collect_changed_paths(&before, &after, "", &mut changed_paths);
+        self.persist(&next).await?;
+        *guard = next.clone();
+        Ok(ConfigUpdateResult {
+            config: next,
+            changed_paths,
+        })
+    }
+
+    pub async fn update_with<F>(&self, apply: F) -> Result<AppConfig>
+    where
+        F: FnOnce(&mut AppConfig) -> Result<()>,
+    {
+        let mut guard = self.inner.write().await;
+        let mut next = guard.clone();
+        apply(&mut next)?;
         next.normalize();
         self.persist(&next).await?;
         *guard = next.clone();
@@ -113,9 +223,25 @@ impl ConfigState {
         self.persist(&guard).await
     }
 
+    /// Compares the config file on disk with the checksum stored next to it.
+    pub async fn verify_integrity(&self) -> Result<ConfigIntegrity> {
+        // Read-лок не даёт проверке пересечься с записью конфига
+        let _guard = self.inner.read().await;
+        let contents = fs::read_to_string(&self.path)
+            .await
+            .with_context(|| format!("read config from {}", self.path.display()))?;
+        let actual = compute_checksum(&contents);
+        let expected = read_checksum(&self.path).await;
+        Ok(ConfigIntegrity {
+            matches: expected.as_deref() == Some(actual.as_str()),
+            expected,
+            actual,
+        })
+    }
+
     async fn persist(&self, state: &AppConfig) -> Result<()> {
         let serialized = serde_json::to_string_pretty(state).context("serialize config")?;
-        fs::write(&self.path, serialized).await.context("write config")
+        write_config_file(&self.path, &serialized).await
     }
 }
 
@@ -137,8 +263,42 @@ fn merge_values(target: &mut Value, patch: Value) {
     }
 }
 
+/// Собирает пути (через точку) всех отличающихся полей. Массивы сравниваются целиком.
+fn collect_changed_paths(before: &Value, after: &Value, prefix: &str, out: &mut Vec<String>) {
+    match (before, after) {
+        (Value::Object(before_map), Value::Object(after_map)) => {
+            let mut keys: Vec<&String> = before_map.keys().chain(after_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                let before_value = before_map.get(key).unwrap_or(&Value::Null);
+                let after_value = after_map.get(key).unwrap_or(&Value::Null);
+                collect_changed_paths(before_value, after_value, &path, out);
+            }
+        }
+        _ => {
+            if before != after {
+                out.push(prefix.to_string());
+            }
+        }
+    }
+}
+
+/// Returns the default value of a config field addressed by a dotted camelCase path (`auth.accessToken`).
+pub fn default_field_value(path: &str) -> Result<Option<Value>> {
+    let defaults = serde_json::to_value(AppConfig::default()).context("serialize default config")?;
+    let pointer = format!("/{}", path.trim().replace('.', "/"));
+    Ok(defaults.pointer(&pointer).cloned())
+}
+
 pub fn should_auto_start_local_speech(config: &AppConfig) -> bool {
     config.auto_start_local_speech_server
+        && !config.local_speech_lazy_start
         && config.setup_completed
         && config.speech.mode == "local"
 }
@@ -7,6 +7,7 @@ pub const DEFAULT_BACKEND_DOMAIN: &str = BACKEND_DOMAIN_COM;
 pub const SITE_BASE_URL: &str = "https://xlartas.com";
 #[allow(dead_code)]
 pub const API_BASE_URL: &str = "https://xlartas.com/api/v1";
+pub const RELEASES_DOWNLOAD_URL: &str = "https://github.com/Artasov/winky/releases/latest";
 #[allow(dead_code)]
 pub const API_BASE_URL_FALLBACK_LOCAL: &str = "http://127.0.0.1:8000/api/v1";
 pub const FAST_WHISPER_REPO_URL: &str = "https://github.com/Artasov/fast-fast-whisper.git";
@@ -23,10 +24,34 @@ pub const FAST_WHISPER_INSTALL_ENV_VAR: &str = "WINKY_LOCAL_SPEECH_DIR";
 // even if the environment variable was not exported into the current process.
 pub const FAST_WHISPER_INSTALL_HINT_FILE: &str = "local-speech-path.txt";
 pub const CONFIG_FILE_NAME: &str = "config.json";
+pub const CONFIG_CHECKSUM_SUFFIX: &str = ".crc32";
 
 pub const DEFAULT_SPEECH_MODEL: &str = "gpt-4o-mini-transcribe";
+pub const SPEECH_MODES: [&str; 2] = ["api", "local"];
 pub const DEFAULT_LLM_MODEL: &str = "o4-mini";
+pub const LLM_MODES: [&str; 2] = ["api", "local"];
+/// Синхронизировано с LLM_API_MODELS в src/shared/constants.ts.
+pub const LLM_API_MODELS: [&str; 24] = [
+    "winky-high", "winky-mid", "winky-low",
+    "o4-mini", "gpt-4.1-mini", "gpt-4.1-nano", "o3-mini", "o1-mini", "gpt-4o-mini",
+    "gpt-4-turbo", "chatgpt-4o-latest", "gpt-3.5-turbo", "gpt-5", "gpt-5-mini", "gpt-5-nano",
+    "gemini-2.5-flash", "gemini-3.0-pro", "gemini-3.0-flash", "gemini-2.5-pro", "gemini-2.0-pro",
+    "gemini-2.0-flash", "gemini-1.5-pro", "gemini-1.5-flash", "gemini-1.0-pro",
+];
+/// Синхронизировано с LLM_LOCAL_MODELS в src/shared/constants.ts.
+pub const LLM_LOCAL_MODELS: [&str; 11] = [
+    "gpt-oss:120b", "gpt-oss:20b", "gemma3:27b", "gemma3:12b", "gemma3:4b", "gemma3:1b",
+    "deepseek-r1:8b", "qwen3-coder:30b", "qwen3:30b", "qwen3:8b", "qwen3:4b",
+];
 pub const DEFAULT_MIC_ANCHOR: &str = "bottom-right";
+pub const DEFAULT_HOTKEY_DEBOUNCE_MS: u64 = 300;
+pub const DEFAULT_HEALTH_POLL_INTERVAL_MS: u64 = 5_000;
+pub const COMPLETION_BEHAVIORS: [&str; 4] = ["copy", "paste", "copy_and_notify", "paste_stream"];
+pub const OVERLAY_THEMES: [&str; 3] = ["default", "minimal", "avatar"];
+pub const DEFAULT_OVERLAY_THEME: &str = "default";
+/// `none` leaves the prompt untouched; the other formats append an instruction.
+pub const LLM_OUTPUT_FORMATS: [&str; 3] = ["none", "text", "markdown"];
+pub const DEFAULT_LLM_OUTPUT_FORMAT: &str = "none";
 
 #[allow(dead_code)]
 pub const MIC_WINDOW_WIDTH: f64 = 520.0;
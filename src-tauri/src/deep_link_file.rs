@@ -6,7 +6,7 @@
 //! Этот модуль использует файл для передачи URL между процессами.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::time::{Duration, interval};
 use tauri::{AppHandle, Manager};
@@ -16,6 +16,9 @@ use crate::logging;
 
 const DEEP_LINK_FILE_NAME: &str = "pending_deep_link.txt";
 const POLL_INTERVAL_MS: u64 = 500;
+/// Попытки записи: файл может быть кратко заблокирован антивирусом или читающим процессом
+const WRITE_ATTEMPTS: u32 = 5;
+const WRITE_RETRY_DELAY_MS: u64 = 100;
 
 /// Получает путь к файлу для deep link
 fn get_deep_link_file_path(app: &AppHandle) -> Option<PathBuf> {
@@ -40,17 +43,39 @@ pub fn write_deep_link_to_file(url: &str) -> Result<(), String> {
     let file_path = get_deep_link_file_path_standalone()
         .ok_or_else(|| "Failed to get deep link file path".to_string())?;
     
+    let mut attempt = 1;
+    loop {
+        match try_write_deep_link_file(&file_path, url) {
+            Ok(()) => break,
+            Err(error) if attempt < WRITE_ATTEMPTS => {
+                println!(
+                    "[DeepLinkFile] Attempt {}/{} failed: {}; retrying",
+                    attempt, WRITE_ATTEMPTS, error
+                );
+                std::thread::sleep(std::time::Duration::from_millis(
+                    WRITE_RETRY_DELAY_MS * u64::from(attempt),
+                ));
+                attempt += 1;
+            }
+            Err(error) => {
+                return Err(format!("{} (after {} attempts)", error, WRITE_ATTEMPTS));
+            }
+        }
+    }
+    
+    println!("[DeepLinkFile] Wrote URL to file: {}", file_path.display());
+    Ok(())
+}
+
+fn try_write_deep_link_file(file_path: &Path, url: &str) -> Result<(), String> {
     // Создаём директорию если не существует
     if let Some(parent) = file_path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create directory: {}", e))?;
     }
     
-    fs::write(&file_path, url)
-        .map_err(|e| format!("Failed to write deep link file: {}", e))?;
-    
-    println!("[DeepLinkFile] Wrote URL to file: {}", file_path.display());
-    Ok(())
+    fs::write(file_path, url)
+        .map_err(|e| format!("Failed to write deep link file: {}", e))
 }
 
 /// Читает и удаляет deep link URL из файла
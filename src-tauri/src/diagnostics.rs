@@ -0,0 +1,151 @@
+//! Диагностический архив для баг-репортов: логи, конфиг без секретов и снимки состояния.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use crate::auth::AuthQueue;
+use crate::config::ConfigState;
+use crate::local_speech::FastWhisperManager;
+use crate::{logging, system_dependencies};
+
+pub(crate) const REDACTED: &str = "<redacted>";
+/// Объекты, все строковые значения которых считаются секретами.
+const SECRET_SECTIONS: [&str; 2] = ["apiKeys", "auth"];
+/// Подстроки имён полей, значения которых считаются секретами в любом месте конфига.
+const SECRET_KEY_MARKERS: [&str; 3] = ["token", "secret", "password"];
+
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let lowered = key.to_ascii_lowercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| lowered.contains(marker))
+}
+
+/// Replaces every secret string in a serialized config with `<redacted>`; empty values stay empty.
+pub fn redact_secrets(value: &mut Value, redact_all: bool) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let secret = redact_all || SECRET_SECTIONS.contains(&key.as_str()) || is_secret_key(key);
+                redact_secrets(child, secret);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_secrets(item, redact_all);
+            }
+        }
+        Value::String(text) if redact_all && !text.is_empty() => {
+            *text = REDACTED.to_string();
+        }
+        _ => {}
+    }
+}
+
+fn pretty(value: &impl serde::Serialize) -> Result<Vec<u8>> {
+    serde_json::to_vec_pretty(value).context("serialize diagnostics entry")
+}
+
+/// Collects logs, redacted config and status snapshots into
+/// `winky-diagnostics-{timestamp}.zip` inside `target_dir`.
+pub async fn create_bundle(app: &AppHandle, target_dir: &Path) -> Result<PathBuf> {
+    let config_state = app.state::<Arc<ConfigState>>().inner().clone();
+    let manager = app.state::<Arc<FastWhisperManager>>().inner().clone();
+    let auth_queue = app.state::<Arc<AuthQueue>>().inner().clone();
+
+    let config = config_state.get().await;
+    let config_path = config_state.path().await;
+    let integrity = config_state.verify_integrity().await.ok();
+    let speech_status = manager.get_status().await;
+    let dependencies = system_dependencies::check_all().await;
+    let pending_auth = auth_queue.peek_redacted().await;
+
+    let mut redacted_config = serde_json::to_value(&config).context("serialize config")?;
+    redact_secrets(&mut redacted_config, false);
+
+    let generated_at = Utc::now();
+    let mut report = vec![
+        "=== Winky diagnostics ===".to_string(),
+        format!("generated at: {}", generated_at.to_rfc3339()),
+    ];
+    report.extend(logging::diagnostics_lines(app, &config, &config_path));
+    report.push(format!(
+        "config checksum: {}",
+        integrity
+            .as_ref()
+            .map(|value| if value.matches { "ok" } else { "mismatch" })
+            .unwrap_or("unavailable")
+    ));
+    for dependency in &dependencies {
+        report.push(format!(
+            "dependency {}: {}",
+            dependency.name,
+            dependency.version.as_deref().unwrap_or(if dependency.present { "present" } else { "missing" })
+        ));
+    }
+
+    let runtime = json!({
+        "whisperBaseUrl": manager.base_url(),
+        "whisperTranscribeEndpoint": manager.transcribe_endpoint(),
+        "configPath": config_path.display().to_string(),
+        "configIntegrity": integrity,
+    });
+
+    let mut entries: Vec<(String, Vec<u8>)> = vec![
+        ("report.txt".into(), report.join("\n").into_bytes()),
+        ("config.redacted.json".into(), pretty(&redacted_config)?),
+        ("runtime.json".into(), pretty(&runtime)?),
+        ("local-speech-status.json".into(), pretty(&speech_status)?),
+        ("system-dependencies.json".into(), pretty(&dependencies)?),
+        ("auth-pending.json".into(), pretty(&pending_auth)?),
+    ];
+    for log_file in logging::existing_log_files() {
+        let name = log_file
+            .file_name()
+            .map(|value| value.to_string_lossy().to_string())
+            .unwrap_or_else(|| "winky.log".into());
+        match tokio::fs::read(&log_file).await {
+            Ok(bytes) => entries.push((format!("logs/{name}"), bytes)),
+            Err(error) => logging::log_message(&format!(
+                "[diagnostics] Skipping log {}: {error}",
+                log_file.display()
+            )),
+        }
+    }
+
+    tokio::fs::create_dir_all(target_dir)
+        .await
+        .with_context(|| format!("create diagnostics directory at {}", target_dir.display()))?;
+    let archive_path = target_dir.join(format!(
+        "winky-diagnostics-{}.zip",
+        generated_at.format("%Y%m%d-%H%M%S")
+    ));
+    let archive_for_task = archive_path.clone();
+    tauri::async_runtime::spawn_blocking(move || write_archive(&archive_for_task, entries))
+        .await
+        .map_err(|error| anyhow!("Diagnostics archive task failed: {error}"))??;
+    Ok(archive_path)
+}
+
+fn write_archive(path: &Path, entries: Vec<(String, Vec<u8>)>) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("create diagnostics archive {}", path.display()))?;
+    let mut writer = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in entries {
+        writer
+            .start_file(name.as_str(), options)
+            .with_context(|| format!("add {name} to diagnostics archive"))?;
+        writer
+            .write_all(&bytes)
+            .with_context(|| format!("write {name} to diagnostics archive"))?;
+    }
+    writer.finish().context("finalize diagnostics archive")?;
+    Ok(())
+}
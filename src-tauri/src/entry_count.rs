@@ -0,0 +1,62 @@
+//! Подсчёт элементов JSON-массивов (заметки, история) без разбора самих записей.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use tokio::fs;
+
+/// Remembers the last count together with the file's mtime and size,
+/// so repeated badge requests skip reading the file entirely.
+#[derive(Default)]
+pub struct CountCache {
+    inner: Mutex<Option<(SystemTime, u64, usize)>>,
+}
+
+impl CountCache {
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(None),
+        }
+    }
+
+    pub async fn count(&self, path: &Path) -> Result<usize> {
+        self.count_where::<IgnoredAny>(path, |_| true).await
+    }
+
+    /// Counts only items for which `keep` returns true; each item is parsed as `T`,
+    /// so `T` should declare just the fields `keep` looks at.
+    pub async fn count_where<T: DeserializeOwned>(&self, path: &Path, keep: fn(&T) -> bool) -> Result<usize> {
+        let Ok(metadata) = fs::metadata(path).await else {
+            return Ok(0);
+        };
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let len = metadata.len();
+        let cached = *self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((cached_modified, cached_len, count)) = cached {
+            if cached_modified == modified && cached_len == len {
+                return Ok(count);
+            }
+        }
+
+        let contents = fs::read_to_string(path)
+            .await
+            .with_context(|| format!("read {}", path.display()))?;
+        let count = if contents.trim().is_empty() {
+            0
+        } else {
+            match serde_json::from_str::<Vec<T>>(&contents) {
+                Ok(items) => items.iter().filter(|item| keep(item)).count(),
+                Err(error) => {
+                    // Как и при чтении списка: битый файл считаем пустым, чинится через *_repair
+                    eprintln!("[count] Failed to parse {}: {error}", path.display());
+                    0
+                }
+            }
+        };
+        *self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((modified, len, count));
+        Ok(count)
+    }
+}
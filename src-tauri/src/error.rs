@@ -0,0 +1,117 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+pub const AUTH_MISSING: &str = "AUTH_MISSING";
+pub const SERVER_DOWN: &str = "SERVER_DOWN";
+pub const NETWORK: &str = "NETWORK";
+pub const NOT_FOUND: &str = "NOT_FOUND";
+pub const INVALID_INPUT: &str = "INVALID_INPUT";
+pub const IO: &str = "IO";
+pub const INTERNAL: &str = "INTERNAL";
+pub const RATE_LIMITED: &str = "RATE_LIMITED";
+pub const DUPLICATE_TITLE: &str = "DUPLICATE_TITLE";
+
+/// Error returned across the command boundary. `code` is stable and meant for
+/// branching in the UI, `message` is human-readable.
+///
+/// Module code returning `anyhow::Result` attaches a code by returning a `CommandError`
+/// (e.g. `CommandError::not_found(..)?`); the code survives conversion back at the command.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+    pub details: Option<Value>,
+}
+
+impl CommandError {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(INVALID_INPUT, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(NOT_FOUND, message)
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+fn classify(error: &anyhow::Error) -> &str {
+    for cause in error.chain() {
+        if let Some(command_error) = cause.downcast_ref::<CommandError>() {
+            return &command_error.code;
+        }
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            return match io_error.kind() {
+                std::io::ErrorKind::NotFound => NOT_FOUND,
+                _ => IO,
+            };
+        }
+        if let Some(http_error) = cause.downcast_ref::<reqwest::Error>() {
+            if http_error.is_connect() {
+                return SERVER_DOWN;
+            }
+            if http_error.status().map(|status| status.as_u16()) == Some(401) {
+                return AUTH_MISSING;
+            }
+            return NETWORK;
+        }
+    }
+    INTERNAL
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        // Без контекста отдаём исходную ошибку целиком, вместе с details
+        let error = match error.downcast::<CommandError>() {
+            Ok(command_error) => return command_error,
+            Err(error) => error,
+        };
+        Self::new(classify(&error), error.to_string())
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(error: std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => NOT_FOUND,
+            _ => IO,
+        };
+        Self::new(code, error.to_string())
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(INTERNAL, message)
+    }
+}
+
+impl From<tauri::Error> for CommandError {
+    fn from(error: tauri::Error) -> Self {
+        Self::new(INTERNAL, error.to_string())
+    }
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
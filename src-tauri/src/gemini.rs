@@ -6,6 +6,8 @@ use reqwest::header::{ACCEPT, CONTENT_TYPE};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
+use crate::{llm_streams, rate_limit};
+
 const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
 
 fn extract_text(payload: &Value) -> String {
@@ -55,12 +57,14 @@ pub async fn stream_generate_content(
         return Err(anyhow!("Gemini model is missing."));
     }
 
+    rate_limit::acquire("gemini").await?;
     let client = reqwest::Client::new();
     let url = format!(
         "{}/{}:streamGenerateContent?key={}&alt=sse",
         GEMINI_BASE_URL, model, token
     );
 
+    let mut timing = llm_streams::StreamTiming::start();
     let response = client
         .post(&url)
         .header(ACCEPT, "text/event-stream")
@@ -80,8 +84,16 @@ pub async fn stream_generate_content(
     let mut full_text = String::new();
     let mut buffer = String::new();
     let mut stream = response.bytes_stream();
-
-    while let Some(chunk) = stream.next().await {
+    let registration = llm_streams::register(stream_id, "gemini:stream");
+
+    loop {
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = registration.cancelled() => return Ok(full_text),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
         let chunk = chunk.map_err(|e| anyhow!("Gemini stream error: {}", e))?;
         let text = String::from_utf8_lossy(&chunk);
         buffer.push_str(&text);
@@ -104,10 +116,7 @@ pub async fn stream_generate_content(
             };
 
             if data == "[DONE]" {
-                let _ = app.emit(
-                    "gemini:stream",
-                    serde_json::json!({"streamId": stream_id, "done": true}),
-                );
+                let _ = app.emit("gemini:stream", timing.done_payload(stream_id));
                 return Ok(full_text);
             }
 
@@ -136,6 +145,7 @@ pub async fn stream_generate_content(
             };
 
             if !delta.is_empty() {
+                timing.mark_delta();
                 let _ = app.emit(
                     "gemini:stream",
                     serde_json::json!({"streamId": stream_id, "delta": delta}),
@@ -160,6 +170,7 @@ pub async fn stream_generate_content(
                         chunk_text
                     };
                     if !delta.is_empty() {
+                        timing.mark_delta();
                         let _ = app.emit(
                             "gemini:stream",
                             serde_json::json!({"streamId": stream_id, "delta": delta}),
@@ -170,10 +181,7 @@ pub async fn stream_generate_content(
         }
     }
 
-    let _ = app.emit(
-        "gemini:stream",
-        serde_json::json!({"streamId": stream_id, "done": true}),
-    );
+    let _ = app.emit("gemini:stream", timing.done_payload(stream_id));
 
     Ok(full_text)
 }
@@ -1,16 +1,22 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use tokio::fs;
 use uuid::Uuid;
 
+use crate::entry_count::CountCache;
+use crate::error::CommandError;
+use crate::repair::{self, RepairReport};
+
 const HISTORY_DIR_NAME: &str = "history";
 const HISTORY_FILE_NAME: &str = "actions.json";
 const HISTORY_AUDIO_DIR_NAME: &str = "audio";
 
+static COUNT_CACHE: CountCache = CountCache::new();
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct ActionHistoryEntry {
@@ -29,6 +35,15 @@ pub struct ActionHistoryEntry {
     pub audio_path: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HistoryListResponse {
+    pub count: usize,
+    pub next_page: Option<u32>,
+    pub previous_page: Option<u32>,
+    pub results: Vec<ActionHistoryEntry>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ActionHistoryInput {
@@ -43,7 +58,7 @@ pub struct ActionHistoryInput {
     pub audio_path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ActionHistoryUpdateInput {
     pub id: String,
@@ -57,7 +72,7 @@ pub struct ActionHistoryUpdateInput {
     pub audio_path: Option<String>,
 }
 
-fn resolve_history_dir(app: &AppHandle) -> Result<PathBuf> {
+pub fn resolve_history_dir(app: &AppHandle) -> Result<PathBuf> {
     let base_dir = app
         .path()
         .app_local_data_dir()
@@ -75,6 +90,11 @@ async fn history_file_path(app: &AppHandle) -> Result<PathBuf> {
     Ok(dir.join(HISTORY_FILE_NAME))
 }
 
+/// Путь к папке с аудио истории без её создания.
+pub fn resolve_history_audio_dir(app: &AppHandle) -> Result<PathBuf> {
+    Ok(resolve_history_dir(app)?.join(HISTORY_AUDIO_DIR_NAME))
+}
+
 async fn history_audio_dir(app: &AppHandle) -> Result<PathBuf> {
     let dir = resolve_history_dir(app)?;
     let audio_dir = dir.join(HISTORY_AUDIO_DIR_NAME);
@@ -105,40 +125,83 @@ fn resolve_audio_extension(mime_type: Option<&str>) -> String {
 
 pub async fn read_history(app: &AppHandle) -> Result<Vec<ActionHistoryEntry>> {
     let path = history_file_path(app).await?;
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let contents = fs::read_to_string(&path)
+    let Some(contents) = repair::read_to_string_retrying(&path)
         .await
-        .with_context(|| format!("read history from {}", path.display()))?;
+        .with_context(|| format!("read history from {}", path.display()))?
+    else {
+        return Ok(Vec::new());
+    };
     if contents.trim().is_empty() {
         return Ok(Vec::new());
     }
     match serde_json::from_str::<Vec<ActionHistoryEntry>>(&contents) {
         Ok(entries) => Ok(entries),
         Err(error) => {
-            eprintln!("[history] Failed to parse history file: {error}");
+            eprintln!("[history] Failed to parse history file (use history_repair to recover): {error}");
             Ok(Vec::new())
         }
     }
 }
 
+fn matches_query(entry: &ActionHistoryEntry, tokens: &[String]) -> bool {
+    let haystack = format!(
+        "{}\n{}\n{}\n{}",
+        entry.action_name,
+        entry.transcription,
+        entry.llm_response.as_deref().unwrap_or_default(),
+        entry.result_text
+    )
+    .to_lowercase();
+    tokens.iter().all(|token| haystack.contains(token.as_str()))
+}
+
+/// One page of history, newest first; every word of `query` must occur in the action name,
+/// transcription, LLM response or result text.
+pub async fn list_history(
+    app: &AppHandle,
+    page: u32,
+    page_size: u32,
+    query: Option<&str>,
+) -> Result<HistoryListResponse> {
+    let page = page.max(1);
+    let page_size = page_size.max(1);
+    let tokens: Vec<String> = query
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|token| token.to_lowercase())
+        .collect();
+    let mut entries = read_history(app).await?;
+    if !tokens.is_empty() {
+        entries.retain(|entry| matches_query(entry, &tokens));
+    }
+
+    let total = entries.len();
+    let start = (page as usize - 1) * page_size as usize;
+    let end = usize::min(start + page_size as usize, total);
+    let results = if start < total {
+        entries.drain(start..end).collect()
+    } else {
+        Vec::new()
+    };
+    Ok(HistoryListResponse {
+        count: total,
+        next_page: if end < total { Some(page + 1) } else { None },
+        previous_page: if page > 1 && start > 0 { Some(page - 1) } else { None },
+        results,
+    })
+}
+
 async fn write_history(app: &AppHandle, entries: &[ActionHistoryEntry]) -> Result<()> {
     let path = history_file_path(app).await?;
     let serialized = serde_json::to_string_pretty(entries).context("serialize history")?;
-    fs::write(&path, serialized)
+    repair::write_atomic(&path, &serialized)
         .await
         .with_context(|| format!("write history to {}", path.display()))
 }
 
 pub async fn append_history(app: &AppHandle, payload: ActionHistoryInput) -> Result<ActionHistoryEntry> {
-    let mut entries = match read_history(app).await {
-        Ok(existing) => existing,
-        Err(error) => {
-            eprintln!("[history] Failed to read history before append: {error}");
-            Vec::new()
-        }
-    };
+    // Файл занят дольше ретраев — не перезаписываем его пустым списком
+    let mut entries = read_history(app).await?;
 
     let entry = ActionHistoryEntry {
         id: Uuid::new_v4().to_string(),
@@ -164,7 +227,7 @@ pub async fn update_history(app: &AppHandle, payload: ActionHistoryUpdateInput)
     let entry = entries
         .iter_mut()
         .find(|entry| entry.id == payload.id)
-        .ok_or_else(|| anyhow!("History entry {} not found", payload.id))?;
+        .ok_or_else(|| CommandError::not_found(format!("History entry {} not found", payload.id)))?;
 
     if let Some(action_name) = payload.action_name {
         entry.action_name = action_name;
@@ -202,7 +265,7 @@ pub async fn clear_history(app: &AppHandle) -> Result<()> {
         .await
         .with_context(|| format!("clear history at {}", path.display()))?;
 
-    let audio_dir = resolve_history_dir(app)?.join(HISTORY_AUDIO_DIR_NAME);
+    let audio_dir = resolve_history_audio_dir(app)?;
     if fs::metadata(&audio_dir).await.is_ok() {
         if let Err(error) = fs::remove_dir_all(&audio_dir).await {
             eprintln!("[history] Failed to remove audio directory {}: {error}", audio_dir.display());
@@ -211,8 +274,45 @@ pub async fn clear_history(app: &AppHandle) -> Result<()> {
     Ok(())
 }
 
-pub async fn read_history_audio(app: &AppHandle, audio_path: String) -> Result<Vec<u8>> {
+/// Deletes audio of entries older than `retention_days`, keeping the entries themselves
+/// with `audio_path` cleared. Returns ids of the pruned entries; 0 days disables pruning.
+pub async fn prune_history_audio(app: &AppHandle, retention_days: u32) -> Result<Vec<String>> {
+    if retention_days == 0 {
+        return Ok(Vec::new());
+    }
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(retention_days));
+    let mut entries = read_history(app).await?;
     let audio_dir = history_audio_dir(app).await?;
+
+    let mut pruned = Vec::new();
+    for entry in &mut entries {
+        let Some(audio_path) = entry.audio_path.as_deref().filter(|path| !path.trim().is_empty()) else {
+            continue;
+        };
+        let expired = DateTime::parse_from_rfc3339(&entry.created_at)
+            .map(|created| created.with_timezone(&Utc) < cutoff)
+            .unwrap_or(false);
+        if !expired {
+            continue;
+        }
+        // Файл мог быть уже удалён вручную — ссылку всё равно убираем
+        if let Ok(file) = resolve_history_audio_file(&audio_dir, audio_path).await {
+            if let Err(error) = fs::remove_file(&file).await {
+                eprintln!("[history] Failed to remove audio {}: {error}", file.display());
+                continue;
+            }
+        }
+        entry.audio_path = None;
+        pruned.push(entry.id.clone());
+    }
+
+    if !pruned.is_empty() {
+        write_history(app, &entries).await?;
+    }
+    Ok(pruned)
+}
+
+async fn resolve_history_audio_file(audio_dir: &Path, audio_path: &str) -> Result<PathBuf> {
     let resolved = {
         let candidate = PathBuf::from(audio_path);
         if candidate.is_absolute() {
@@ -222,7 +322,7 @@ pub async fn read_history_audio(app: &AppHandle, audio_path: String) -> Result<V
         }
     };
 
-    let audio_dir_canonical = fs::canonicalize(&audio_dir)
+    let audio_dir_canonical = fs::canonicalize(audio_dir)
         .await
         .with_context(|| format!("resolve history audio directory {}", audio_dir.display()))?;
     let resolved_canonical = fs::canonicalize(&resolved)
@@ -232,10 +332,156 @@ pub async fn read_history_audio(app: &AppHandle, audio_path: String) -> Result<V
     if !resolved_canonical.starts_with(&audio_dir_canonical) {
         return Err(anyhow!("Requested audio path is outside history audio directory"));
     }
+    Ok(resolved_canonical)
+}
 
-    fs::read(&resolved_canonical)
+pub async fn read_history_audio(app: &AppHandle, audio_path: String) -> Result<Vec<u8>> {
+    let audio_dir = history_audio_dir(app).await?;
+    let resolved = resolve_history_audio_file(&audio_dir, &audio_path).await?;
+    fs::read(&resolved)
         .await
-        .with_context(|| format!("read history audio file {}", resolved_canonical.display()))
+        .with_context(|| format!("read history audio file {}", resolved.display()))
+}
+
+/// Имя файла для экспорта: `<дата>_<время>_<действие>`, только безопасные для ФС символы.
+fn export_audio_stem(entry: &ActionHistoryEntry) -> String {
+    let timestamp = DateTime::parse_from_rfc3339(&entry.created_at)
+        .map(|value| value.format("%Y-%m-%d_%H-%M-%S").to_string())
+        .unwrap_or_else(|_| entry.id.clone());
+    let action: String = entry
+        .action_name
+        .trim()
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() || ch == '-' { ch } else { '_' })
+        .collect();
+    let action = action.trim_matches('_');
+    if action.is_empty() {
+        timestamp
+    } else {
+        format!("{timestamp}_{action}")
+    }
+}
+
+/// Copies recorded audio of the selected (or all) history entries into `target_dir`.
+pub async fn export_history_audio(
+    app: &AppHandle,
+    target_dir: &Path,
+    ids: Option<Vec<String>>,
+) -> Result<Vec<String>> {
+    let entries = read_history(app).await?;
+    let audio_dir = history_audio_dir(app).await?;
+    fs::create_dir_all(target_dir)
+        .await
+        .with_context(|| format!("create export directory at {}", target_dir.display()))?;
+
+    let mut written = Vec::new();
+    for entry in &entries {
+        if let Some(ids) = ids.as_ref() {
+            if !ids.contains(&entry.id) {
+                continue;
+            }
+        }
+        let Some(audio_path) = entry.audio_path.as_deref().filter(|path| !path.trim().is_empty()) else {
+            continue;
+        };
+        let source = match resolve_history_audio_file(&audio_dir, audio_path).await {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("[history] Skipping audio export for {}: {error}", entry.id);
+                continue;
+            }
+        };
+        let extension = source
+            .extension()
+            .and_then(|value| value.to_str())
+            .unwrap_or("webm")
+            .to_string();
+        let stem = export_audio_stem(entry);
+        let mut destination = target_dir.join(format!("{stem}.{extension}"));
+        let mut suffix = 2;
+        while fs::metadata(&destination).await.is_ok() {
+            destination = target_dir.join(format!("{stem}-{suffix}.{extension}"));
+            suffix += 1;
+        }
+        fs::copy(&source, &destination)
+            .await
+            .with_context(|| format!("copy {} to {}", source.display(), destination.display()))?;
+        written.push(destination.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Parses an export bound: RFC 3339 timestamp or a plain `YYYY-MM-DD` date.
+/// For a plain date `end_of_day` selects its last moment, so `until` stays inclusive.
+pub fn parse_export_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+        return Ok(parsed.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| CommandError::invalid_input(format!("Invalid date {value}: expected RFC 3339 or YYYY-MM-DD")))?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    }
+    .ok_or_else(|| CommandError::invalid_input(format!("Invalid date {value}")))?;
+    Ok(time.and_utc())
+}
+
+/// Renders history entries of one action (or all) within `[since, until]` as Markdown,
+/// oldest first.
+pub async fn export_history_markdown(
+    app: &AppHandle,
+    action_id: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Result<String> {
+    let mut entries: Vec<(DateTime<Utc>, ActionHistoryEntry)> = read_history(app)
+        .await?
+        .into_iter()
+        .filter(|entry| action_id.is_none_or(|id| entry.action_id == id))
+        .filter_map(|entry| {
+            let created = DateTime::parse_from_rfc3339(&entry.created_at)
+                .ok()?
+                .with_timezone(&Utc);
+            let in_range = since.is_none_or(|bound| created >= bound)
+                && until.is_none_or(|bound| created <= bound);
+            in_range.then_some((created, entry))
+        })
+        .collect();
+    entries.sort_by_key(|(created, _)| *created);
+
+    let title = match action_id {
+        Some(_) => entries
+            .first()
+            .map(|(_, entry)| entry.action_name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "Action".to_string()),
+        None => "All actions".to_string(),
+    };
+    let mut document = format!("# Winky history: {title}\n");
+    if since.is_some() || until.is_some() {
+        let bound = |value: Option<DateTime<Utc>>| {
+            value
+                .map(|value| value.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "…".to_string())
+        };
+        document.push_str(&format!("\n_{} – {}_\n", bound(since), bound(until)));
+    }
+    for (created, entry) in &entries {
+        document.push_str(&format!(
+            "\n## {} · {}\n\n",
+            created.format("%Y-%m-%d %H:%M"),
+            entry.action_name.trim()
+        ));
+        document.push_str(&format!("**Transcription**\n\n{}\n", entry.transcription.trim()));
+        let result = entry.result_text.trim();
+        if !result.is_empty() && result != entry.transcription.trim() {
+            document.push_str(&format!("\n**Result**\n\n{result}\n"));
+        }
+    }
+    Ok(document)
 }
 
 pub async fn save_history_audio(
@@ -252,3 +498,16 @@ pub async fn save_history_audio(
         .with_context(|| format!("write history audio file {}", path.display()))?;
     Ok(path.to_string_lossy().to_string())
 }
+
+/// Salvages valid entries from a damaged history file, keeping a backup of the original.
+pub async fn repair_history(app: &AppHandle) -> Result<RepairReport> {
+    let path = history_file_path(app).await?;
+    let (_, report) = repair::repair_json_array::<ActionHistoryEntry>(&path).await?;
+    Ok(report)
+}
+
+/// Number of stored history entries, served from cache while the file is unchanged.
+pub async fn count_history(app: &AppHandle) -> Result<usize> {
+    let path = history_file_path(app).await?;
+    COUNT_CACHE.count(&path).await
+}
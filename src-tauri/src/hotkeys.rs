@@ -1,17 +1,49 @@
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
+use crate::constants::DEFAULT_HOTKEY_DEBOUNCE_MS;
+
+/// Payload of `mic:shortcut`. `reason` is kept for older listeners, `source` mirrors it.
+pub fn mic_shortcut_payload(source: &str, accelerator: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "reason": source,
+        "source": source,
+        "accelerator": accelerator,
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Payload of `hotkey:action-triggered`.
+pub fn action_triggered_payload(action_id: &str, source: &str, accelerator: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "actionId": action_id,
+        "source": source,
+        "accelerator": accelerator,
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+    })
+}
+
 #[derive(Debug, Default)]
 pub struct HotkeyState {
     mic: Mutex<Option<String>>,
     actions: Mutex<HashMap<String, String>>,
     mic_action_overrides: Mutex<HashMap<String, String>>,
     recording_active: AtomicBool,
+    debounce_ms: AtomicU64,
+    last_mic_trigger: Mutex<Option<Instant>>,
+    auto_hide_delay_ms: AtomicU64,
+    // Любая смена состояния записи увеличивает поколение и тем самым отменяет отложенное скрытие
+    auto_hide_generation: AtomicU64,
+    // Каждая очистка хоткеев действий увеличивает поколение: обработчики прошлых регистраций молчат
+    action_generation: AtomicU64,
+    // Повторные вызовы register_action_hotkeys не должны перемежаться друг с другом
+    action_registration: Mutex<()>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,9 +52,96 @@ pub struct ActionHotkeyInput {
     pub accelerator: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReservedHotkey {
+    pub accelerator: String,
+    pub description: String,
+}
+
+#[cfg(target_os = "windows")]
+const RESERVED_HOTKEYS: &[(&str, &str)] = &[
+    ("Super+L", "Lock the computer"),
+    ("Super+D", "Show desktop"),
+    ("Super+E", "Open File Explorer"),
+    ("Super+R", "Run dialog"),
+    ("Super+Tab", "Task view"),
+    ("Super+V", "Clipboard history"),
+    ("Super+Shift+S", "Snipping tool"),
+    ("Alt+F4", "Close window"),
+    ("Alt+Tab", "Switch windows"),
+    ("Alt+Escape", "Cycle windows"),
+    ("Ctrl+Alt+Delete", "Security screen"),
+    ("Ctrl+Shift+Escape", "Task Manager"),
+    ("Ctrl+Escape", "Start menu"),
+];
+
+#[cfg(target_os = "macos")]
+const RESERVED_HOTKEYS: &[(&str, &str)] = &[
+    ("Cmd+Space", "Spotlight"),
+    ("Cmd+Tab", "Switch applications"),
+    ("Cmd+Q", "Quit application"),
+    ("Cmd+W", "Close window"),
+    ("Cmd+H", "Hide application"),
+    ("Cmd+M", "Minimize window"),
+    ("Cmd+Shift+3", "Screenshot"),
+    ("Cmd+Shift+4", "Screenshot selection"),
+    ("Cmd+Shift+5", "Screenshot toolbar"),
+    ("Ctrl+Cmd+Q", "Lock screen"),
+    ("Cmd+Alt+Escape", "Force quit"),
+    ("Ctrl+Space", "Switch input source"),
+];
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const RESERVED_HOTKEYS: &[(&str, &str)] = &[
+    ("Super+L", "Lock screen"),
+    ("Super", "Activities overview"),
+    ("Alt+F4", "Close window"),
+    ("Alt+Tab", "Switch windows"),
+    ("Alt+F2", "Run command"),
+    ("Ctrl+Alt+Delete", "Log out"),
+    ("Ctrl+Alt+T", "Open terminal"),
+    ("Ctrl+Alt+Left", "Switch workspace"),
+    ("Ctrl+Alt+Right", "Switch workspace"),
+    ("Print", "Screenshot"),
+];
+
+/// Commonly OS-reserved accelerators for the current platform. Informational only:
+/// nothing is registered, the list lets the UI warn before a binding attempt.
+pub fn reserved_hotkeys() -> Vec<ReservedHotkey> {
+    RESERVED_HOTKEYS
+        .iter()
+        .map(|(accelerator, description)| ReservedHotkey {
+            accelerator: (*accelerator).to_string(),
+            description: (*description).to_string(),
+        })
+        .collect()
+}
+
 impl HotkeyState {
     pub fn new() -> Self {
-        Self::default()
+        let state = Self::default();
+        state.set_debounce_ms(DEFAULT_HOTKEY_DEBOUNCE_MS);
+        state
+    }
+
+    pub fn set_debounce_ms(&self, debounce_ms: u64) {
+        self.debounce_ms.store(debounce_ms, Ordering::Relaxed);
+    }
+
+    /// Drops triggers that arrive within the debounce window of the previous one
+    /// (OS key repeat while the toggle hotkey is held).
+    fn accept_mic_trigger(&self) -> bool {
+        let window = Duration::from_millis(self.debounce_ms.load(Ordering::Relaxed));
+        let now = Instant::now();
+        let mut last = self.last_mic_trigger.lock().unwrap();
+        if let Some(previous) = *last {
+            if now.duration_since(previous) < window {
+                return false;
+            }
+        }
+        *last = Some(now);
+        true
     }
 
     fn normalize_accelerator(accelerator: &str) -> String {
@@ -43,6 +162,32 @@ impl HotkeyState {
 
     pub fn set_recording_active(&self, active: bool) {
         self.recording_active.store(active, Ordering::Relaxed);
+        self.auto_hide_generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_auto_hide_delay_ms(&self, delay_ms: u64) {
+        self.auto_hide_delay_ms.store(delay_ms, Ordering::Relaxed);
+    }
+
+    /// Emits `mic:auto-hide` once the configured delay has passed after recording stopped,
+    /// unless recording was restarted in the meantime. A zero delay disables it.
+    pub fn schedule_auto_hide(self: &Arc<Self>, app: &AppHandle) {
+        let delay_ms = self.auto_hide_delay_ms.load(Ordering::Relaxed);
+        if delay_ms == 0 || self.is_recording_active() {
+            return;
+        }
+        let generation = self.auto_hide_generation.load(Ordering::Relaxed);
+        let state = Arc::clone(self);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            if state.auto_hide_generation.load(Ordering::Relaxed) != generation
+                || state.is_recording_active()
+            {
+                return;
+            }
+            let _ = app.emit("mic:auto-hide", serde_json::json!({"delayMs": delay_ms}));
+        });
     }
 
     fn is_recording_active(&self) -> bool {
@@ -102,17 +247,23 @@ impl HotkeyState {
         let accelerator_for_handler = accelerator.clone();
         match manager.on_shortcut(accelerator.as_str(), move |app_handle, _, _| {
             if let Some(hotkeys) = app_handle.try_state::<Arc<HotkeyState>>() {
+                if !hotkeys.accept_mic_trigger() {
+                    return;
+                }
                 if hotkeys.is_recording_active() {
                     if let Some(action_id) = hotkeys.action_for_mic_accelerator(&accelerator_for_handler) {
                         let _ = app_handle.emit(
                             "hotkey:action-triggered",
-                            serde_json::json!({"actionId": action_id}),
+                            action_triggered_payload(&action_id, "mic-shortcut", Some(&accelerator_for_handler)),
                         );
                         return;
                     }
                 }
             }
-            let _ = app_handle.emit("mic:shortcut", serde_json::json!({"reason": "shortcut"}));
+            let _ = app_handle.emit(
+                "mic:shortcut",
+                mic_shortcut_payload("shortcut", Some(&accelerator_for_handler)),
+            );
         }) {
             Ok(_) => {
                 *current = Some(accelerator_clone.clone());
@@ -138,11 +289,17 @@ impl HotkeyState {
         }
     }
 
+    fn is_current_action_generation(&self, generation: u64) -> bool {
+        self.action_generation.load(Ordering::SeqCst) == generation
+    }
+
     pub fn register_action_hotkeys(&self, app: &AppHandle, hotkeys: Vec<ActionHotkeyInput>) {
+        let _registration = self.action_registration.lock().unwrap();
         self.clear_action_hotkeys(app);
         if hotkeys.is_empty() {
             return;
         }
+        let generation = self.action_generation.load(Ordering::SeqCst);
 
         let manager = app.global_shortcut();
         let mut used = HashMap::new();
@@ -182,10 +339,17 @@ impl HotkeyState {
             let action_id = entry.id.clone();
             let handler_action_id = action_id.clone();
             let accelerator_str = accelerator.to_string();
+            let handler_accelerator = accelerator_str.clone();
             match manager.on_shortcut(accelerator_str.as_str(), move |app_handle, _, _| {
+                let is_stale = app_handle
+                    .try_state::<Arc<HotkeyState>>()
+                    .is_some_and(|hotkeys| !hotkeys.is_current_action_generation(generation));
+                if is_stale {
+                    return;
+                }
                 let _ = app_handle.emit(
                     "hotkey:action-triggered",
-                    serde_json::json!({"actionId": handler_action_id.clone()}),
+                    action_triggered_payload(&handler_action_id, "shortcut", Some(&handler_accelerator)),
                 );
             }) {
                 Ok(_) => {
@@ -212,6 +376,7 @@ impl HotkeyState {
     }
 
     pub fn clear_action_hotkeys(&self, app: &AppHandle) {
+        self.action_generation.fetch_add(1, Ordering::SeqCst);
         let manager = app.global_shortcut();
         self.mic_action_overrides.lock().unwrap().clear();
         for (_, accelerator) in self.actions.lock().unwrap().drain() {
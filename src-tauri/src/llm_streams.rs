@@ -0,0 +1,133 @@
+//! Registry of active LLM streams so they can be cancelled by `stream_id` or all at once.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Notify;
+
+use crate::logging;
+
+struct ActiveStream {
+    /// Provider event channel (`openai:stream`, `gemini:stream`, `ollama:stream`).
+    event: &'static str,
+    cancel: Arc<Notify>,
+}
+
+static ACTIVE_STREAMS: Lazy<Mutex<HashMap<String, ActiveStream>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn active_streams() -> std::sync::MutexGuard<'static, HashMap<String, ActiveStream>> {
+    ACTIVE_STREAMS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keeps a stream registered while alive; dropping it removes the registry entry.
+pub struct StreamRegistration {
+    stream_id: String,
+    cancel: Arc<Notify>,
+}
+
+impl StreamRegistration {
+    /// Resolves once the stream is cancelled through [`cancel`] or [`cancel_all`].
+    pub async fn cancelled(&self) {
+        self.cancel.notified().await;
+    }
+}
+
+impl Drop for StreamRegistration {
+    fn drop(&mut self) {
+        let mut streams = active_streams();
+        // Тот же stream_id мог быть переиспользован новым стримом — удаляем только свою запись
+        if streams
+            .get(&self.stream_id)
+            .is_some_and(|entry| Arc::ptr_eq(&entry.cancel, &self.cancel))
+        {
+            streams.remove(&self.stream_id);
+        }
+    }
+}
+
+pub fn register(stream_id: &str, event: &'static str) -> StreamRegistration {
+    let cancel = Arc::new(Notify::new());
+    active_streams().insert(
+        stream_id.to_string(),
+        ActiveStream {
+            event,
+            cancel: cancel.clone(),
+        },
+    );
+    StreamRegistration {
+        stream_id: stream_id.to_string(),
+        cancel,
+    }
+}
+
+/// Latency of one stream: from sending the request to the first delta and to the end.
+pub struct StreamTiming {
+    started: Instant,
+    first_token_ms: Option<u64>,
+}
+
+impl StreamTiming {
+    /// Call right before the request is sent, after rate limiting.
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            first_token_ms: None,
+        }
+    }
+
+    pub fn mark_delta(&mut self) {
+        if self.first_token_ms.is_none() {
+            self.first_token_ms = Some(self.started.elapsed().as_millis() as u64);
+        }
+    }
+
+    /// Terminal `done` event; `firstTokenMs` is `null` when the model produced no text.
+    pub fn done_payload(&self, stream_id: &str) -> Value {
+        serde_json::json!({
+            "streamId": stream_id,
+            "done": true,
+            "firstTokenMs": self.first_token_ms,
+            "totalMs": self.started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+fn abort(app: &AppHandle, stream_id: &str, stream: ActiveStream) {
+    // notify_one сохраняет разрешение, даже если стрим сейчас не ждёт в select!
+    stream.cancel.notify_one();
+    let _ = app.emit(
+        stream.event,
+        serde_json::json!({"streamId": stream_id, "cancelled": true, "done": true}),
+    );
+    let _ = app.emit("llm:stream-cancelled", serde_json::json!({"streamId": stream_id}));
+}
+
+/// Cancels a single stream; returns `false` when it is not (or no longer) active.
+pub fn cancel(app: &AppHandle, stream_id: &str) -> bool {
+    let Some(stream) = active_streams().remove(stream_id) else {
+        return false;
+    };
+    abort(app, stream_id, stream);
+    true
+}
+
+/// Cancels every active stream and returns their ids.
+pub fn cancel_all(app: &AppHandle) -> Vec<String> {
+    let drained: Vec<(String, ActiveStream)> = active_streams().drain().collect();
+    let mut cancelled = Vec::with_capacity(drained.len());
+    for (stream_id, stream) in drained {
+        abort(app, &stream_id, stream);
+        cancelled.push(stream_id);
+    }
+    if !cancelled.is_empty() {
+        logging::log_message(&format!("[llm] Cancelled {} active stream(s)", cancelled.len()));
+    }
+    cancelled
+}
@@ -8,13 +8,17 @@ use std::io::Cursor;
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex as StdMutex};
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use reqwest::header::{CONTENT_RANGE, RANGE};
 use reqwest::StatusCode;
+use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::{mpsc, Mutex as AsyncMutex};
 use tokio::task::spawn_blocking;
@@ -22,23 +26,198 @@ use tokio::time::sleep;
 use zip::ZipArchive;
 
 use crate::constants::{
-    FAST_WHISPER_HEALTH_ENDPOINT, FAST_WHISPER_INSTALL_ENV_VAR, FAST_WHISPER_INSTALL_HINT_FILE,
+    DEFAULT_HEALTH_POLL_INTERVAL_MS, FAST_WHISPER_HEALTH_ENDPOINT, FAST_WHISPER_INSTALL_ENV_VAR, FAST_WHISPER_INSTALL_HINT_FILE,
     FAST_WHISPER_PORT, FAST_WHISPER_REPO_ARCHIVE_URL, FAST_WHISPER_REPO_NAME,
     FAST_WHISPER_REPO_URL,
 };
+use crate::error::CommandError;
 use crate::types::FastWhisperStatus;
 
 const HEALTH_TIMEOUT: Duration = Duration::from_secs(120);
 const HEALTH_INTERVAL: Duration = Duration::from_secs(2);
 const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+const ARCHIVE_DOWNLOAD_ATTEMPTS: u32 = 3;
+const ARCHIVE_RETRY_DELAY: Duration = Duration::from_secs(3);
+const ARCHIVE_PARTIAL_SUFFIX: &str = ".zip.part";
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
+/// Kills the script together with everything it spawned (pip, python, venv setup),
+/// otherwise the children keep running detached after the app is gone.
+fn kill_process_tree(pid: u32) {
+    #[cfg(windows)]
+    let result = std::process::Command::new("taskkill")
+        .args(["/PID", pid.to_string().as_str(), "/T", "/F"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .status();
+    // Скрипт запущен в собственной группе процессов, поэтому сигнал уходит всей группе
+    #[cfg(not(windows))]
+    let result = std::process::Command::new("kill")
+        .args(["-TERM", "--", format!("-{pid}").as_str()])
+        .status();
+    if let Err(error) = result {
+        eprintln!("[local_speech] Failed to kill script process {pid}: {error}");
+    }
+}
+
+/// The script interpreter itself could not be found, so waiting for health is pointless.
+fn is_missing_program(error: &anyhow::Error) -> bool {
+    error
+        .root_cause()
+        .downcast_ref::<std::io::Error>()
+        .map(|io_error| io_error.kind() == std::io::ErrorKind::NotFound)
+        .unwrap_or(false)
+}
+
+/// Creates the directory if needed and verifies files can actually be written there.
+async fn check_dir_writable(dir: &Path) -> Result<()> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|error| anyhow!("Cannot create {}: {error}", dir.display()))?;
+    let probe = dir.join(".winky-write-test");
+    tokio::fs::write(&probe, b"ok")
+        .await
+        .map_err(|error| anyhow!("Folder {} is not writable: {error}", dir.display()))?;
+    let _ = tokio::fs::remove_file(&probe).await;
+    Ok(())
+}
+
+fn copy_dir_recursive(source: &Path, target: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let destination = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves a directory, falling back to copy + delete when the target is on another drive.
+async fn move_dir(source: PathBuf, target: PathBuf) -> Result<()> {
+    if tokio::fs::rename(&source, &target).await.is_ok() {
+        return Ok(());
+    }
+    let (copy_source, copy_target) = (source.clone(), target.clone());
+    let copied = spawn_blocking(move || copy_dir_recursive(&copy_source, &copy_target)).await?;
+    if let Err(error) = copied {
+        let _ = tokio::fs::remove_dir_all(&target).await;
+        return Err(anyhow!("Failed to move {} to {}: {error}", source.display(), target.display()));
+    }
+    tokio::fs::remove_dir_all(&source)
+        .await
+        .map_err(|error| anyhow!("Copied install but failed to remove {}: {error}", source.display()))
+}
+
 #[derive(Default)]
 pub struct FastWhisperManager {
     status: AsyncMutex<FastWhisperStatus>,
     lock: AsyncMutex<()>,
     install_override: StdMutex<Option<PathBuf>>,
+    runtime_override: StdMutex<Option<(String, u16)>>,
+    log_seq: AtomicU64,
+    health_poll_interval_ms: AtomicU64,
+    /// Каждый запуск/остановка поллера увеличивает поколение, старые циклы по нему завершаются
+    health_poll_generation: AtomicU64,
+    /// Сервер поднят самим приложением (автозапуск/смена режима), а не пользователем
+    auto_started: AtomicBool,
+    /// Ответ `/info` работающего сервера; сбрасывается при остановке и смене состояния здоровья
+    info_cache: AsyncMutex<Option<LocalSpeechInfo>>,
+    /// 0 — автоостановка по простою выключена
+    idle_stop_minutes: AtomicU64,
+    /// Время последней транскрипции (мс с эпохи), от него отсчитывается простой
+    last_activity_ms: AtomicU64,
+    idle_watch_generation: AtomicU64,
+    /// Сервер остановлен по простою и должен подняться при следующем запросе
+    idle_stopped: AtomicBool,
+    /// Ленивый режим: сервер не стартует с приложением, а поднимается первой транскрипцией
+    lazy_start: AtomicBool,
+    /// PID of the install/start script that is running right now, so it can be killed on exit
+    script_pid: StdMutex<Option<u32>>,
+}
+
+/// What the running server reports about itself; fields it does not expose stay empty.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalSpeechInfo {
+    pub model: Option<String>,
+    pub device: Option<String>,
+    pub compute_type: Option<String>,
+    pub languages: Vec<String>,
+    /// `"info"` or `"health"` — which endpoint the data came from.
+    pub source: String,
+    pub raw: serde_json::Value,
+}
+
+impl LocalSpeechInfo {
+    fn from_payload(raw: serde_json::Value, source: &str) -> Self {
+        let text = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| raw.get(*key).and_then(|value| value.as_str()))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let languages = ["languages", "supported_languages"]
+            .iter()
+            .find_map(|key| raw.get(*key).and_then(|value| value.as_array()))
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            model: text(&["model", "loaded_model", "model_name"]),
+            device: text(&["device"]),
+            compute_type: text(&["compute_type"]),
+            languages,
+            source: source.to_string(),
+            raw,
+        }
+    }
+}
+
+/// Payload of `local-speech:health`, emitted only when the server goes up or down.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalSpeechHealthEvent {
+    pub healthy: bool,
+    pub previous: bool,
+    pub timestamp: i64,
+}
+
+/// One problem found by [`FastWhisperManager::verify_repository`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryIssue {
+    pub path: String,
+    pub problem: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryVerification {
+    pub repo_path: String,
+    pub valid: bool,
+    pub issues: Vec<RepositoryIssue>,
+    /// `"reinstall"` when the install is broken and should be downloaded again.
+    pub suggestion: Option<String>,
+}
+
+/// One line of script output, emitted on `local-speech:log`. `seq` grows
+/// monotonically for the lifetime of the app so the UI can order and dedupe lines.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalSpeechLogLine {
+    pub seq: u64,
+    pub source: String,
+    pub line: String,
+    pub timestamp: i64,
 }
 
 fn install_hint_path(app: &AppHandle) -> Option<PathBuf> {
@@ -217,7 +396,7 @@ pub async fn persist_install_dir_choice(
         return Ok(None);
     };
     let normalized = normalize_install_dir(&raw_dir)
-        .ok_or_else(|| anyhow!("Installation path is empty or invalid"))?;
+        .ok_or_else(|| CommandError::invalid_input("Installation path is empty or invalid"))?;
     tokio::fs::create_dir_all(&normalized).await?;
     let resolved = normalize_saved_path(
         tokio::fs::canonicalize(&normalized)
@@ -261,15 +440,209 @@ impl FastWhisperManager {
             status: AsyncMutex::new(FastWhisperStatus::new("Local server is not installed.")),
             lock: AsyncMutex::new(()),
             install_override: StdMutex::new(None),
+            runtime_override: StdMutex::new(None),
+            log_seq: AtomicU64::new(0),
+            health_poll_interval_ms: AtomicU64::new(DEFAULT_HEALTH_POLL_INTERVAL_MS),
+            health_poll_generation: AtomicU64::new(0),
+            auto_started: AtomicBool::new(false),
+            info_cache: AsyncMutex::new(None),
+            idle_stop_minutes: AtomicU64::new(0),
+            last_activity_ms: AtomicU64::new(0),
+            idle_watch_generation: AtomicU64::new(0),
+            idle_stopped: AtomicBool::new(false),
+            lazy_start: AtomicBool::new(false),
+            script_pid: StdMutex::new(None),
+        }
+    }
+
+    /// Updates the poll interval; a running poller picks it up on its next tick.
+    pub fn set_health_poll_interval_ms(self: &Arc<Self>, app: &AppHandle, interval_ms: u64) {
+        let previous = self.health_poll_interval_ms.swap(interval_ms, Ordering::SeqCst);
+        if previous == 0 && interval_ms > 0 {
+            // Поллер был выключен: поднимаем его, если сервер уже работает
+            let manager = Arc::clone(self);
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if manager.get_status().await.running {
+                    manager.start_health_poller(&app);
+                }
+            });
         }
     }
 
+    fn start_health_poller(self: &Arc<Self>, app: &AppHandle) {
+        let generation = self.health_poll_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let manager = Arc::clone(self);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut last_healthy = true;
+            loop {
+                let interval_ms = manager.health_poll_interval_ms.load(Ordering::SeqCst);
+                if interval_ms == 0 {
+                    break;
+                }
+                sleep(Duration::from_millis(interval_ms)).await;
+                if manager.health_poll_generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                let healthy = manager.is_server_healthy().await;
+                // Пока шёл запрос сервер могли остановить — такой переход не сообщаем
+                if manager.health_poll_generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                if healthy == last_healthy {
+                    continue;
+                }
+                *manager.info_cache.lock().await = None;
+                let _ = app.emit(
+                    "local-speech:health",
+                    LocalSpeechHealthEvent {
+                        healthy,
+                        previous: last_healthy,
+                        timestamp: chrono::Utc::now().timestamp_millis(),
+                    },
+                );
+                manager
+                    .update_status(&app, |status| {
+                        status.running = healthy;
+                        if healthy {
+                            status.phase = "running".into();
+                            status.message = "Server is running.".into();
+                        } else {
+                            status.phase = "idle".into();
+                            status.message = "Server stopped responding.".into();
+                        }
+                    })
+                    .await;
+                last_healthy = healthy;
+            }
+        });
+    }
+
+    fn stop_health_poller(&self) {
+        self.health_poll_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Updates the idle timeout; a running watcher picks it up on its next check.
+    pub fn set_idle_stop_minutes(&self, minutes: u64) {
+        self.idle_stop_minutes.store(minutes, Ordering::SeqCst);
+    }
+
+    pub fn set_lazy_start(&self, enabled: bool) {
+        self.lazy_start.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Records a transcription request so the idle timer starts over.
+    pub fn mark_activity(&self) {
+        let now = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.last_activity_ms.store(now, Ordering::SeqCst);
+    }
+
+    fn start_idle_watcher(self: &Arc<Self>, app: &AppHandle) {
+        let generation = self.idle_watch_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.idle_stopped.store(false, Ordering::SeqCst);
+        self.mark_activity();
+        let manager = Arc::clone(self);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                sleep(IDLE_CHECK_INTERVAL).await;
+                if manager.idle_watch_generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                let minutes = manager.idle_stop_minutes.load(Ordering::SeqCst);
+                if minutes == 0 {
+                    continue;
+                }
+                let now = chrono::Utc::now().timestamp_millis().max(0) as u64;
+                let idle_ms = now.saturating_sub(manager.last_activity_ms.load(Ordering::SeqCst));
+                if idle_ms < minutes.saturating_mul(60_000) {
+                    continue;
+                }
+                crate::logging::log_message(&format!(
+                    "[local_speech] Stopping server after {minutes} minute(s) of inactivity"
+                ));
+                match manager.stop(&app).await {
+                    Ok(_) => {
+                        manager.idle_stopped.store(true, Ordering::SeqCst);
+                        manager
+                            .update_status(&app, |status| {
+                                status.message = format!(
+                                    "Server stopped after {minutes} min of inactivity. It will start on the next transcription."
+                                );
+                            })
+                            .await;
+                    }
+                    Err(error) => crate::logging::log_message(&format!(
+                        "[local_speech] Idle stop failed: {error}"
+                    )),
+                }
+                break;
+            }
+        });
+    }
+
+    fn stop_idle_watcher(&self) {
+        self.idle_watch_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Called before a local transcription: resets the idle timer and brings back a server
+    /// that was stopped for inactivity or, in lazy-start mode, has not been started yet.
+    /// Resolves only after the server passes its health check, so the request waits for it.
+    pub async fn wake(self: &Arc<Self>, app: &AppHandle) -> Result<FastWhisperStatus> {
+        self.mark_activity();
+        let idle_stopped = self.idle_stopped.swap(false, Ordering::SeqCst);
+        let lazy_start = self.lazy_start.load(Ordering::SeqCst);
+        if !idle_stopped && !lazy_start {
+            return Ok(self.get_status().await);
+        }
+        if self.is_server_healthy().await {
+            return Ok(self.get_status().await);
+        }
+        crate::logging::log_message(if idle_stopped {
+            "[local_speech] Restarting server stopped for inactivity"
+        } else {
+            "[local_speech] Lazy-starting server for the first transcription"
+        });
+        let status = self.start_existing(app).await?;
+        self.auto_started.store(status.running, Ordering::SeqCst);
+        Ok(status)
+    }
+
+    /// Overrides host/port taken from the environment and restarts the server on them.
+    pub async fn set_runtime(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        host: String,
+        port: u16,
+    ) -> Result<FastWhisperStatus> {
+        self.execute(app, move |manager, handle| async move {
+            // Останавливаем сервер со старыми host/port, иначе stop-скрипт будет искать его на новом порту
+            manager.stop_server(&handle).await.ok();
+            if let Ok(mut guard) = manager.runtime_override.lock() {
+                *guard = Some((host, port));
+            }
+            manager.start_server(&handle, "restart").await
+        })
+        .await
+    }
+
     pub async fn set_install_override(&self, path: Option<PathBuf>) {
         if let Ok(mut guard) = self.install_override.lock() {
             *guard = path;
         }
     }
 
+    /// Kills a running install/start script with its children; returns whether one was running.
+    pub fn abort_running_script(&self) -> bool {
+        let Some(pid) = self.script_pid.lock().unwrap().take() else {
+            return false;
+        };
+        crate::logging::log_message(&format!("[local_speech] Killing script process {pid}"));
+        kill_process_tree(pid);
+        true
+    }
+
     pub async fn get_status(&self) -> FastWhisperStatus {
         self.status.lock().await.clone()
     }
@@ -294,6 +667,43 @@ impl FastWhisperManager {
             .unwrap_or(false)
     }
 
+    /// Loaded model, device and languages of the running server. Asks `/info` first and
+    /// falls back to the health response body; the result is cached while the server runs.
+    pub async fn info(&self) -> Result<LocalSpeechInfo> {
+        if let Some(cached) = self.info_cache.lock().await.clone() {
+            return Ok(cached);
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        let mut last_error = None;
+        for (source, url) in [
+            ("info", format!("{}/info", self.base_url())),
+            ("health", self.health_endpoint()),
+        ] {
+            let response = match client.get(&url).send().await {
+                Ok(response) if response.status().is_success() => response,
+                Ok(response) => {
+                    last_error = Some(anyhow!("{url} returned {}", response.status()));
+                    continue;
+                }
+                Err(error) => {
+                    last_error = Some(anyhow!("Failed to reach {url}: {error}"));
+                    continue;
+                }
+            };
+            // Health может ответить простым текстом — тогда модели в нём нет, пробуем дальше
+            let Ok(payload) = response.json::<serde_json::Value>().await else {
+                last_error = Some(anyhow!("{url} returned a non-JSON body"));
+                continue;
+            };
+            let info = LocalSpeechInfo::from_payload(payload, source);
+            *self.info_cache.lock().await = Some(info.clone());
+            return Ok(info);
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("Local speech server is not running")))
+    }
+
     pub async fn check_health(self: &Arc<Self>, app: &AppHandle) -> FastWhisperStatus {
         let repo_exists = self.repo_path(app).exists();
         let health_url = self.health_endpoint();
@@ -360,6 +770,8 @@ impl FastWhisperManager {
                     status.error = None;
                 })
                 .await;
+                manager.start_health_poller(&handle);
+                manager.start_idle_watcher(&handle);
                 return Ok(manager.get_status().await);
             }
             
@@ -371,6 +783,22 @@ impl FastWhisperManager {
         .await
     }
 
+    /// Starts the server on the app's behalf unless it is already healthy; such a start is
+    /// remembered so that switching speech mode away can stop it again.
+    pub async fn start_if_auto_enabled(self: &Arc<Self>, app: &AppHandle) -> Result<FastWhisperStatus> {
+        if self.is_server_healthy().await {
+            return Ok(self.get_status().await);
+        }
+        let status = self.start_existing(app).await?;
+        self.auto_started.store(status.running, Ordering::SeqCst);
+        Ok(status)
+    }
+
+    /// Returns whether the running server was auto-started, clearing the flag.
+    pub fn take_auto_started(&self) -> bool {
+        self.auto_started.swap(false, Ordering::SeqCst)
+    }
+
     pub async fn restart(self: &Arc<Self>, app: &AppHandle) -> Result<FastWhisperStatus> {
         self.execute(app, |manager, handle| async move {
             manager.stop_server(&handle).await.ok();
@@ -401,6 +829,66 @@ impl FastWhisperManager {
         .await
     }
 
+    /// Останавливает сервер и удаляет скачанный репозиторий вместе с моделями.
+    pub async fn uninstall(self: &Arc<Self>, app: &AppHandle) -> Result<FastWhisperStatus> {
+        self.execute(app, |manager, handle| async move {
+            manager.stop_server(&handle).await?;
+            let repo_dir = manager.repo_path(&handle);
+            if tokio::fs::metadata(&repo_dir).await.is_ok() {
+                tokio::fs::remove_dir_all(&repo_dir).await?;
+            }
+            manager.update_status(&handle, |status| {
+                status.installed = false;
+                status.running = false;
+                status.phase = "not-installed".into();
+                status.message = "Local server is not installed.".into();
+                status.error = None;
+            })
+            .await;
+            Ok(manager.get_status().await)
+        })
+        .await
+    }
+
+    /// Переносит установленный сервер в новую папку (если он есть) и переключает на неё install root.
+    pub async fn relocate(self: &Arc<Self>, app: &AppHandle, target_root: PathBuf, migrate: bool) -> Result<FastWhisperStatus> {
+        self.execute(app, move |manager, handle| async move {
+            check_dir_writable(&target_root).await?;
+            let current_repo = manager.repo_path(&handle);
+            let target_repo = target_root.join(FAST_WHISPER_REPO_NAME);
+            if migrate && !paths_equal(&current_repo, &target_repo) && current_repo.exists() {
+                if target_repo.exists() {
+                    return Err(anyhow!(
+                        "{} already exists; remove it or choose another folder",
+                        target_repo.display()
+                    ));
+                }
+                manager.stop_server(&handle).await.ok();
+                manager
+                    .update_status(&handle, |status| {
+                        status.message = format!("Moving local server to {}…", target_root.display());
+                    })
+                    .await;
+                move_dir(current_repo, target_repo.clone()).await?;
+            }
+            manager.set_install_override(Some(target_root.clone())).await;
+            remember_install_dir_in_process(&target_root);
+            write_install_hint(&handle, &target_root).await?;
+            let installed = target_repo.exists();
+            manager
+                .update_status(&handle, |status| {
+                    status.installed = installed;
+                    status.running = false;
+                    status.phase = if installed { "idle".into() } else { "not-installed".into() };
+                    status.message = format!("Install location set to {}.", target_root.display());
+                    status.error = None;
+                })
+                .await;
+            Ok(manager.get_status().await)
+        })
+        .await
+    }
+
     pub async fn is_model_downloaded(
         &self,
         app: &AppHandle,
@@ -479,6 +967,7 @@ impl FastWhisperManager {
         let mut guard = self.status.lock().await;
         update(&mut guard);
         guard.install_dir = Some(install_dir.to_string_lossy().to_string());
+        guard.base_url = Some(self.base_url());
         guard.updated_at = chrono::Utc::now().timestamp_millis();
         let _ = app.emit("local-speech:status", guard.clone());
     }
@@ -500,7 +989,7 @@ impl FastWhisperManager {
             state.message = format!("Downloading repository from {FAST_WHISPER_REPO_URL}…");
         })
         .await;
-        let archive = self.download_repository_archive().await?;
+        let archive = self.download_repository_archive(app).await?;
         self.update_status(app, |state| {
             state.message = "Extracting repository…".into();
         })
@@ -544,15 +1033,129 @@ impl FastWhisperManager {
         Ok(())
     }
 
-    async fn download_repository_archive(&self) -> Result<Vec<u8>> {
-        let response = reqwest::get(FAST_WHISPER_REPO_ARCHIVE_URL).await?;
+    async fn download_repository_archive(&self, app: &AppHandle) -> Result<Vec<u8>> {
+        let partial_path = self
+            .install_root(app)
+            .join(format!("{FAST_WHISPER_REPO_NAME}{ARCHIVE_PARTIAL_SUFFIX}"));
+        let mut last_error = None;
+        let mut expected_size = None;
+        for attempt in 1..=ARCHIVE_DOWNLOAD_ATTEMPTS {
+            let downloaded = tokio::fs::metadata(&partial_path)
+                .await
+                .map(|meta| meta.len())
+                .unwrap_or(0);
+            if attempt > 1 {
+                self.update_status(app, |state| {
+                    state.message = format!(
+                        "Resuming download ({:.1} MB received, attempt {attempt}/{ARCHIVE_DOWNLOAD_ATTEMPTS})…",
+                        downloaded as f64 / (1024.0 * 1024.0)
+                    );
+                })
+                .await;
+            }
+            match Self::download_archive_to(&partial_path, downloaded).await {
+                Ok(size) => {
+                    expected_size = size;
+                    last_error = None;
+                    break;
+                }
+                Err(error) => {
+                    crate::logging::log_message(&format!(
+                        "[local_speech] archive download attempt {attempt} failed: {error}"
+                    ));
+                    last_error = Some(error);
+                    if attempt < ARCHIVE_DOWNLOAD_ATTEMPTS {
+                        sleep(ARCHIVE_RETRY_DELAY).await;
+                    }
+                }
+            }
+        }
+        if let Some(error) = last_error {
+            return Err(error.context(format!(
+                "Failed to download repository archive after {ARCHIVE_DOWNLOAD_ATTEMPTS} attempts"
+            )));
+        }
+        let archive = tokio::fs::read(&partial_path).await?;
+        // Архив проверяется до распаковки: битый файл удаляем, чтобы следующая попытка качала заново
+        if let Err(error) = Self::verify_archive(&archive, expected_size) {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+            return Err(error);
+        }
+        let _ = tokio::fs::remove_file(&partial_path).await;
+        Ok(archive)
+    }
+
+    /// Downloads the archive into `partial_path`, continuing from `downloaded` bytes
+    /// when the server honours range requests. Returns the expected total size if known.
+    async fn download_archive_to(partial_path: &Path, downloaded: u64) -> Result<Option<u64>> {
+        let client = reqwest::Client::new();
+        let mut request = client.get(FAST_WHISPER_REPO_ARCHIVE_URL);
+        if downloaded > 0 {
+            request = request.header(RANGE, format!("bytes={downloaded}-"));
+        }
+        let response = request.send().await?;
         let status = response.status();
-        if !status.is_success() {
-            return Err(anyhow!(
-                "Failed to download repository archive: HTTP {status}"
-            ));
+        let content_range_total = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit('/').next())
+            .and_then(|total| total.trim().parse::<u64>().ok());
+        let (resume, expected_size) = match status {
+            StatusCode::PARTIAL_CONTENT => (
+                true,
+                content_range_total.or_else(|| response.content_length().map(|len| downloaded + len)),
+            ),
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                if content_range_total == Some(downloaded) {
+                    return Ok(Some(downloaded));
+                }
+                let _ = tokio::fs::remove_file(partial_path).await;
+                return Err(anyhow!(
+                    "Partial archive does not match the remote file, restarting download"
+                ));
+            }
+            status if status.is_success() => (false, response.content_length()),
+            status => {
+                return Err(anyhow!(
+                    "Failed to download repository archive: HTTP {status}"
+                ));
+            }
+        };
+        let mut file = if resume {
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(partial_path)
+                .await?
+        } else {
+            tokio::fs::File::create(partial_path).await?
+        };
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            file.write_all(&chunk?).await?;
         }
-        Ok(response.bytes().await?.to_vec())
+        file.flush().await?;
+        Ok(expected_size)
+    }
+
+    fn verify_archive(archive: &[u8], expected_size: Option<u64>) -> Result<()> {
+        if let Some(expected) = expected_size {
+            if archive.len() as u64 != expected {
+                return Err(anyhow!(
+                    "Downloaded archive size mismatch: expected {expected} bytes, got {}",
+                    archive.len()
+                ));
+            }
+        }
+        let mut zip = ZipArchive::new(Cursor::new(archive))
+            .map_err(|error| anyhow!("Downloaded archive is corrupted: {error}"))?;
+        for index in 0..zip.len() {
+            let mut entry = zip.by_index(index)?;
+            // Чтение до конца проверяет CRC32 каждой записи
+            std::io::copy(&mut entry, &mut std::io::sink())
+                .map_err(|error| anyhow!("Downloaded archive is corrupted ({}): {error}", entry.name()))?;
+        }
+        Ok(())
     }
 
     fn extract_repository_archive(archive: Vec<u8>, target_dir: PathBuf) -> Result<()> {
@@ -622,6 +1225,7 @@ impl FastWhisperManager {
         let (command, args) = self.start_command(app);
         let script_error = match self.run_script(app, &command, &args, "start").await {
             Ok(_) => None,
+            Err(error) if is_missing_program(&error) => return Err(error),
             Err(error) => {
                 let message = error.to_string();
                 self.update_status(app, |state| {
@@ -660,10 +1264,16 @@ impl FastWhisperManager {
             state.last_success_at = Some(chrono::Utc::now().timestamp_millis());
         })
         .await;
+        self.start_health_poller(app);
+        self.start_idle_watcher(app);
         Ok(self.get_status().await)
     }
 
     async fn stop_server(self: &Arc<Self>, app: &AppHandle) -> Result<()> {
+        self.stop_health_poller();
+        self.stop_idle_watcher();
+        self.auto_started.store(false, Ordering::SeqCst);
+        *self.info_cache.lock().await = None;
         if !self.repo_path(app).exists() {
             return Ok(());
         }
@@ -688,8 +1298,24 @@ impl FastWhisperManager {
         {
             process.creation_flags(CREATE_NO_WINDOW);
         }
+        #[cfg(unix)]
+        {
+            process.process_group(0);
+        }
 
-        let mut child = process.spawn()?;
+        let mut child = process.spawn().map_err(|error| {
+            // Установка идёт через zip-архив и git не нужен, но скриптам нужен интерпретатор
+            let message = if error.kind() == std::io::ErrorKind::NotFound {
+                format!("{command} is not installed or not on PATH; install it to run the {label} script")
+            } else {
+                format!("Failed to launch {label} script via {command}")
+            };
+            anyhow::Error::new(error).context(message)
+        })?;
+        // Остановка быстрая и нужна при выходе сама, её не прерываем
+        if label != "stop" {
+            *self.script_pid.lock().unwrap() = child.id();
+        }
         let (tx, mut rx) = mpsc::unbounded_channel::<String>();
 
         if let Some(stdout) = child.stdout.take() {
@@ -720,17 +1346,27 @@ impl FastWhisperManager {
                 continue;
             }
             let message = trimmed.to_string();
+            // Сырой вывод идёт в отдельный канал, status.message остаётся кратким статусом
+            let _ = app.emit(
+                "local-speech:log",
+                LocalSpeechLogLine {
+                    seq: self.log_seq.fetch_add(1, Ordering::Relaxed) + 1,
+                    source: label.to_string(),
+                    line: message.clone(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                },
+            );
             self.update_status(app, |state| {
                 state.log_line = Some(message.clone());
-                if matches!(state.phase.as_str(), "installing" | "starting" | "reinstalling") {
-                    state.message = message.clone();
-                }
             })
             .await;
         }
 
-        let status = child.wait().await?;
-        if !status.success() {
+        let status = child.wait().await;
+        if label != "stop" {
+            *self.script_pid.lock().unwrap() = None;
+        }
+        if !status?.success() {
             return Err(anyhow!("{label} script failed"));
         }
 
@@ -794,10 +1430,69 @@ impl FastWhisperManager {
         resolved
     }
 
-    fn repo_path(&self, app: &AppHandle) -> PathBuf {
+    pub fn repo_path(&self, app: &AppHandle) -> PathBuf {
         self.install_root(app).join(FAST_WHISPER_REPO_NAME)
     }
 
+    /// Scripts the start/stop commands rely on for the current platform.
+    fn required_scripts() -> [&'static str; 2] {
+        if cfg!(target_os = "windows") {
+            ["start.bat", "stop.bat"]
+        } else {
+            ["start-unix.sh", "stop-unix.sh"]
+        }
+    }
+
+    /// Checks that the downloaded repository is complete enough to start.
+    pub async fn verify_repository(&self, app: &AppHandle) -> RepositoryVerification {
+        let repo_dir = self.repo_path(app);
+        let mut issues = Vec::new();
+        let mut issue = |path: &Path, problem: &str| {
+            issues.push(RepositoryIssue {
+                path: path.display().to_string(),
+                problem: problem.to_string(),
+            });
+        };
+
+        match tokio::fs::metadata(&repo_dir).await {
+            Ok(meta) if meta.is_dir() => {
+                let has_entries = match tokio::fs::read_dir(&repo_dir).await {
+                    Ok(mut entries) => matches!(entries.next_entry().await, Ok(Some(_))),
+                    Err(_) => false,
+                };
+                if !has_entries {
+                    issue(&repo_dir, "Repository directory is empty or unreadable");
+                }
+                for script in Self::required_scripts() {
+                    let path = repo_dir.join(script);
+                    match tokio::fs::metadata(&path).await {
+                        Ok(meta) if meta.is_file() && meta.len() > 0 => {}
+                        Ok(meta) if meta.is_file() => issue(&path, "Script is empty"),
+                        Ok(_) => issue(&path, "Expected a file"),
+                        Err(_) => issue(&path, "Script is missing"),
+                    }
+                }
+            }
+            Ok(_) => issue(&repo_dir, "Expected a directory"),
+            Err(_) => issue(&repo_dir, "Repository is not installed"),
+        }
+
+        let partial_archive = self
+            .install_root(app)
+            .join(format!("{FAST_WHISPER_REPO_NAME}{ARCHIVE_PARTIAL_SUFFIX}"));
+        if tokio::fs::metadata(&partial_archive).await.is_ok() {
+            issue(&partial_archive, "Interrupted download left a partial archive");
+        }
+
+        let valid = issues.is_empty();
+        RepositoryVerification {
+            repo_path: repo_dir.display().to_string(),
+            valid,
+            issues,
+            suggestion: (!valid).then(|| "reinstall".to_string()),
+        }
+    }
+
     fn start_command(&self, app: &AppHandle) -> (String, Vec<String>) {
         if cfg!(target_os = "windows") {
             (
@@ -837,8 +1532,8 @@ impl FastWhisperManager {
     fn script_env(&self) -> Vec<(String, String)> {
         let mut env: Vec<(String, String)> = std::env::vars().collect();
         env.push(("PAUSE_SECONDS".into(), "0".into()));
-        env.push(("FAST_FAST_WHISPER_PORT".into(), Self::resolve_port().to_string()));
-        env.push(("FAST_FAST_WHISPER_HOST".into(), Self::resolve_host()));
+        env.push(("FAST_FAST_WHISPER_PORT".into(), self.port().to_string()));
+        env.push(("FAST_FAST_WHISPER_HOST".into(), self.host()));
         env
     }
 
@@ -888,7 +1583,7 @@ impl FastWhisperManager {
         candidates
     }
 
-    fn resolve_port() -> u16 {
+    pub fn resolve_port() -> u16 {
         std::env::var("FAST_FAST_WHISPER_PORT")
             .or_else(|_| std::env::var("PORT"))
             .ok()
@@ -896,15 +1591,41 @@ impl FastWhisperManager {
             .unwrap_or(FAST_WHISPER_PORT)
     }
 
-    fn resolve_host() -> String {
+    pub fn resolve_host() -> String {
         std::env::var("FAST_FAST_WHISPER_HOST")
             .or_else(|_| std::env::var("HOST"))
             .unwrap_or_else(|_| "127.0.0.1".into())
     }
 
+    /// Порт с учётом переопределения из `local_speech_set_runtime`.
+    pub fn port(&self) -> u16 {
+        self.runtime_override
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|(_, port)| *port))
+            .unwrap_or_else(Self::resolve_port)
+    }
+
+    /// Хост с учётом переопределения из `local_speech_set_runtime`.
+    pub fn host(&self) -> String {
+        self.runtime_override
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|(host, _)| host.clone()))
+            .unwrap_or_else(Self::resolve_host)
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", self.host(), self.port())
+    }
+
+    pub fn transcribe_endpoint(&self) -> String {
+        format!("{}/v1/audio/transcriptions", self.base_url())
+    }
+
     fn health_endpoint(&self) -> String {
-        let host = Self::resolve_host();
-        let port = Self::resolve_port();
+        let host = self.host();
+        let port = self.port();
         if host == "127.0.0.1" && port == FAST_WHISPER_PORT {
             FAST_WHISPER_HEALTH_ENDPOINT.into()
         } else {
@@ -3,44 +3,64 @@
 
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 use tauri::{AppHandle, Manager};
 
+use crate::local_speech::FastWhisperManager;
+use crate::oauth;
+use crate::types::AppConfig;
+
 static LOG_FILE: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
 
+const LOG_FILE_NAME: &str = "winky.log";
+const LOG_DIR_NAME: &str = "logs";
+const MAX_LOG_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+/// Сколько старых поколений лога (winky.1.log … winky.N.log) хранить при ротации
+const MAX_LOG_GENERATIONS: usize = 3;
+
+/// Папки-кандидаты для логов в порядке предпочтения. Папка установки (resource_dir)
+/// идёт последней: в Program Files она обычно доступна только на чтение.
+fn candidate_log_dirs(app: &AppHandle) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = app.path().app_local_data_dir() {
+        dirs.push(dir.join(LOG_DIR_NAME));
+    }
+    if let Ok(dir) = app.path().resource_dir() {
+        dirs.push(dir);
+    }
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
+    {
+        dirs.push(dir);
+    }
+    dirs.push(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    dirs
+}
+
+fn is_writable_log_file(log_file: &Path) -> bool {
+    log_file
+        .parent()
+        .is_none_or(|dir| std::fs::create_dir_all(dir).is_ok())
+        && OpenOptions::new().create(true).append(true).open(log_file).is_ok()
+}
+
 /// Инициализирует логирование в файл
-/// Логи сохраняются в папке установки приложения рядом с exe файлом
+/// Логи сохраняются в app_local_data_dir/logs, при недоступности — рядом с exe
 pub fn init_logging(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    // Пробуем получить папку ресурсов (рядом с exe в production)
-    let log_dir = match app.path().resource_dir() {
-        Ok(dir) => dir,
-        Err(_) => {
-            // Fallback: пробуем получить папку exe
-            std::env::current_exe()
-                .ok()
-                .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
-                .ok_or_else(|| "Failed to get resource dir or exe dir".to_string())?
-        }
-    };
+    let candidates = candidate_log_dirs(app);
+    let log_file = candidates
+        .iter()
+        .map(|dir| dir.join(LOG_FILE_NAME))
+        .find(|file| is_writable_log_file(file))
+        .ok_or_else(|| format!("No writable log directory, tried: {:?}", candidates))?;
     
-    // Создаём папку если её нет (на случай если это не resource_dir)
-    if let Err(_) = std::fs::create_dir_all(&log_dir) {
-        // Если не получилось создать, пробуем использовать текущую директорию
-        let fallback_dir = std::env::current_dir()
-            .unwrap_or_else(|_| PathBuf::from("."));
-        let log_file = fallback_dir.join("winky.log");
-        *LOG_FILE.lock().unwrap() = Some(log_file);
-        return Ok(());
-    }
-    
-    let log_file = log_dir.join("winky.log");
-    
-    // Очищаем старый лог если он больше 10MB
+    // Ротируем лог, если он разросся больше 10MB
     if let Ok(metadata) = std::fs::metadata(&log_file) {
-        if metadata.len() > 10 * 1024 * 1024 {
-            let _ = std::fs::remove_file(&log_file);
+        if metadata.len() > MAX_LOG_SIZE_BYTES {
+            let _ = rotate_at(&log_file);
         }
     }
     
@@ -48,10 +68,51 @@ pub fn init_logging(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     
     // Пишем начальное сообщение
     log_message("=== Winky started ===");
+    log_message(&format!("Log file: {}", log_file.display()));
     
     Ok(())
 }
 
+/// Описание окружения для лога и диагностического архива.
+/// Секреты не выводятся - только признак их наличия.
+pub fn diagnostics_lines(app: &AppHandle, config: &AppConfig, config_path: &Path) -> Vec<String> {
+    let package = app.package_info();
+    let data_dir = app
+        .path()
+        .app_local_data_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|error| format!("<unavailable: {error}>"));
+    let present = |value: &str| if value.trim().is_empty() { "missing" } else { "present" };
+
+    vec![
+        format!("version: {}", package.version),
+        format!("os: {} ({})", std::env::consts::OS, std::env::consts::ARCH),
+        format!("admin: {}", oauth::is_running_as_admin()),
+        format!("config path: {}", config_path.display()),
+        format!("data dir: {}", data_dir),
+        format!(
+            "whisper server: {}:{}",
+            FastWhisperManager::resolve_host(),
+            FastWhisperManager::resolve_port()
+        ),
+        format!("backend domain: {}", config.backend_domain),
+        format!("speech: mode={} model={}", config.speech.mode, config.speech.model),
+        format!("llm: mode={} model={}", config.llm.mode, config.llm.model),
+        format!("openai key: {}", present(&config.api_keys.openai)),
+        format!("google key: {}", present(&config.api_keys.google)),
+        format!("auth token: {}", present(&config.auth.access)),
+    ]
+}
+
+/// Пишет в лог блок с описанием окружения, чтобы каждый баг-репорт был самодостаточным.
+pub fn log_startup_diagnostics(app: &AppHandle, config: &AppConfig, config_path: &Path) {
+    log_message("=== Startup diagnostics ===");
+    for line in diagnostics_lines(app, config, config_path) {
+        log_message(&line);
+    }
+    log_message("===========================");
+}
+
 /// Получает путь к файлу логов
 pub fn get_log_file_path(app: &AppHandle) -> Option<PathBuf> {
     // Сначала пробуем из статической переменной
@@ -61,14 +122,72 @@ pub fn get_log_file_path(app: &AppHandle) -> Option<PathBuf> {
         }
     }
     
-    // Fallback: пробуем получить из resource_dir
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        Some(resource_dir.join("winky.log"))
-    } else if let Ok(exe_path) = std::env::current_exe() {
-        exe_path.parent().map(|p| p.join("winky.log"))
-    } else {
-        None
+    // Fallback: логирование ещё не инициализировано, берём первую подходящую папку
+    candidate_log_dirs(app)
+        .into_iter()
+        .next()
+        .map(|dir| dir.join(LOG_FILE_NAME))
+}
+
+fn generation_path(log_path: &Path, generation: usize) -> PathBuf {
+    log_path.with_file_name(format!("winky.{generation}.log"))
+}
+
+/// Сдвигает поколения: winky.log -> winky.1.log -> … ; самое старое удаляется
+fn rotate_at(log_path: &Path) -> std::io::Result<()> {
+    let oldest = generation_path(log_path, MAX_LOG_GENERATIONS);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+    }
+    for generation in (1..MAX_LOG_GENERATIONS).rev() {
+        let from = generation_path(log_path, generation);
+        if from.exists() {
+            std::fs::rename(&from, generation_path(log_path, generation + 1))?;
+        }
+    }
+    if log_path.exists() {
+        std::fs::rename(log_path, generation_path(log_path, 1))?;
     }
+    Ok(())
+}
+
+/// Текущий лог и сохранённые поколения, которые существуют на диске
+pub fn existing_log_files() -> Vec<PathBuf> {
+    let guard = LOG_FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(log_path) = guard.as_ref() else {
+        return Vec::new();
+    };
+    std::iter::once(log_path.clone())
+        .chain((1..=MAX_LOG_GENERATIONS).map(|generation| generation_path(log_path, generation)))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Принудительно ротирует текущий лог; новые записи пойдут в чистый winky.log
+pub fn rotate_log_file() -> std::io::Result<()> {
+    {
+        let guard = LOG_FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(log_path) = guard.as_ref() {
+            rotate_at(log_path)?;
+        }
+    }
+    log_message("=== Log rotated ===");
+    Ok(())
+}
+
+/// Очищает текущий файл логов, не удаляя его, и удаляет старые поколения
+pub fn clear_log_file() -> std::io::Result<()> {
+    let guard = LOG_FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(log_path) = guard.as_ref() {
+        std::fs::write(log_path, "")?;
+        for generation in 1..=MAX_LOG_GENERATIONS {
+            let path = generation_path(log_path, generation);
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Записывает сообщение в лог файл
@@ -1,46 +1,72 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod actions;
 mod audio;
+mod audio_self_test;
 mod auth;
 mod config;
 mod constants;
 mod deep_link_file;
+mod diagnostics;
+mod entry_count;
+mod error;
 mod hotkeys;
 mod history;
 mod gemini;
 mod notes;
+mod llm_streams;
 mod local_speech;
 mod logging;
+mod mic_permission;
+mod mouse_trigger;
 mod oauth;
 mod oauth_server;
+mod process_info;
+mod rate_limit;
 mod ollama;
 mod openai;
+mod repair;
 mod resources;
+mod speech_languages;
+mod storage;
+mod system_dependencies;
+mod text_input;
+mod transcription;
 mod tray;
 mod types;
+mod warmup;
+mod window_layout;
 
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
 
+use actions::ActionUsageEntry;
 use auth::AuthQueue;
 use serde::Deserialize;
-use config::{should_auto_start_local_speech, ConfigState};
-use hotkeys::{ActionHotkeyInput, HotkeyState};
+use config::{should_auto_start_local_speech, ConfigIntegrity, ConfigState, ConfigUpdateResult};
+use error::{CommandError, CommandResult};
+use hotkeys::{ActionHotkeyInput, HotkeyState, ReservedHotkey};
 use history::{
     append_history,
     clear_history,
+    export_history_audio,
     read_history,
     read_history_audio,
     save_history_audio,
     ActionHistoryEntry,
     ActionHistoryInput,
     ActionHistoryUpdateInput,
+    HistoryListResponse,
     update_history,
 };
 use notes::{
+    append_to_note,
     bulk_delete_notes,
     create_note,
     delete_note,
+    duplicate_note,
     list_notes,
+    list_notes_by_username,
     update_note,
     NoteBulkDeleteInput,
     NoteBulkDeleteResponse,
@@ -48,17 +74,26 @@ use notes::{
     NoteDeleteInput,
     NoteEntry,
     NoteListResponse,
+    NoteDateRange,
+    NoteSort,
+    NoteTagCount,
     NoteUpdateInput,
 };
-use local_speech::{persist_install_dir_choice, FastWhisperManager};
+use local_speech::{persist_install_dir_choice, FastWhisperManager, LocalSpeechInfo, RepositoryVerification};
 use oauth_server::OAuthServerState;
+use process_info::{ProcessEntry, ResourceUsage};
 use once_cell::sync::Lazy;
 use serde_json::json;
 use tauri::{Emitter, Manager, State};
 use tauri::window::Color;
 use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_autostart::ManagerExt;
-use types::{AppConfig, AuthDeepLinkPayload, AuthTokens, FastWhisperStatus};
+use audio_self_test::AudioSelfTestResult;
+use repair::RepairReport;
+use speech_languages::SpeechLanguage;
+use storage::StorageCategoryUsage;
+use transcription::{BatchTranscriptionResult, SpeechProvider, TranscriptionBenchmark};
+use types::{AppConfig, AuthDeepLinkPayload, AuthTokens, FastWhisperStatus, WindowPosition};
 
 static PENDING_DEEP_LINKS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
@@ -73,6 +108,16 @@ struct NotesListArgs {
     page: Option<u32>,
     #[serde(alias = "pageSize", alias = "page_size")]
     page_size: Option<u32>,
+    #[serde(default, alias = "sortBy", alias = "sort_by")]
+    sort_by: Option<String>,
+    #[serde(default)]
+    direction: Option<String>,
+    #[serde(default, alias = "createdFrom", alias = "created_from")]
+    created_from: Option<String>,
+    #[serde(default, alias = "createdTo", alias = "created_to")]
+    created_to: Option<String>,
+    #[serde(default, alias = "includeArchived", alias = "include_archived")]
+    include_archived: bool,
 }
 
 #[derive(Deserialize)]
@@ -89,7 +134,7 @@ struct HistoryReadAudioInput {
 }
 
 #[tauri::command]
-async fn config_get(state: State<'_, Arc<ConfigState>>) -> Result<AppConfig, String> {
+async fn config_get(state: State<'_, Arc<ConfigState>>) -> CommandResult<AppConfig> {
     Ok(state.get().await)
 }
 
@@ -100,26 +145,122 @@ async fn config_update(
     hotkeys: State<'_, Arc<HotkeyState>>,
     speech: State<'_, Arc<FastWhisperManager>>,
     payload: serde_json::Value,
-) -> Result<AppConfig, String> {
+) -> CommandResult<ConfigUpdateResult> {
     // Проверяем, изменяется ли настройка автозапуска
     let autostart_changed = payload
         .get("launchOnSystemStartup")
         .and_then(|v| v.as_bool())
         .is_some();
     
-    let updated = state
+    let result = state
         .update(payload)
-        .await
-        .map_err(|error| error.to_string())?;
+        .await?;
+    let updated = &result.config;
     
     // Обновляем автозапуск системы, если настройка изменилась
     if autostart_changed {
         update_autostart(&app, updated.launch_on_system_startup)
-            .map_err(|error| format!("Failed to update autostart: {}", error))?;
+            .map_err(|error| CommandError::from(format!("Failed to update autostart: {}", error)))?;
     }
     
-    app.emit("config:updated", &updated)
-        .map_err(|error| error.to_string())?;
+    app.emit("config:updated", updated)?;
+    if result.changed_paths.iter().any(|path| path == "overlayTheme") {
+        emit_overlay_theme(&app, &updated.overlay_theme)?;
+    }
+    // Побочные эффекты имеют смысл только если merge действительно что-то изменил
+    if !result.changed_paths.is_empty() {
+        handle_config_effects(
+            &app,
+            updated,
+            hotkeys.inner().clone(),
+            speech.inner().clone(),
+        );
+    }
+    Ok(result)
+}
+
+fn emit_overlay_theme(app: &tauri::AppHandle, theme: &str) -> tauri::Result<()> {
+    app.emit("overlay:theme-changed", json!({"theme": theme}))
+}
+
+#[tauri::command]
+async fn overlay_themes() -> CommandResult<Vec<String>> {
+    Ok(constants::OVERLAY_THEMES.iter().map(|theme| theme.to_string()).collect())
+}
+
+#[tauri::command]
+async fn overlay_set_theme(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    theme: String,
+) -> CommandResult<AppConfig> {
+    let theme = theme.trim().to_ascii_lowercase();
+    if !constants::OVERLAY_THEMES.contains(&theme.as_str()) {
+        return Err(CommandError::invalid_input(format!(
+            "Unknown overlay theme \"{theme}\"; expected one of: {}",
+            constants::OVERLAY_THEMES.join(", ")
+        )));
+    }
+    let updated = state
+        .update_with(|config| {
+            config.overlay_theme = theme.clone();
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    emit_overlay_theme(&app, &updated.overlay_theme)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn llm_set_output_format(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    format: String,
+) -> CommandResult<AppConfig> {
+    let format = format.trim().to_ascii_lowercase();
+    if !constants::LLM_OUTPUT_FORMATS.contains(&format.as_str()) {
+        return Err(CommandError::invalid_input(format!(
+            "Unknown output format \"{format}\"; expected one of: {}",
+            constants::LLM_OUTPUT_FORMATS.join(", ")
+        )));
+    }
+    let updated = state
+        .update_with(|config| {
+            config.llm_output_format = format.clone();
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+/// Лёгкое событие для окон с login-gated UI: без токенов, только факт входа и провайдер
+fn emit_auth_changed(app: &tauri::AppHandle, tokens: &AuthTokens, provider: Option<&str>) -> tauri::Result<()> {
+    let authenticated = !tokens.access.trim().is_empty() || !tokens.access_token.trim().is_empty();
+    app.emit(
+        "auth:changed",
+        json!({
+            "authenticated": authenticated,
+            "provider": provider.filter(|_| authenticated),
+        }),
+    )
+}
+
+#[tauri::command]
+async fn config_set_auth(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    hotkeys: State<'_, Arc<HotkeyState>>,
+    speech: State<'_, Arc<FastWhisperManager>>,
+    tokens: AuthTokens,
+    provider: Option<String>,
+) -> CommandResult<AppConfig> {
+    let updated = state
+        .set_auth_tokens(tokens)
+        .await?;
+    app.emit("config:updated", &updated)?;
+    emit_auth_changed(&app, &updated.auth, provider.as_deref())?;
     handle_config_effects(
         &app,
         &updated,
@@ -130,19 +271,15 @@ async fn config_update(
 }
 
 #[tauri::command]
-async fn config_set_auth(
+async fn auth_logout(
     app: tauri::AppHandle,
     state: State<'_, Arc<ConfigState>>,
     hotkeys: State<'_, Arc<HotkeyState>>,
     speech: State<'_, Arc<FastWhisperManager>>,
-    tokens: AuthTokens,
-) -> Result<AppConfig, String> {
-    let updated = state
-        .set_auth_tokens(tokens)
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("config:updated", &updated)
-        .map_err(|error| error.to_string())?;
+) -> CommandResult<AppConfig> {
+    let updated = state.set_auth_tokens(AuthTokens::default()).await?;
+    app.emit("config:updated", &updated)?;
+    emit_auth_changed(&app, &updated.auth, None)?;
     handle_config_effects(
         &app,
         &updated,
@@ -152,19 +289,58 @@ async fn config_set_auth(
     Ok(updated)
 }
 
+/// Повторная отметка шага не сдвигает время его первого прохождения
+#[tauri::command]
+async fn onboarding_mark_step(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+) -> CommandResult<HashMap<String, String>> {
+    let id = id.trim().to_string();
+    if id.is_empty() {
+        return Err(CommandError::invalid_input("Onboarding step id is empty"));
+    }
+    let updated = state
+        .update_with(|config| {
+            config
+                .onboarding_state
+                .entry(id)
+                .or_insert_with(|| chrono::Utc::now().to_rfc3339());
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated.onboarding_state)
+}
+
+/// `setup_completed` is left as is; only the per-step progress is cleared.
+#[tauri::command]
+async fn onboarding_reset(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+) -> CommandResult<()> {
+    let updated = state
+        .update_with(|config| {
+            config.onboarding_state.clear();
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(())
+}
+
 #[tauri::command]
 async fn config_reset(
     app: tauri::AppHandle,
     state: State<'_, Arc<ConfigState>>,
     hotkeys: State<'_, Arc<HotkeyState>>,
     speech: State<'_, Arc<FastWhisperManager>>,
-) -> Result<AppConfig, String> {
+) -> CommandResult<AppConfig> {
     let updated = state
         .reset()
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("config:updated", &updated)
-        .map_err(|error| error.to_string())?;
+        .await?;
+    app.emit("config:updated", &updated)?;
+    emit_auth_changed(&app, &updated.auth, None)?;
     handle_config_effects(
         &app,
         &updated,
@@ -175,27 +351,60 @@ async fn config_reset(
 }
 
 #[tauri::command]
-async fn config_path(state: State<'_, Arc<ConfigState>>) -> Result<String, String> {
+async fn config_path(state: State<'_, Arc<ConfigState>>) -> CommandResult<String> {
     Ok(state.path().await.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-async fn history_get(app: tauri::AppHandle) -> Result<Vec<ActionHistoryEntry>, String> {
+async fn config_field_default(path: String) -> CommandResult<serde_json::Value> {
+    if path.trim().is_empty() {
+        return Err(CommandError::invalid_input("Config path cannot be empty"));
+    }
+    config::default_field_value(&path)?
+        .ok_or_else(|| CommandError::not_found(format!("Unknown config field: {path}")))
+}
+
+#[tauri::command]
+async fn config_verify_integrity(state: State<'_, Arc<ConfigState>>) -> CommandResult<ConfigIntegrity> {
+    state.verify_integrity().await.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_get(app: tauri::AppHandle) -> CommandResult<Vec<ActionHistoryEntry>> {
     read_history(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_list(
+    app: tauri::AppHandle,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    query: Option<String>,
+) -> CommandResult<HistoryListResponse> {
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(20).max(1);
+    history::list_history(&app, page, page_size, query.as_deref())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_count(app: tauri::AppHandle) -> CommandResult<usize> {
+    history::count_history(&app).await.map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn history_add(
     app: tauri::AppHandle,
     payload: ActionHistoryInput,
-) -> Result<ActionHistoryEntry, String> {
-    let entry = append_history(&app, payload)
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("history:updated", json!({"type": "added", "entry": &entry}))
-        .map_err(|error| error.to_string())?;
+) -> CommandResult<ActionHistoryEntry> {
+    let entry = append_history(&app, payload).await?;
+    if let Err(error) = actions::record_usage(&app, &entry.action_id).await {
+        logging::log_message(&format!("[history_add] Failed to record action usage: {}", error));
+    }
+    app.emit("history:updated", json!({"type": "added", "entry": &entry}))?;
     Ok(entry)
 }
 
@@ -203,82 +412,395 @@ async fn history_add(
 async fn history_update(
     app: tauri::AppHandle,
     payload: ActionHistoryUpdateInput,
-) -> Result<ActionHistoryEntry, String> {
-    let entry = update_history(&app, payload)
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("history:updated", json!({"type": "updated", "entry": &entry}))
-        .map_err(|error| error.to_string())?;
+) -> CommandResult<ActionHistoryEntry> {
+    let entry = update_history(&app, payload).await?;
+    app.emit("history:updated", json!({"type": "updated", "entry": &entry}))?;
     Ok(entry)
 }
 
 #[tauri::command]
-async fn history_clear(app: tauri::AppHandle) -> Result<(), String> {
-    clear_history(&app)
+async fn actions_recent(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    limit: Option<u32>,
+) -> CommandResult<Vec<ActionUsageEntry>> {
+    let config = state.get().await;
+    let limit = limit.unwrap_or(5).max(1) as usize;
+    actions::recent_actions(&app, &config, limit)
         .await
-        .map_err(|error| error.to_string())?;
-    app.emit("history:updated", json!({"type": "cleared"}))
-        .map_err(|error| error.to_string())?;
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn actions_most_used(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    limit: Option<u32>,
+) -> CommandResult<Vec<ActionUsageEntry>> {
+    let config = state.get().await;
+    let limit = limit.unwrap_or(5).max(1) as usize;
+    actions::most_used_actions(&app, &config, limit)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn trigger_action(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+) -> CommandResult<()> {
+    let config = state.get().await;
+    let action = actions::find_action(&config, &id)
+        .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+    if !actions::is_action_enabled(action) {
+        return Err(CommandError::invalid_input(format!("Action {id} is disabled")));
+    }
+    // Окно микрофона запускает действие так же, как по глобальному хоткею
+    app.emit("hotkey:action-triggered", hotkeys::action_triggered_payload(&id, "command", None))
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn actions_reorder(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    ids: Vec<String>,
+) -> CommandResult<AppConfig> {
+    let updated = state
+        .update_with(|config| actions::reorder_actions(&mut config.actions, &ids)).await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn actions_set_enabled(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+    enabled: bool,
+) -> CommandResult<AppConfig> {
+    let updated = state
+        .update_with(|config| actions::set_action_enabled(&mut config.actions, &id, enabled))
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn actions_get_language(
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+) -> CommandResult<Option<String>> {
+    let config = state.get().await;
+    let action = actions::find_action(&config, &id)
+        .ok_or_else(|| CommandError::not_found(format!("Action {id} not found")))?;
+    Ok(actions::action_language(action))
+}
+
+#[tauri::command]
+async fn actions_set_language(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+    language: Option<String>,
+) -> CommandResult<AppConfig> {
+    let updated = state
+        .update_with(|config| actions::set_action_language(&mut config.actions, &id, language))
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+/// `sound: None` возвращает действию глобальный звук завершения
+#[tauri::command]
+async fn actions_set_completion_sound(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    id: String,
+    sound: Option<String>,
+) -> CommandResult<AppConfig> {
+    let sound = sound.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    if let Some(name) = &sound {
+        if !resources::list_bundled_sounds(&app).contains(name) {
+            return Err(CommandError::not_found(format!("Sound {name} not found")));
+        }
+    }
+    let updated = state
+        .update_with(|config| actions::set_action_completion_sound(&mut config.actions, &id, sound))
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn actions_export(state: State<'_, Arc<ConfigState>>) -> CommandResult<String> {
+    let config = state.get().await;
+    actions::export_actions(&config.actions).map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn actions_import(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    json: String,
+    merge: bool,
+) -> CommandResult<AppConfig> {
+    let imported = actions::parse_actions_pack(&json)?;
+    let updated = state
+        .update_with(|config| {
+            actions::import_actions(&mut config.actions, imported, merge);
+            Ok(())
+        }).await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn history_clear(app: tauri::AppHandle) -> CommandResult<()> {
+    clear_history(&app).await?;
+    app.emit("history:updated", json!({"type": "cleared"}))?;
     Ok(())
 }
 
+/// Распознаёт сохранённое аудио записи заново выбранной моделью и обновляет транскрипцию
+#[tauri::command]
+async fn retranscribe_history(
+    app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    id: String,
+    provider: String,
+    model: Option<String>,
+) -> CommandResult<ActionHistoryEntry> {
+    let provider = SpeechProvider::parse(&provider)?;
+    let entry = read_history(&app)
+        .await?
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| CommandError::not_found(format!("History entry {id} not found")))?;
+    let audio_path = entry
+        .audio_path
+        .filter(|path| !path.trim().is_empty())
+        .ok_or_else(|| CommandError::not_found("History entry has no recorded audio"))?;
+    let audio = read_history_audio(&app, audio_path).await?;
+
+    let config = config_state.get().await;
+    let model = model
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| config.speech.model.clone());
+    let audio = if provider == SpeechProvider::Local {
+        manager.wake(&app).await?;
+        if config.transcode_local_audio {
+            transcription::transcode_to_wav(&audio).await?.unwrap_or(audio)
+        } else {
+            audio
+        }
+    } else {
+        audio
+    };
+    let mime_type = transcription::detect_mime_type(&audio);
+    let text = transcription::transcribe(
+        provider,
+        &manager.transcribe_endpoint(),
+        &config.api_keys.openai,
+        &model,
+        audio,
+        mime_type,
+    )
+    .await?;
+
+    let updated = update_history(
+        &app,
+        ActionHistoryUpdateInput {
+            id,
+            transcription: Some(text),
+            ..Default::default()
+        },
+    )
+    .await?;
+    app.emit("history:updated", json!({"type": "updated", "entry": &updated}))?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn history_prune_audio(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+) -> CommandResult<Vec<String>> {
+    let retention_days = state.get().await.history_audio_retention_days;
+    let pruned = history::prune_history_audio(&app, retention_days).await?;
+    if !pruned.is_empty() {
+        app.emit("history:updated", json!({"type": "audio-pruned", "ids": &pruned}))?;
+    }
+    Ok(pruned)
+}
+
 #[tauri::command]
 async fn history_save_audio(
     app: tauri::AppHandle,
     payload: HistoryAudioInput,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     save_history_audio(&app, payload.audio, payload.mime_type)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_export_audio(
+    app: tauri::AppHandle,
+    dir: String,
+    ids: Option<Vec<String>>,
+) -> CommandResult<Vec<String>> {
+    let target = dir.trim();
+    if target.is_empty() {
+        return Err(CommandError::invalid_input("Export directory cannot be empty"));
+    }
+    export_history_audio(&app, std::path::Path::new(target), ids)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_export_markdown(
+    app: tauri::AppHandle,
+    action_id: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+) -> CommandResult<String> {
+    let bound = |value: Option<String>, end_of_day: bool| -> CommandResult<_> {
+        value
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| history::parse_export_bound(&value, end_of_day))
+            .transpose()
+            .map_err(|error| CommandError::invalid_input(error.to_string()))
+    };
+    let since = bound(since, false)?;
+    let until = bound(until, true)?;
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            return Err(CommandError::invalid_input("`since` must not be later than `until`"));
+        }
+    }
+    let action_id = action_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    history::export_history_markdown(&app, action_id.as_deref(), since, until)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn history_read_audio(
     app: tauri::AppHandle,
     payload: HistoryReadAudioInput,
-) -> Result<Vec<u8>, String> {
+) -> CommandResult<Vec<u8>> {
     read_history_audio(&app, payload.audio_path)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn notes_get(app: tauri::AppHandle, args: NotesListArgs) -> Result<NoteListResponse, String> {
+async fn notes_get(app: tauri::AppHandle, args: NotesListArgs) -> CommandResult<NoteListResponse> {
     let page = args.page.unwrap_or(1).max(1);
     let page_size = args.page_size.unwrap_or(20).max(1);
-    list_notes(&app, page, page_size)
+    let sort = NoteSort::parse(args.sort_by.as_deref(), args.direction.as_deref())
+        .map_err(|error| CommandError::invalid_input(error.to_string()))?;
+    let created = NoteDateRange::parse(args.created_from.as_deref(), args.created_to.as_deref())
+        .map_err(|error| CommandError::invalid_input(error.to_string()))?;
+    list_notes(&app, page, page_size, sort, created, args.include_archived)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn notes_create(app: tauri::AppHandle, payload: NoteCreateInput) -> Result<NoteEntry, String> {
-    let entry = create_note(&app, payload)
+async fn notes_get_by_username(
+    app: tauri::AppHandle,
+    username: String,
+    args: NotesListArgs,
+) -> CommandResult<NoteListResponse> {
+    if notes::normalize_username(&username).is_empty() {
+        return Err(CommandError::invalid_input("Username cannot be empty"));
+    }
+    let page = args.page.unwrap_or(1).max(1);
+    let page_size = args.page_size.unwrap_or(20).max(1);
+    list_notes_by_username(&app, &username, page, page_size)
         .await
-        .map_err(|error| error.to_string())?;
-    app.emit("notes:updated", json!({"type": "added", "mode": "local", "entry": &entry}))
-        .map_err(|error| error.to_string())?;
-    Ok(entry)
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn notes_update(app: tauri::AppHandle, payload: NoteUpdateInput) -> Result<NoteEntry, String> {
-    let entry = update_note(&app, payload)
+async fn notes_search(
+    app: tauri::AppHandle,
+    query: String,
+    args: NotesListArgs,
+) -> CommandResult<NoteListResponse> {
+    let page = args.page.unwrap_or(1).max(1);
+    let page_size = args.page_size.unwrap_or(20).max(1);
+    notes::search_notes(&app, &query, page, page_size)
         .await
-        .map_err(|error| error.to_string())?;
-    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))
-        .map_err(|error| error.to_string())?;
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn notes_list_tags(
+    app: tauri::AppHandle,
+    include_archived: Option<bool>,
+) -> CommandResult<Vec<NoteTagCount>> {
+    notes::list_tags(&app, include_archived.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn notes_export_markdown(app: tauri::AppHandle, dest_path: String) -> CommandResult<usize> {
+    let target = dest_path.trim();
+    if target.is_empty() {
+        return Err(CommandError::invalid_input("Destination path cannot be empty"));
+    }
+    notes::export_notes_markdown(&app, std::path::Path::new(target))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn notes_count(app: tauri::AppHandle) -> CommandResult<usize> {
+    notes::count_notes(&app).await.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn notes_create(app: tauri::AppHandle, payload: NoteCreateInput) -> CommandResult<NoteEntry> {
+    let entry = create_note(&app, payload).await?;
+    app.emit("notes:updated", json!({"type": "added", "mode": "local", "entry": &entry}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_update(app: tauri::AppHandle, payload: NoteUpdateInput) -> CommandResult<NoteEntry> {
+    let entry = update_note(&app, payload).await?;
+    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_duplicate(app: tauri::AppHandle, id: String) -> CommandResult<NoteEntry> {
+    let entry = duplicate_note(&app, &id).await?;
+    app.emit("notes:updated", json!({"type": "added", "mode": "local", "entry": &entry}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_append(app: tauri::AppHandle, id: String, text: String) -> CommandResult<NoteEntry> {
+    let entry = append_to_note(&app, &id, &text).await?;
+    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))?;
     Ok(entry)
 }
 
 #[tauri::command]
-async fn notes_delete(app: tauri::AppHandle, payload: NoteDeleteInput) -> Result<(), String> {
+async fn notes_delete(app: tauri::AppHandle, payload: NoteDeleteInput) -> CommandResult<()> {
     let deleted_id = payload.id.clone();
-    delete_note(&app, payload)
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("notes:updated", json!({"type": "deleted", "mode": "local", "id": deleted_id}))
-        .map_err(|error| error.to_string())?;
+    delete_note(&app, payload).await?;
+    app.emit("notes:updated", json!({"type": "deleted", "mode": "local", "id": deleted_id}))?;
     Ok(())
 }
 
@@ -286,49 +808,248 @@ async fn notes_delete(app: tauri::AppHandle, payload: NoteDeleteInput) -> Result
 async fn notes_bulk_delete(
     app: tauri::AppHandle,
     payload: NoteBulkDeleteInput,
-) -> Result<NoteBulkDeleteResponse, String> {
+) -> CommandResult<NoteBulkDeleteResponse> {
     let ids = payload.ids.clone();
-    let response = bulk_delete_notes(&app, payload)
-        .await
-        .map_err(|error| error.to_string())?;
-    app.emit("notes:updated", json!({"type": "bulk-deleted", "mode": "local", "ids": ids}))
-        .map_err(|error| error.to_string())?;
+    let response = bulk_delete_notes(&app, payload).await?;
+    app.emit("notes:updated", json!({"type": "bulk-deleted", "mode": "local", "ids": ids}))?;
     Ok(response)
 }
 
+#[tauri::command]
+async fn notes_set_pinned(app: tauri::AppHandle, id: String, pinned: bool) -> CommandResult<NoteEntry> {
+    let entry = notes::set_note_pinned(&app, &id, pinned).await?;
+    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_merge(
+    app: tauri::AppHandle,
+    primary_id: String,
+    secondary_id: String,
+) -> CommandResult<NoteEntry> {
+    let entry = notes::merge_notes(&app, primary_id.trim(), secondary_id.trim()).await?;
+    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))?;
+    app.emit("notes:updated", json!({"type": "deleted", "mode": "local", "id": secondary_id.trim()}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_set_archived(app: tauri::AppHandle, id: String, archived: bool) -> CommandResult<NoteEntry> {
+    let entry = notes::set_note_archived(&app, &id, archived).await?;
+    app.emit("notes:updated", json!({"type": "updated", "mode": "local", "entry": &entry}))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn notes_list_trash(app: tauri::AppHandle, args: NotesListArgs) -> CommandResult<NoteListResponse> {
+    let page = args.page.unwrap_or(1).max(1);
+    let page_size = args.page_size.unwrap_or(20).max(1);
+    notes::list_trash(&app, page, page_size)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn notes_restore(app: tauri::AppHandle, id: String) -> CommandResult<NoteEntry> {
+    let restored = notes::restore_note(&app, &id).await?;
+    app.emit("notes:updated", json!({"type": "added", "mode": "local", "entry": &restored}))?;
+    Ok(restored)
+}
+
+#[tauri::command]
+async fn notes_empty_trash(app: tauri::AppHandle) -> CommandResult<usize> {
+    let removed = notes::empty_trash(&app).await?;
+    app.emit("notes:updated", json!({"type": "trash-emptied", "mode": "local", "count": removed}))?;
+    Ok(removed)
+}
+
+#[tauri::command]
+async fn notes_backup(app: tauri::AppHandle) -> CommandResult<String> {
+    let path = notes::backup_notes(&app).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn notes_repair(app: tauri::AppHandle) -> CommandResult<RepairReport> {
+    notes::repair_notes(&app)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn history_repair(app: tauri::AppHandle) -> CommandResult<RepairReport> {
+    let report = history::repair_history(&app).await?;
+    if report.was_corrupt {
+        app.emit("history:updated", json!({"type": "repaired", "recovered": report.recovered}))?;
+    }
+    Ok(report)
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NotesStorageModeResult {
+    mode: String,
+    migrated: usize,
+}
+
+#[tauri::command]
+async fn notes_get_storage_mode(state: State<'_, Arc<ConfigState>>) -> CommandResult<String> {
+    Ok(state.get().await.notes_storage_mode)
+}
+
+#[tauri::command]
+async fn notes_set_storage_mode(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    mode: String,
+    migrate: bool,
+) -> CommandResult<NotesStorageModeResult> {
+    let mode = mode.trim().to_ascii_lowercase();
+    if !notes::NOTES_STORAGE_MODES.contains(&mode.as_str()) {
+        return Err(CommandError::invalid_input(format!(
+            "Unknown notes storage mode \"{mode}\"; expected one of: {}",
+            notes::NOTES_STORAGE_MODES.join(", ")
+        )));
+    }
+    let config = state.get().await;
+    let previous = config.notes_storage_mode.clone();
+    // Режим переключаем только после успешной миграции, чтобы заметки не «пропали»
+    let migrated = if migrate && previous != mode {
+        notes::migrate_notes(&app, &config, &previous, &mode).await?
+    } else {
+        0
+    };
+    let updated = state
+        .update_with(|config| {
+            config.notes_storage_mode = mode.clone();
+            Ok(())
+        }).await?;
+    app.emit("config:updated", &updated)?;
+    Ok(NotesStorageModeResult { mode, migrated })
+}
+
 #[tauri::command]
 async fn resources_sound_path(
     app: tauri::AppHandle,
     sound_name: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     resources::resolve_sound_path(&app, &sound_name)
-        .ok_or_else(|| format!("Sound {sound_name} not found"))
+        .ok_or_else(|| CommandError::not_found(format!("Sound {sound_name} not found")))
+}
+
+#[tauri::command]
+async fn audio_duck_others(
+    config_state: State<'_, Arc<ConfigState>>,
+    enable: bool,
+) -> CommandResult<()> {
+    // Восстановление громкости разрешено всегда, даже если настройку успели выключить
+    if enable && !config_state.get().await.duck_audio_while_recording {
+        return Err(CommandError::invalid_input("Audio ducking is disabled in settings"));
+    }
+    tokio::task::spawn_blocking(move || audio::duck_other_sessions(enable))
+        .await
+        .map_err(|error| CommandError::from(error.to_string()))?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn sound_resolve(app: tauri::AppHandle, name: String) -> CommandResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(CommandError::invalid_input("Sound name cannot be empty"));
+    }
+    resources::resolve_sound_path(&app, name).ok_or_else(|| {
+        CommandError::not_found(format!(
+            "Sound {name} not found, searched: [{}]",
+            resources::sound_search_paths(&app, name).join(", ")
+        ))
+    })
+}
+
+#[tauri::command]
+async fn sounds_bundled(app: tauri::AppHandle) -> CommandResult<Vec<String>> {
+    Ok(resources::list_bundled_sounds(&app))
 }
 
 #[tauri::command]
 async fn resources_sound_data(
     app: tauri::AppHandle,
     sound_name: String,
-) -> Result<Vec<u8>, String> {
+) -> CommandResult<Vec<u8>> {
     resources::read_sound_file(&app, &sound_name)
-        .ok_or_else(|| format!("Sound {sound_name} not found or could not be read"))
+        .ok_or_else(|| CommandError::not_found(format!("Sound {sound_name} not found or could not be read")))
 }
 
 #[tauri::command]
 async fn resources_play_sound(
     app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
     sound_name: String,
-) -> Result<(), String> {
-    audio::play_sound_sync(&app, &sound_name)
+    volume: Option<f32>,
+) -> CommandResult<()> {
+    let device = state.get().await.sound_output_device;
+    audio::play_sound_sync(&app, &sound_name, volume, device.as_deref()).map_err(CommandError::from)
+}
+
+/// Preview from settings: plays even when `completion_sound_enabled` is off.
+#[tauri::command]
+async fn play_test_sound(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    name: Option<String>,
+    volume: Option<f32>,
+) -> CommandResult<()> {
+    let config = state.get().await;
+    let name = name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| audio::DEFAULT_COMPLETION_SOUND.to_string());
+    let volume = volume.unwrap_or(config.completion_sound_volume);
+    if !volume.is_finite() {
+        return Err(CommandError::invalid_input("Volume must be a number between 0 and 1"));
+    }
+    audio::play_sound_sync(&app, &name, Some(volume), config.sound_output_device.as_deref())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn diagnostics_bundle(app: tauri::AppHandle, path: String) -> CommandResult<String> {
+    let target = path.trim();
+    if target.is_empty() {
+        return Err(CommandError::invalid_input("Target directory cannot be empty"));
+    }
+    let archive = diagnostics::create_bundle(&app, std::path::Path::new(target)).await?;
+    Ok(archive.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn system_dependencies() -> CommandResult<Vec<system_dependencies::SystemDependency>> {
+    Ok(system_dependencies::check_all().await)
+}
+
+#[tauri::command]
+async fn audio_output_devices() -> CommandResult<Vec<audio::AudioOutputDevice>> {
+    tauri::async_runtime::spawn_blocking(audio::output_devices)
+        .await
+        .map_err(|error| CommandError::from(error.to_string()))?
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn auth_consume_pending(
     queue: State<'_, Arc<AuthQueue>>,
-) -> Result<Vec<AuthDeepLinkPayload>, String> {
+) -> CommandResult<Vec<AuthDeepLinkPayload>> {
     Ok(queue.drain().await)
 }
 
+#[tauri::command]
+async fn auth_peek_pending(
+    queue: State<'_, Arc<AuthQueue>>,
+) -> CommandResult<Vec<AuthDeepLinkPayload>> {
+    Ok(queue.peek_redacted().await)
+}
+
 #[tauri::command]
 async fn auth_start_oauth(
     app: tauri::AppHandle,
@@ -336,12 +1057,14 @@ async fn auth_start_oauth(
     queue: State<'_, Arc<AuthQueue>>,
     oauth_state: State<'_, Arc<OAuthServerState>>,
     provider: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use tauri_plugin_opener::OpenerExt;
     
-    // Если работаем от админа и OAuth сервер еще не запущен - запускаем
-    if oauth::is_running_as_admin() {
-        logging::log_message("[auth_start_oauth] Running as admin, starting OAuth server...");
+    let config = config_state.get().await;
+    let use_http_callback = oauth::should_use_http_callback(&config);
+    // Если нужен HTTP callback и OAuth сервер еще не запущен - запускаем
+    if use_http_callback {
+        logging::log_message("[auth_start_oauth] Using HTTP callback, starting OAuth server...");
         let app_clone = app.clone();
         let queue_clone = queue.inner().clone();
         let state_clone = oauth_state.inner().clone();
@@ -366,36 +1089,157 @@ async fn auth_start_oauth(
             }
             Err(e) => {
                 logging::log_message(&format!("[auth_start_oauth] Failed to start OAuth server: {}", e));
-                return Err(format!("Failed to start OAuth server: {}", e));
+                return Err(CommandError::from(format!("Failed to start OAuth server: {}", e)));
             }
         }
     } else {
         logging::log_message("[auth_start_oauth] Not running as admin, using deep link");
     }
     
-    let config = config_state.get().await;
-    let url = oauth::build_oauth_start_url(&provider, Some(config.backend_domain.as_str()))
-        .map_err(|error| error.to_string())?;
+    let url = oauth::build_oauth_start_url(
+        &provider,
+        Some(config.backend_domain.as_str()),
+        use_http_callback,
+    )?;
     logging::log_message(&format!("[auth_start_oauth] Opening OAuth URL: {}", url));
     app.opener()
         .open_url(url, None::<String>)
-        .map_err(|error| error.to_string())
+        .map_err(|error| CommandError::from(error.to_string()))
+}
+
+/// URL, который открыл бы `auth_start_oauth`, без запуска браузера и с замаскированными секретами
+#[tauri::command]
+async fn oauth_start_url(
+    config_state: State<'_, Arc<ConfigState>>,
+    provider: String,
+) -> CommandResult<String> {
+    let config = config_state.get().await;
+    let url = oauth::build_oauth_start_url(
+        &provider,
+        Some(config.backend_domain.as_str()),
+        oauth::should_use_http_callback(&config),
+    )?;
+    Ok(oauth::redact_oauth_url(&url))
+}
+
+/// Открывает страницу загрузки релиза; `url` из проверки обновлений, иначе последний релиз на GitHub
+#[tauri::command]
+fn open_download_page(app: tauri::AppHandle, url: Option<String>) -> CommandResult<String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let target = match url.map(|value| value.trim().to_string()).filter(|value| !value.is_empty()) {
+        Some(raw) => {
+            let parsed = url::Url::parse(&raw)
+                .map_err(|error| CommandError::invalid_input(format!("Invalid download URL: {error}")))?;
+            if parsed.scheme() != "https" {
+                return Err(CommandError::invalid_input("Download URL must use https"));
+            }
+            parsed.to_string()
+        }
+        None => constants::RELEASES_DOWNLOAD_URL.to_string(),
+    };
+    logging::log_message(&format!("[open_download_page] Opening {target}"));
+    app.opener()
+        .open_url(target.clone(), None::<String>)
+        .map_err(|error| CommandError::from(error.to_string()))?;
+    Ok(target)
+}
+
+#[tauri::command]
+async fn mic_permission_status() -> CommandResult<String> {
+    tauri::async_runtime::spawn_blocking(mic_permission::status)
+        .await
+        .map_err(|error| CommandError::from(format!("Permission check task failed: {error}")))?
+        .map(str::to_string)
+        .map_err(CommandError::from)
+}
+
+/// Вызывает системный запрос доступа; если доступ уже запрещён — открывает настройки приватности
+#[tauri::command]
+async fn mic_request_permission(app: tauri::AppHandle) -> CommandResult<String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let status = tauri::async_runtime::spawn_blocking(mic_permission::request)
+        .await
+        .map_err(|error| CommandError::from(format!("Permission request task failed: {error}")))??;
+    if status == mic_permission::DENIED {
+        if let Some(url) = mic_permission::SETTINGS_URL {
+            logging::log_message("[mic_permission] Access denied, opening privacy settings");
+            if let Err(error) = app.opener().open_url(url, None::<String>) {
+                logging::log_message(&format!("[mic_permission] Failed to open settings: {error}"));
+            }
+        }
+    }
+    Ok(status.to_string())
+}
+
+/// `device` — имя устройства записи в ОС (не id из браузера); `None` — устройство по умолчанию
+#[tauri::command]
+async fn audio_self_test(device: Option<String>, duration_ms: u32) -> CommandResult<AudioSelfTestResult> {
+    if !(audio_self_test::MIN_DURATION_MS..=audio_self_test::MAX_DURATION_MS).contains(&duration_ms) {
+        return Err(CommandError::invalid_input(format!(
+            "Duration must be between {} and {} ms",
+            audio_self_test::MIN_DURATION_MS,
+            audio_self_test::MAX_DURATION_MS
+        )));
+    }
+    let result = audio_self_test::run(device.as_deref(), duration_ms).await?;
+    logging::log_message(&format!(
+        "[audio_self_test] peak={:.4} rms={:.4} signal={}",
+        result.peak, result.rms, result.signal_detected
+    ));
+    Ok(result)
 }
 
 #[tauri::command]
-async fn auth_is_admin() -> Result<bool, String> {
+async fn auth_is_admin() -> CommandResult<bool> {
     Ok(oauth::is_running_as_admin())
 }
 
+/// Включает подробные логи OAuth сервера до перезапуска приложения
 #[tauri::command]
-async fn get_log_file_path(app: tauri::AppHandle) -> Result<String, String> {
+fn oauth_debug(enable: bool) -> CommandResult<()> {
+    oauth_server::set_debug_logging(enable);
+    Ok(())
+}
+
+#[tauri::command]
+async fn auth_set_prefer_http_oauth(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    enabled: bool,
+) -> CommandResult<AppConfig> {
+    let updated = state
+        .update_with(|config| {
+            config.prefer_http_oauth = enabled;
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn get_log_file_path(app: tauri::AppHandle) -> CommandResult<String> {
     Ok(logging::get_log_file_path(&app)
         .and_then(|p| p.to_str().map(|s| s.to_string()))
         .unwrap_or_else(|| "Log file path not available".to_string()))
 }
 
 #[tauri::command]
-async fn open_file_path(_app: tauri::AppHandle, file_path: String) -> Result<(), String> {
+async fn logs_clear() -> CommandResult<()> {
+    logging::clear_log_file()?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn logs_rotate() -> CommandResult<()> {
+    logging::rotate_log_file()?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_file_path(_app: tauri::AppHandle, file_path: String) -> CommandResult<()> {
     use std::path::Path;
     println!("[open_file_path] Received request to open file: {}", file_path);
     
@@ -403,13 +1247,13 @@ async fn open_file_path(_app: tauri::AppHandle, file_path: String) -> Result<(),
     if !path.exists() {
         let msg = format!("File does not exist: {}", file_path);
         eprintln!("[open_file_path] Error: {}", msg);
-        return Err(msg);
+        return Err(CommandError::not_found(msg));
     }
     
     if !path.is_file() {
         let msg = format!("Path is not a file: {}", file_path);
         eprintln!("[open_file_path] Error: {}", msg);
-        return Err(msg);
+        return Err(CommandError::invalid_input(msg));
     }
     
     println!("[open_file_path] File exists, attempting to open: {}", file_path);
@@ -501,13 +1345,64 @@ async fn open_file_path(_app: tauri::AppHandle, file_path: String) -> Result<(),
             }
         }
     };
-    result
+    result.map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn storage_usage(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Vec<StorageCategoryUsage>> {
+    storage::usage(&app, manager.inner())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn storage_clear(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    category: String,
+) -> CommandResult<()> {
+    storage::clear(&app, manager.inner(), category.trim())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn insert_text(app: tauri::AppHandle, text: String) -> CommandResult<()> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    if text.trim().is_empty() {
+        return Err(CommandError::invalid_input("Nothing to insert"));
+    }
+    app.clipboard()
+        .write_text(text)
+        .map_err(|error| CommandError::from(error.to_string()))?;
+    // Даём буферу обмена обновиться до нажатия Ctrl+V
+    tokio::time::sleep(tokio::time::Duration::from_millis(80)).await;
+    tokio::task::spawn_blocking(text_input::send_paste_shortcut)
+        .await
+        .map_err(|error| CommandError::from(error.to_string()))?
+        .map_err(CommandError::from)
+}
+
+/// Печатает дельту стрима нажатиями клавиш в активное окно (режим paste_stream)
+#[tauri::command]
+async fn type_text(text: String) -> CommandResult<()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    tokio::task::spawn_blocking(move || text_input::type_text(&text))
+        .await
+        .map_err(|error| CommandError::from(error.to_string()))?
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn local_speech_get_status(
     manager: State<'_, Arc<FastWhisperManager>>,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     Ok(manager.get_status().await)
 }
 
@@ -515,7 +1410,7 @@ async fn local_speech_get_status(
 async fn local_speech_check_health(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     Ok(manager.check_health(&app).await)
 }
 
@@ -524,49 +1419,245 @@ async fn local_speech_install(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
     args: InstallArgs,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     let resolved_target = args.target_dir.clone();
     if resolved_target.is_none() {
-        return Err("Путь установки не выбран".into());
+        return Err(CommandError::invalid_input("Путь установки не выбран"));
     }
 
-    let selected = persist_install_dir_choice(&app, resolved_target.clone())
-        .await
-        .map_err(|error| error.to_string())?;
+    let selected = persist_install_dir_choice(&app, resolved_target.clone()).await?;
     if let Some(path) = selected {
         manager.set_install_override(Some(path)).await;
     } else {
-        return Err(format!(
+        return Err(CommandError::invalid_input(format!(
             "Путь установки не выбран (target_dir from UI: {:?})",
             resolved_target
-        ));
+        )));
     }
     manager
         .install_and_start(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn local_speech_set_install_dir(
+    app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    dir: Option<String>,
+    migrate: bool,
+) -> CommandResult<FastWhisperStatus> {
+    let dir = dir
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let status = match dir.as_deref() {
+        Some(value) => manager
+            .relocate(&app, std::path::PathBuf::from(value), migrate).await?,
+        None => {
+            // Без явной папки возвращаемся к общему расположению (env / hint / app data)
+            manager.set_install_override(None).await;
+            manager.check_health(&app).await
+        }
+    };
+    let updated = config_state
+        .update_with(|config| {
+            config.local_speech_install_dir = dir.clone();
+            Ok(())
+        }).await?;
+    app.emit("config:updated", &updated)?;
+    Ok(status)
+}
+
+#[tauri::command]
+async fn local_speech_resource_usage(
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Option<ResourceUsage>> {
+    let Some(pid) = process_info::find_pid_by_port(manager.port()).await else {
+        return Ok(None);
+    };
+    Ok(process_info::resource_usage(pid).await)
+}
+
+#[tauri::command]
+async fn local_speech_list_processes(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Vec<ProcessEntry>> {
+    let repo_path = manager.repo_path(&app);
+    Ok(process_info::list_processes_matching(&repo_path.to_string_lossy()).await)
+}
+
+/// Завершает все процессы сервера, кроме того, что сейчас слушает порт.
+#[tauri::command]
+async fn local_speech_kill_strays(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Vec<u32>> {
+    let repo_path = manager.repo_path(&app);
+    let tracked = process_info::find_pid_by_port(manager.port()).await;
+    let mut killed = Vec::new();
+    let mut errors = Vec::new();
+    for process in process_info::list_processes_matching(&repo_path.to_string_lossy()).await {
+        if Some(process.pid) == tracked {
+            continue;
+        }
+        match process_info::kill_process(process.pid).await {
+            Ok(()) => killed.push(process.pid),
+            Err(error) => errors.push(error.to_string()),
+        }
+    }
+    logging::log_message(&format!(
+        "[local_speech_kill_strays] killed {:?}, kept {:?}, errors: {:?}",
+        killed, tracked, errors
+    ));
+    if killed.is_empty() && !errors.is_empty() {
+        return Err(CommandError::from(errors.join("; ")).with_details(json!({ "errors": errors })));
+    }
+    Ok(killed)
 }
 
 #[tauri::command]
 async fn local_speech_start(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     manager
         .start_existing(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Меняет модель LLM по умолчанию (быстрая диктовка) без общего config_update.
+/// Для "local" кроме известных моделей принимаются уже скачанные в Ollama.
+#[tauri::command]
+async fn llm_set_model(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    mode: String,
+    model: String,
+) -> CommandResult<AppConfig> {
+    let mode = mode.trim().to_ascii_lowercase();
+    let model = model.trim().to_string();
+    let known = match mode.as_str() {
+        "api" => constants::LLM_API_MODELS.contains(&model.as_str()),
+        "local" => {
+            constants::LLM_LOCAL_MODELS.contains(&model.as_str())
+                || ollama::list_models().await.unwrap_or_default().contains(&model)
+        }
+        _ => {
+            return Err(CommandError::invalid_input(format!(
+                "Unknown LLM mode \"{mode}\"; expected one of: {}",
+                constants::LLM_MODES.join(", ")
+            )))
+        }
+    };
+    if !known {
+        return Err(CommandError::invalid_input(format!(
+            "Model \"{model}\" is not available for LLM mode \"{mode}\""
+        )));
+    }
+    let updated = state
+        .update_with(|config| {
+            config.llm.mode = mode.clone();
+            config.llm.model = model.clone();
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+/// В локальном режиме берём языки из `/info` сервера, если он их сообщает
+#[tauri::command]
+async fn speech_supported_languages(
+    state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Vec<SpeechLanguage>> {
+    let config = state.get().await;
+    if config.speech.mode == "local" {
+        let reported = manager
+            .info()
+            .await
+            .map(|info| info.languages)
+            .unwrap_or_default();
+        return Ok(speech_languages::local(&reported));
+    }
+    Ok(speech_languages::api())
+}
+
+/// Переключает speech.mode и сразу согласует локальный сервер:
+/// "local" поднимает его, уход с "local" останавливает сервер, если его запускало приложение
+#[tauri::command]
+async fn speech_set_mode(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    mode: String,
+) -> CommandResult<AppConfig> {
+    let mode = mode.trim().to_ascii_lowercase();
+    if !constants::SPEECH_MODES.contains(&mode.as_str()) {
+        return Err(CommandError::invalid_input(format!(
+            "Unknown speech mode \"{mode}\"; expected one of: {}",
+            constants::SPEECH_MODES.join(", ")
+        )));
+    }
+    let updated = state
+        .update_with(|config| {
+            config.speech.mode = mode.clone();
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+
+    let manager = manager.inner().clone();
+    if mode == "local" {
+        // Установка/запуск может занять минуты — прогресс уходит через события статуса
+        tauri::async_runtime::spawn(async move {
+            if let Err(error) = manager.start_if_auto_enabled(&app).await {
+                logging::log_message(&format!("[speech_set_mode] Failed to start local server: {error}"));
+            }
+        });
+    } else if manager.take_auto_started() {
+        tauri::async_runtime::spawn(async move {
+            if let Err(error) = manager.stop(&app).await {
+                logging::log_message(&format!("[speech_set_mode] Failed to stop local server: {error}"));
+            }
+        });
+    }
+    Ok(updated)
 }
 
 #[tauri::command]
 async fn local_speech_restart(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     manager
         .restart(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn local_speech_set_runtime(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    host: String,
+    port: u32,
+) -> CommandResult<FastWhisperStatus> {
+    let host = host.trim().to_string();
+    if host.is_empty() {
+        return Err(CommandError::invalid_input("Host cannot be empty"));
+    }
+    let port = u16::try_from(port)
+        .ok()
+        .filter(|value| *value >= 1024)
+        .ok_or_else(|| CommandError::invalid_input(format!("Port must be between 1024 and 65535, got {port}")))?;
+    manager
+        .set_runtime(&app, host, port)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -574,38 +1665,55 @@ async fn local_speech_reinstall(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
     args: InstallArgs,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     let resolved_target = args.target_dir.clone();
     if resolved_target.is_none() {
-        return Err("Путь установки не выбран".into());
+        return Err(CommandError::invalid_input("Путь установки не выбран"));
     }
 
-    let selected = persist_install_dir_choice(&app, resolved_target.clone())
-        .await
-        .map_err(|error| error.to_string())?;
+    let selected = persist_install_dir_choice(&app, resolved_target.clone()).await?;
     if let Some(path) = selected {
         manager.set_install_override(Some(path)).await;
     } else {
-        return Err(format!(
+        return Err(CommandError::invalid_input(format!(
             "Путь установки не выбран (target_dir from UI: {:?})",
             resolved_target
-        ));
+        )));
     }
     manager
         .reinstall(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Проверяет целостность скачанного репозитория до попытки запуска
+#[tauri::command]
+async fn local_speech_verify(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<RepositoryVerification> {
+    Ok(manager.verify_repository(&app).await)
+}
+
+#[tauri::command]
+async fn local_speech_info(
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<LocalSpeechInfo> {
+    manager
+        .info()
+        .await
+        .map_err(|error| CommandError::new(error::SERVER_DOWN, error.to_string()))
 }
 
 #[tauri::command]
 async fn local_speech_stop(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
-) -> Result<FastWhisperStatus, String> {
+) -> CommandResult<FastWhisperStatus> {
     manager
         .stop(&app)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -613,55 +1721,165 @@ async fn local_speech_check_model_downloaded(
     app: tauri::AppHandle,
     manager: State<'_, Arc<FastWhisperManager>>,
     model: String,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     let normalized = model.trim().to_string();
     manager
         .is_model_downloaded(&app, &normalized)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Сбрасывает таймер простоя и поднимает сервер, остановленный по простою или ещё не запущенный
+/// в ленивом режиме
+#[tauri::command]
+async fn local_speech_wake(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<FastWhisperStatus> {
+    manager.wake(&app).await.map_err(CommandError::from)
+}
+
+/// Прерывает идущую установку/запуск: скрипт убивается вместе с дочерними процессами
+#[tauri::command]
+async fn local_speech_abort_install(manager: State<'_, Arc<FastWhisperManager>>) -> CommandResult<bool> {
+    Ok(manager.abort_running_script())
+}
+
+#[tauri::command]
+async fn benchmark_transcription(
+    app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    provider: String,
+    model: Option<String>,
+    sample: Option<Vec<u8>>,
+) -> CommandResult<TranscriptionBenchmark> {
+    let provider = SpeechProvider::parse(&provider)?;
+    let config = config_state.get().await;
+    let model = model
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| config.speech.model.clone());
+    if provider == SpeechProvider::Local {
+        manager.wake(&app).await?;
+    }
+    let audio = match sample {
+        Some(bytes) if !bytes.is_empty() => bytes,
+        _ => resources::read_benchmark_clip(&app)
+            .ok_or_else(|| CommandError::not_found("Benchmark clip not found"))?,
+    };
+    transcription::benchmark(
+        provider,
+        &manager.transcribe_endpoint(),
+        &config.api_keys.openai,
+        &model,
+        audio,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+async fn transcribe_batch(
+    app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+    paths: Vec<String>,
+    provider: String,
+    model: Option<String>,
+) -> CommandResult<BTreeMap<String, BatchTranscriptionResult>> {
+    let provider = SpeechProvider::parse(&provider)?;
+    let mut unique = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.trim().to_string();
+        if !path.is_empty() && !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    if unique.is_empty() {
+        return Err(CommandError::invalid_input("No audio files to transcribe"));
+    }
+    if provider == SpeechProvider::Local {
+        manager.wake(&app).await?;
+    }
+    let config = config_state.get().await;
+    let model = model
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| config.speech.model.clone());
+    Ok(transcription::transcribe_batch(
+        provider,
+        &manager.transcribe_endpoint(),
+        &config.api_keys.openai,
+        &model,
+        unique,
+        config.transcode_local_audio,
+        |progress| {
+            let _ = app.emit("transcribe:batch-progress", &progress);
+        },
+    )
+    .await)
+}
+
+#[tauri::command]
+async fn speech_transcode_audio(audio: Vec<u8>) -> CommandResult<Option<Vec<u8>>> {
+    if audio.is_empty() {
+        return Err(CommandError::invalid_input("Audio cannot be empty"));
+    }
+    transcription::transcode_to_wav(&audio)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn ollama_check_installed() -> Result<bool, String> {
+async fn ollama_check_installed() -> CommandResult<bool> {
     ollama::check_installed()
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn ollama_is_server_running() -> Result<bool, String> {
+async fn ollama_is_server_running() -> CommandResult<bool> {
     Ok(ollama::is_server_running().await)
 }
 
 #[tauri::command]
-async fn ollama_list_models() -> Result<Vec<String>, String> {
+async fn ollama_list_models() -> CommandResult<Vec<String>> {
     ollama::list_models()
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn ollama_pull_model(model: String) -> Result<(), String> {
+async fn ollama_pull_model(model: String) -> CommandResult<()> {
     ollama::pull_model(&model)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-async fn ollama_warmup_model(model: String) -> Result<(), String> {
+async fn warmup_all(
+    app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
+    manager: State<'_, Arc<FastWhisperManager>>,
+) -> CommandResult<Vec<warmup::BackendReadiness>> {
+    let config = config_state.get().await;
+    Ok(warmup::warmup_all(&app, manager.inner().clone(), &config).await)
+}
+
+#[tauri::command]
+async fn ollama_warmup_model(model: String) -> CommandResult<()> {
     ollama::warmup_model(&model)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 async fn ollama_chat_completions(
     model: String,
     messages: Vec<ollama::ChatMessage>,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     ollama::chat_completions(&model, messages)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -670,20 +1888,47 @@ async fn ollama_chat_completions_stream(
     model: String,
     messages: Vec<ollama::ChatMessage>,
     stream_id: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     ollama::chat_completions_stream(app, &model, messages, &stream_id)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Для запросов, которые фронтенд шлёт напрямую через fetch: общий лимитер на провайдера
+#[tauri::command]
+async fn rate_limit_acquire(provider: String) -> CommandResult<()> {
+    let provider = provider.trim().to_ascii_lowercase();
+    if !rate_limit::PROVIDERS.contains(&provider.as_str()) {
+        return Err(CommandError::invalid_input(format!(
+            "Unknown provider \"{provider}\"; expected one of: {}",
+            rate_limit::PROVIDERS.join(", ")
+        )));
+    }
+    rate_limit::acquire(&provider)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Прерывает один активный LLM-стрим; `false`, если он уже завершён
+#[tauri::command]
+fn llm_cancel(app: tauri::AppHandle, stream_id: String) -> bool {
+    llm_streams::cancel(&app, &stream_id)
+}
+
+/// Прерывает все активные LLM-стримы и возвращает их stream_id
+#[tauri::command]
+fn llm_cancel_all(app: tauri::AppHandle) -> Vec<String> {
+    llm_streams::cancel_all(&app)
 }
 
 #[tauri::command]
 async fn openai_chat_completions(
     api_key: String,
     body: serde_json::Value,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     openai::chat_completions(&api_key, body)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -692,10 +1937,10 @@ async fn openai_chat_completions_stream(
     api_key: String,
     body: serde_json::Value,
     stream_id: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     openai::chat_completions_stream(app, &api_key, body, &stream_id)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -705,18 +1950,29 @@ async fn gemini_generate_content_stream(
     model: String,
     body: serde_json::Value,
     stream_id: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     gemini::stream_generate_content(app, &api_key, &model, body, &stream_id)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-fn action_hotkeys_register(
+async fn action_hotkeys_register(
     app: tauri::AppHandle,
+    config_state: State<'_, Arc<ConfigState>>,
     hotkeys_state: State<'_, Arc<HotkeyState>>,
     hotkeys: Vec<ActionHotkeyInput>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
+    let config = config_state.get().await;
+    // Отключённые действия не получают глобальный хоткей
+    let hotkeys = hotkeys
+        .into_iter()
+        .filter(|entry| {
+            actions::find_action(&config, &entry.id)
+                .map(actions::is_action_enabled)
+                .unwrap_or(true)
+        })
+        .collect();
     hotkeys_state.register_action_hotkeys(&app, hotkeys);
     Ok(())
 }
@@ -725,34 +1981,66 @@ fn action_hotkeys_register(
 fn action_hotkeys_clear(
     app: tauri::AppHandle,
     hotkeys_state: State<'_, Arc<HotkeyState>>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     hotkeys_state.clear_action_hotkeys(&app);
     Ok(())
 }
 
+/// Сбрасывает только хоткеи: микрофон получает значение по умолчанию, у действий они снимаются
+#[tauri::command]
+async fn hotkeys_reset(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    hotkeys_state: State<'_, Arc<HotkeyState>>,
+) -> CommandResult<AppConfig> {
+    let mut cleared = 0;
+    let updated = state
+        .update_with(|config| {
+            config.mic_hotkey = AppConfig::default().mic_hotkey;
+            cleared = actions::clear_action_hotkeys(&mut config.actions, &mut config.groups);
+            Ok(())
+        })
+        .await?;
+    hotkeys_state.clear_action_hotkeys(&app);
+    let mic_hotkey = updated.mic_hotkey.trim();
+    hotkeys_state.register_mic(&app, (!mic_hotkey.is_empty()).then(|| mic_hotkey.to_string()));
+    logging::log_message(&format!("[hotkeys] Reset: cleared {cleared} action hotkey(s)"));
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+fn hotkeys_reserved() -> CommandResult<Vec<ReservedHotkey>> {
+    Ok(hotkeys::reserved_hotkeys())
+}
+
 #[tauri::command]
 fn hotkeys_set_recording_active(
+    app: tauri::AppHandle,
     hotkeys_state: State<'_, Arc<HotkeyState>>,
     active: bool,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     hotkeys_state.set_recording_active(active);
+    if !active {
+        hotkeys_state.schedule_auto_hide(&app);
+    }
     Ok(())
 }
 
 #[tauri::command]
-async fn window_open_devtools(_app: tauri::AppHandle) -> Result<(), String> {
+async fn window_open_devtools(_app: tauri::AppHandle) -> CommandResult<()> {
     #[cfg(feature = "devtools")]
     {
         if let Some(window) = _app.get_webview_window("main") {
             window.open_devtools();
             Ok(())
         } else {
-            Err("Main window not found".to_string())
+            Err(CommandError::not_found("Main window not found"))
         }
     }
     #[cfg(not(feature = "devtools"))]
     {
-        Err("DevTools feature is not enabled".to_string())
+        Err(CommandError::from("DevTools feature is not enabled".to_string()))
     }
 }
 
@@ -791,7 +2079,7 @@ async fn window_set_ignore_cursor_events(
     label: String,
     ignore: bool,
     skip_native: Option<bool>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let mut window = app.get_webview_window(&label);
     if window.is_none() {
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -803,12 +2091,12 @@ async fn window_set_ignore_cursor_events(
         if !skip_native_call {
             window
                 .set_ignore_cursor_events(ignore)
-                .map_err(|e| format!("Failed to set ignore cursor events: {}", e))?;
+                .map_err(|e| CommandError::from(format!("Failed to set ignore cursor events: {}", e)))?;
         }
 
         #[cfg(target_os = "windows")]
         {
-            let hwnd = window.hwnd().map_err(|e| format!("Failed to get HWND: {}", e))?;
+            let hwnd = window.hwnd().map_err(|e| CommandError::from(format!("Failed to get HWND: {}", e)))?;
             unsafe {
                 let hwnd_ptr: winapi::shared::windef::HWND = std::mem::transmute(hwnd.0);
                 update_window_ex_style(hwnd_ptr, ignore);
@@ -820,13 +2108,104 @@ async fn window_set_ignore_cursor_events(
     }
 }
 
+/// Для окна микрофона выбор сохраняется в конфиг и применяется при следующем создании окна
+#[tauri::command]
+async fn window_set_always_on_top(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    label: String,
+    on_top: bool,
+) -> CommandResult<()> {
+    let window = app.get_webview_window(&label);
+    if let Some(window) = &window {
+        window
+            .set_always_on_top(on_top)
+            .map_err(|error| CommandError::from(format!("Failed to set always on top: {error}")))?;
+    }
+    if label == "mic" {
+        let updated = state
+            .update_with(|config| {
+                config.mic_always_on_top = on_top;
+                Ok(())
+            })
+            .await?;
+        app.emit("config:updated", &updated)?;
+    } else if window.is_none() {
+        return Err(CommandError::not_found(format!("Window {label} not found")));
+    }
+    Ok(())
+}
+
+/// Для окна микрофона новая позиция сохраняется в конфиг
+#[tauri::command]
+async fn window_move_to_monitor(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+    label: String,
+    monitor_index: usize,
+) -> CommandResult<WindowPosition> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| CommandError::not_found(format!("Window {label} not found")))?;
+    let monitor_count = window
+        .available_monitors()
+        .map_err(|error| CommandError::from(format!("Failed to enumerate monitors: {error}")))?
+        .len();
+    if monitor_index >= monitor_count {
+        return Err(CommandError::invalid_input(format!(
+            "Monitor index {monitor_index} is out of range, {monitor_count} monitor(s) available"
+        )));
+    }
+    let anchor = state.mic_anchor().await;
+    let (x, y) = window_layout::move_to_monitor(&app, &label, monitor_index, &anchor)?;
+    let position = WindowPosition { x, y };
+    if label == "mic" {
+        let updated = state
+            .update_with(|config| {
+                config.mic_window_position = Some(position.clone());
+                Ok(())
+            })
+            .await?;
+        app.emit("config:updated", &updated)?;
+    }
+    Ok(position)
+}
+
+#[tauri::command]
+async fn layout_save(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+) -> CommandResult<AppConfig> {
+    let layout = window_layout::capture(&app)?;
+    let updated = state
+        .update_with(|config| {
+            config.window_layout = layout;
+            Ok(())
+        })
+        .await?;
+    app.emit("config:updated", &updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+async fn layout_restore(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<ConfigState>>,
+) -> CommandResult<Vec<String>> {
+    let config = state.get().await;
+    if config.window_layout.is_empty() {
+        return Err(CommandError::not_found("No saved window layout"));
+    }
+    window_layout::apply(&app, &config.window_layout).map_err(CommandError::from)
+}
+
 #[tauri::command]
-async fn window_open_main(app: tauri::AppHandle) -> Result<(), String> {
+async fn window_open_main(app: tauri::AppHandle) -> CommandResult<()> {
     // Пробуем получить существующее окно
     if let Some(main) = app.get_webview_window("main") {
-        main.unminimize().map_err(|e| format!("Failed to unminimize main window: {}", e))?;
-        main.show().map_err(|e| format!("Failed to show main window: {}", e))?;
-        main.set_focus().map_err(|e| format!("Failed to focus main window: {}", e))?;
+        main.unminimize().map_err(|e| CommandError::from(format!("Failed to unminimize main window: {}", e)))?;
+        main.show().map_err(|e| CommandError::from(format!("Failed to show main window: {}", e)))?;
+        main.set_focus().map_err(|e| CommandError::from(format!("Failed to focus main window: {}", e)))?;
         Ok(())
     } else {
         // Если окно не найдено, создаем его заново
@@ -843,10 +2222,14 @@ async fn window_open_main(app: tauri::AppHandle) -> Result<(), String> {
             .transparent(true)
             .background_color(Color(0, 0, 0, 0))
             .build()
-            .map_err(|e| format!("Failed to create main window: {}", e))?;
+            .map_err(|e| CommandError::from(format!("Failed to create main window: {}", e)))?;
+        let app_handle = app.clone();
+        window.on_window_event(move |event| {
+            tray::handle_main_window_event(&app_handle, event);
+        });
         
-        window.show().map_err(|e| format!("Failed to show main window: {}", e))?;
-        window.set_focus().map_err(|e| format!("Failed to focus main window: {}", e))?;
+        window.show().map_err(|e| CommandError::from(format!("Failed to show main window: {}", e)))?;
+        window.set_focus().map_err(|e| CommandError::from(format!("Failed to focus main window: {}", e)))?;
         Ok(())
     }
 }
@@ -886,6 +2269,15 @@ fn main() {
                     logging::log_message("[SingleInstance] AuthQueue not ready, saving to pending");
                     PENDING_DEEP_LINKS.lock().unwrap().push(url);
                 }
+            } else {
+                // Повторный запуск без deep link: поднимаем уже работающее окно
+                logging::log_message("[SingleInstance] Second launch detected, focusing main window");
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = window_open_main(app_handle).await {
+                        logging::log_message(&format!("[SingleInstance] Failed to focus main window: {}", e));
+                    }
+                });
             }
         }))
         .plugin(tauri_plugin_deep_link::init())
@@ -899,9 +2291,21 @@ fn main() {
             let config_state =
                 Arc::new(tauri::async_runtime::block_on(ConfigState::initialize(&app_handle))?);
             let initial_config = tauri::async_runtime::block_on(config_state.get());
+            let config_path = tauri::async_runtime::block_on(config_state.path());
+            logging::log_startup_diagnostics(app_handle, &initial_config, &config_path);
 
             let hotkeys = Arc::new(HotkeyState::new());
             let fast_whisper = Arc::new(FastWhisperManager::new());
+            if let Some(install_dir) = initial_config
+                .local_speech_install_dir
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+            {
+                tauri::async_runtime::block_on(
+                    fast_whisper.set_install_override(Some(std::path::PathBuf::from(install_dir))),
+                );
+            }
             let auth_queue = Arc::new(AuthQueue::new());
             let oauth_server_state = Arc::new(OAuthServerState::new());
 
@@ -921,9 +2325,9 @@ fn main() {
             deep_link_file::start_deep_link_file_polling(app_handle.clone(), auth_queue.clone());
             
             // Запускаем OAuth HTTP сервер при работе от администратора
-            // Это нужно потому что deep link не работает из-за UIPI
-            if oauth::is_running_as_admin() {
-                logging::log_message("[Main] Running as admin, starting OAuth HTTP server...");
+            // (deep link не работает из-за UIPI) или если пользователь выбрал HTTP callback
+            if oauth::should_use_http_callback(&initial_config) {
+                logging::log_message("[Main] HTTP OAuth callback enabled, starting OAuth HTTP server...");
                 let app_for_oauth = app_handle.clone();
                 let queue_for_oauth = auth_queue.clone();
                 let state_for_oauth = oauth_server_state.clone();
@@ -944,18 +2348,35 @@ fn main() {
             }
             
             handle_config_effects(&app_handle, &initial_config, hotkeys, fast_whisper);
+
+            if initial_config.history_audio_retention_days > 0 {
+                let app_for_prune = app_handle.clone();
+                let retention_days = initial_config.history_audio_retention_days;
+                tauri::async_runtime::spawn(async move {
+                    match history::prune_history_audio(&app_for_prune, retention_days).await {
+                        Ok(pruned) if !pruned.is_empty() => {
+                            logging::log_message(&format!(
+                                "[history] Pruned audio of {} entries past {retention_days}d",
+                                pruned.len()
+                            ));
+                            let _ = app_for_prune.emit(
+                                "history:updated",
+                                json!({"type": "audio-pruned", "ids": &pruned}),
+                            );
+                        }
+                        Ok(_) => {}
+                        Err(error) => {
+                            logging::log_message(&format!("[history] Audio prune failed: {error}"))
+                        }
+                    }
+                });
+            }
             
-            // Обрабатываем закрытие главного окна - скрываем его вместо закрытия приложения
+            // Закрытие/сворачивание главного окна: в трей или выход, в зависимости от настроек
             if let Some(main_window) = app.get_webview_window("main") {
                 let app_handle_clone = app_handle.clone();
                 main_window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                        // Скрываем окно вместо закрытия, чтобы приложение продолжало работать в фоне
-                        api.prevent_close();
-                        let _ = app_handle_clone.get_webview_window("main").and_then(|w| {
-                            w.hide().ok()
-                        });
-                    }
+                    tray::handle_main_window_event(&app_handle_clone, event);
                 });
             }
             
@@ -965,54 +2386,150 @@ fn main() {
             config_get,
             config_update,
             config_set_auth,
+            auth_logout,
+            onboarding_mark_step,
+            onboarding_reset,
             config_reset,
             config_path,
+            config_verify_integrity,
+            config_field_default,
+            overlay_themes,
+            overlay_set_theme,
+            llm_set_output_format,
             history_get,
+            history_list,
+            history_count,
             history_add,
             history_update,
             history_clear,
+            history_prune_audio,
+            retranscribe_history,
             history_save_audio,
             history_read_audio,
+            history_export_audio,
+            history_export_markdown,
+            actions_recent,
+            actions_most_used,
+            trigger_action,
+            actions_reorder,
+            actions_set_enabled,
+            actions_get_language,
+            actions_set_language,
+            actions_set_completion_sound,
+            actions_export,
+            actions_import,
             notes_get,
+            notes_get_by_username,
+            notes_search,
+            notes_list_tags,
+            notes_export_markdown,
+            notes_set_pinned,
+            notes_set_archived,
+            notes_merge,
+            notes_list_trash,
+            notes_restore,
+            notes_empty_trash,
+            notes_backup,
+            notes_count,
             notes_create,
             notes_update,
+            notes_duplicate,
+            notes_append,
             notes_delete,
             notes_bulk_delete,
+            notes_get_storage_mode,
+            notes_set_storage_mode,
+            notes_repair,
+            history_repair,
             resources_sound_path,
             resources_sound_data,
             resources_play_sound,
+            play_test_sound,
+            audio_output_devices,
+            system_dependencies,
+            diagnostics_bundle,
+            sound_resolve,
+            sounds_bundled,
+            audio_duck_others,
             auth_consume_pending,
+            auth_peek_pending,
             auth_start_oauth,
             auth_is_admin,
+            auth_set_prefer_http_oauth,
+            oauth_debug,
+            oauth_start_url,
+            open_download_page,
             get_log_file_path,
+            logs_clear,
+            logs_rotate,
             open_file_path,
+            storage_usage,
+            storage_clear,
+            insert_text,
+            type_text,
             local_speech_get_status,
             local_speech_check_health,
             local_speech_install,
+            local_speech_set_install_dir,
+            local_speech_resource_usage,
+            local_speech_list_processes,
+            local_speech_kill_strays,
             local_speech_start,
+            speech_set_mode,
+            speech_supported_languages,
+            llm_set_model,
             local_speech_restart,
+            local_speech_set_runtime,
             local_speech_reinstall,
             local_speech_stop,
+            local_speech_verify,
+            local_speech_info,
+            local_speech_wake,
+            local_speech_abort_install,
             local_speech_check_model_downloaded,
+            benchmark_transcription,
+            speech_transcode_audio,
+            transcribe_batch,
             ollama_check_installed,
             ollama_is_server_running,
             ollama_list_models,
             ollama_pull_model,
             ollama_warmup_model,
+            warmup_all,
             ollama_chat_completions,
             ollama_chat_completions_stream,
+            rate_limit_acquire,
+            llm_cancel,
+            llm_cancel_all,
             openai_chat_completions,
             openai_chat_completions_stream,
             gemini_generate_content_stream,
             action_hotkeys_register,
             action_hotkeys_clear,
             hotkeys_set_recording_active,
+            hotkeys_reserved,
+            hotkeys_reset,
             window_open_devtools,
             window_open_main,
-            window_set_ignore_cursor_events
+            layout_save,
+            layout_restore,
+            window_set_ignore_cursor_events,
+            window_set_always_on_top,
+            window_move_to_monitor,
+            mic_permission_status,
+            mic_request_permission,
+            audio_self_test
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Иначе установочный скрипт и его pip/python переживают закрытие приложения
+                if let Some(manager) = app.try_state::<Arc<FastWhisperManager>>() {
+                    manager.abort_running_script();
+                }
+            }
+        });
 }
 
 fn setup_deep_link_listener(app: &tauri::AppHandle, queue: Arc<AuthQueue>) {
@@ -1060,7 +2577,15 @@ fn handle_config_effects(
             Some(trimmed.to_string())
         }
     };
+    hotkeys.set_debounce_ms(config.hotkey_debounce_ms);
+    hotkeys.set_auto_hide_delay_ms(config.mic_auto_hide_delay_ms);
+    rate_limit::set_limits(&config.provider_rate_limits);
+    tray::set_main_window_behavior(config.close_to_tray, config.minimize_to_tray);
+    speech.set_health_poll_interval_ms(app, config.local_speech_health_poll_ms);
+    speech.set_idle_stop_minutes(config.local_speech_idle_stop_minutes);
+    speech.set_lazy_start(config.local_speech_lazy_start && config.speech.mode == "local");
     hotkeys.register_mic(app, accelerator);
+    mouse_trigger::apply(app, config.mouse_trigger.clone());
 
     if should_auto_start_local_speech(config) {
         let manager = speech.clone();
@@ -1068,9 +2593,7 @@ fn handle_config_effects(
         tauri::async_runtime::spawn(async move {
             // Check if server is already healthy before attempting to start
             // This prevents unnecessary restart cycles
-            if !manager.is_server_healthy().await {
-                let _ = manager.start_existing(&app_handle).await;
-            }
+            let _ = manager.start_if_auto_enabled(&app_handle).await;
         });
     }
 
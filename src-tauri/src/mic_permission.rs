@@ -0,0 +1,120 @@
+//! Статус системного разрешения на микрофон: без него запись молча получает тишину.
+
+use anyhow::Result;
+
+pub const GRANTED: &str = "granted";
+pub const DENIED: &str = "denied";
+/// Only macOS reports a not-yet-asked state.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub const UNDETERMINED: &str = "undetermined";
+
+/// Privacy settings page the user is sent to when access was denied; `None` where the OS has none.
+#[cfg(target_os = "windows")]
+pub const SETTINGS_URL: Option<&str> = Some("ms-settings:privacy-microphone");
+#[cfg(target_os = "macos")]
+pub const SETTINGS_URL: Option<&str> =
+    Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone");
+#[cfg(all(unix, not(target_os = "macos")))]
+pub const SETTINGS_URL: Option<&str> = None;
+
+/// Reads the consent switches under `CapabilityAccessManager`: the system-wide one (HKLM),
+/// the per-user one and the one for desktop (non-packaged) apps. Any `Deny` blocks recording.
+#[cfg(target_os = "windows")]
+pub fn status() -> Result<&'static str> {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use winreg::RegKey;
+
+    const CONSENT_KEY: &str =
+        r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+    let read = |root: RegKey, path: &str| {
+        root.open_subkey(path)
+            .and_then(|key| key.get_value::<String, _>("Value"))
+            .ok()
+    };
+    let values = [
+        read(RegKey::predef(HKEY_LOCAL_MACHINE), CONSENT_KEY),
+        read(RegKey::predef(HKEY_CURRENT_USER), CONSENT_KEY),
+        read(RegKey::predef(HKEY_CURRENT_USER), &format!(r"{CONSENT_KEY}\NonPackaged")),
+    ];
+    if values.iter().flatten().any(|value| value.eq_ignore_ascii_case("Deny")) {
+        return Ok(DENIED);
+    }
+    // Ключей нет на старых сборках Windows 10 — там доступ не ограничивается
+    Ok(GRANTED)
+}
+
+/// Desktop apps on Windows get no consent prompt; the status is only re-read.
+#[cfg(target_os = "windows")]
+pub fn request() -> Result<&'static str> {
+    status()
+}
+
+/// Дочерний osascript наследует TCC-атрибуцию приложения, поэтому видит разрешение самого Winky.
+#[cfg(target_os = "macos")]
+fn run_jxa(script: &str) -> Result<String> {
+    use anyhow::anyhow;
+
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", script])
+        .output()
+        .map_err(|error| anyhow!("Failed to run osascript: {error}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "osascript exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Maps `AVAuthorizationStatus`: 0 — not determined, 1 — restricted, 2 — denied, 3 — authorized.
+#[cfg(target_os = "macos")]
+fn from_av_status(value: &str) -> &'static str {
+    match value {
+        "3" => GRANTED,
+        "1" | "2" => DENIED,
+        _ => UNDETERMINED,
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn status() -> Result<&'static str> {
+    let output = run_jxa(
+        "ObjC.import('AVFoundation'); \
+         String($.AVCaptureDevice.authorizationStatusForMediaType($.AVMediaTypeAudio))",
+    )?;
+    Ok(from_av_status(&output))
+}
+
+/// Shows the system prompt when the status is not determined yet and waits for the answer.
+#[cfg(target_os = "macos")]
+pub fn request() -> Result<&'static str> {
+    let current = status()?;
+    if current != UNDETERMINED {
+        return Ok(current);
+    }
+    // Колбэк приходит только пока крутится run loop — ждём ответа не дольше минуты
+    run_jxa(
+        "ObjC.import('AVFoundation'); \
+         var done = false; \
+         $.AVCaptureDevice.requestAccessForMediaTypeCompletionHandler($.AVMediaTypeAudio, function () { done = true; }); \
+         for (var i = 0; i < 600 && !done; i++) { \
+             $.NSRunLoop.currentRunLoop.runUntilDate($.NSDate.dateWithTimeIntervalSinceNow(0.1)); \
+         } \
+         String(done)",
+    )?;
+    status()
+}
+
+/// Linux has no OS-level microphone consent; access is governed by the audio server.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn status() -> Result<&'static str> {
+    Ok(GRANTED)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn request() -> Result<&'static str> {
+    status()
+}
@@ -0,0 +1,150 @@
+//! Mic trigger on an extra mouse button via a low-level mouse hook (Windows only).
+
+use tauri::AppHandle;
+
+use crate::logging;
+use crate::types::MouseTrigger;
+
+/// Buttons 1 and 2 (left/right) are never hooked: binding them would break normal clicking.
+const MIN_TRIGGER_BUTTON: u8 = 3;
+const MAX_TRIGGER_BUTTON: u8 = 5;
+
+fn validate(trigger: Option<MouseTrigger>) -> Option<MouseTrigger> {
+    let trigger = trigger?;
+    if !(MIN_TRIGGER_BUTTON..=MAX_TRIGGER_BUTTON).contains(&trigger.button) {
+        logging::log_message(&format!(
+            "[mouse_trigger] Ignoring unsupported mouse button {} (allowed {MIN_TRIGGER_BUTTON}-{MAX_TRIGGER_BUTTON})",
+            trigger.button
+        ));
+        return None;
+    }
+    Some(trigger)
+}
+
+#[cfg(all(target_os = "windows", feature = "mouse-trigger"))]
+mod hook {
+    use std::sync::Mutex;
+
+    use once_cell::sync::{Lazy, OnceCell};
+    use tauri::{AppHandle, Emitter};
+    use winapi::shared::minwindef::{LPARAM, LRESULT, WPARAM};
+    use winapi::shared::windef::HHOOK;
+    use winapi::um::winuser::{
+        CallNextHookEx, GetAsyncKeyState, GetMessageW, SetWindowsHookExW, HC_ACTION, MSG,
+        MSLLHOOKSTRUCT, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT, WH_MOUSE_LL,
+        WM_MBUTTONDOWN, WM_XBUTTONDOWN, XBUTTON1, XBUTTON2,
+    };
+
+    use crate::logging;
+    use crate::types::MouseTrigger;
+
+    static APP: OnceCell<AppHandle> = OnceCell::new();
+    static TRIGGER: Lazy<Mutex<Option<MouseTrigger>>> = Lazy::new(|| Mutex::new(None));
+
+    fn is_key_down(key: i32) -> bool {
+        unsafe { (GetAsyncKeyState(key) as u16 & 0x8000) != 0 }
+    }
+
+    fn modifier_pressed(modifier: Option<&str>) -> bool {
+        match modifier.map(|value| value.trim().to_ascii_lowercase()) {
+            None => true,
+            Some(value) if value.is_empty() => true,
+            Some(value) => match value.as_str() {
+                "ctrl" | "control" => is_key_down(VK_CONTROL),
+                "alt" => is_key_down(VK_MENU),
+                "shift" => is_key_down(VK_SHIFT),
+                "super" | "win" | "meta" => is_key_down(VK_LWIN) || is_key_down(VK_RWIN),
+                _ => false,
+            },
+        }
+    }
+
+    /// Human-readable trigger, e.g. `Ctrl+Mouse4`.
+    fn describe(trigger: &MouseTrigger) -> String {
+        match trigger.modifier.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(modifier) => format!("{modifier}+Mouse{}", trigger.button),
+            None => format!("Mouse{}", trigger.button),
+        }
+    }
+
+    unsafe extern "system" fn mouse_proc(code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        if code == HC_ACTION {
+            let button = match w_param as u32 {
+                WM_MBUTTONDOWN => Some(3u8),
+                WM_XBUTTONDOWN => {
+                    let info = &*(l_param as *const MSLLHOOKSTRUCT);
+                    match (info.mouseData >> 16) as u16 {
+                        XBUTTON1 => Some(4),
+                        XBUTTON2 => Some(5),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(button) = button {
+                let matched = TRIGGER
+                    .lock()
+                    .map(|guard| {
+                        guard.as_ref().map_or(false, |trigger| {
+                            trigger.button == button && modifier_pressed(trigger.modifier.as_deref())
+                        })
+                    })
+                    .unwrap_or(false);
+                if matched {
+                    if let Some(app) = APP.get() {
+                        let accelerator = TRIGGER
+                            .lock()
+                            .ok()
+                            .and_then(|guard| guard.as_ref().map(describe))
+                            .unwrap_or_else(|| format!("Mouse{button}"));
+                        let _ = app.emit(
+                            "mic:shortcut",
+                            crate::hotkeys::mic_shortcut_payload("mouse", Some(&accelerator)),
+                        );
+                    }
+                }
+            }
+        }
+        CallNextHookEx(std::ptr::null_mut(), code, w_param, l_param)
+    }
+
+    pub fn apply(app: &AppHandle, trigger: Option<MouseTrigger>) {
+        let enabled = trigger.is_some();
+        if let Ok(mut guard) = TRIGGER.lock() {
+            *guard = trigger;
+        }
+        if !enabled || APP.set(app.clone()).is_err() {
+            // Хук уже запущен, достаточно обновить настройку
+            return;
+        }
+        std::thread::spawn(|| unsafe {
+            let hook: HHOOK = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_proc), std::ptr::null_mut(), 0);
+            if hook.is_null() {
+                logging::log_message(&format!(
+                    "[mouse_trigger] Failed to install mouse hook: {}",
+                    std::io::Error::last_os_error()
+                ));
+                return;
+            }
+            logging::log_message("[mouse_trigger] Mouse hook installed");
+            // Low-level хукам нужен цикл сообщений в потоке, который их установил
+            let mut message: MSG = std::mem::zeroed();
+            while GetMessageW(&mut message, std::ptr::null_mut(), 0, 0) > 0 {}
+        });
+    }
+}
+
+/// Applies the configured mouse trigger, installing the hook on first use.
+#[cfg(all(target_os = "windows", feature = "mouse-trigger"))]
+pub fn apply(app: &AppHandle, trigger: Option<MouseTrigger>) {
+    hook::apply(app, validate(trigger));
+}
+
+#[cfg(not(all(target_os = "windows", feature = "mouse-trigger")))]
+pub fn apply(_app: &AppHandle, trigger: Option<MouseTrigger>) {
+    if validate(trigger).is_some() {
+        logging::log_message(
+            "[mouse_trigger] Mouse trigger is configured but not supported in this build",
+        );
+    }
+}
@@ -1,15 +1,33 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use tokio::fs;
 use uuid::Uuid;
 
+use crate::{history, oauth};
+use crate::entry_count::CountCache;
+use crate::error::{self, CommandError};
+use crate::repair::{self, RepairReport};
+use crate::types::AppConfig;
+
 const NOTES_DIR_NAME: &str = "notes";
 const NOTES_FILE_NAME: &str = "notes.json";
+const NOTES_BACKUP_DIR_NAME: &str = "backups";
+const MAX_NOTES_BACKUPS: usize = 10;
 const LOCAL_PROFILE_ID: &str = "local";
+const NOTES_API_PATH: &str = "winky/notes/";
+const NOTES_API_PAGE_SIZE: u32 = 100;
+/// Разделитель между ранее надиктованным текстом и дописываемым.
+const NOTE_APPEND_SEPARATOR: &str = "\n\n";
+/// Разделитель между описаниями объединяемых заметок.
+const NOTE_MERGE_SEPARATOR: &str = "\n\n---\n\n";
+pub const NOTES_STORAGE_MODES: [&str; 2] = ["api", "local"];
+
+static COUNT_CACHE: CountCache = CountCache::new();
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -20,8 +38,41 @@ pub struct NoteEntry {
     pub description: String,
     #[serde(default)]
     pub x_username: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Kept but hidden from the default listing and tag counts; unlike the trash it never expires.
+    #[serde(default)]
+    pub archived: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Время перемещения в корзину; `None` — заметка активна
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+    /// Derived from `description` whenever notes are read or changed; values on disk are ignored.
+    #[serde(default, skip_deserializing)]
+    pub word_count: usize,
+    #[serde(default, skip_deserializing)]
+    pub char_count: usize,
+}
+
+impl NoteEntry {
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    fn refresh_counts(&mut self) {
+        self.word_count = self.description.split_whitespace().count();
+        self.char_count = self.description.chars().count();
+    }
+}
+
+/// Только поле корзины — для подсчёта активных заметок без разбора остальных полей.
+#[derive(Deserialize)]
+struct NoteTrashMarker {
+    #[serde(default)]
+    deleted_at: Option<serde::de::IgnoredAny>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,7 +81,17 @@ pub struct NoteListResponse {
     pub count: usize,
     pub next_page: Option<u32>,
     pub previous_page: Option<u32>,
+    /// At least 1, so an empty listing still has a (blank) first page.
+    #[serde(default)]
+    pub total_pages: u32,
+    #[serde(default)]
+    pub has_more: bool,
     pub results: Vec<NoteEntry>,
+    /// Sums over every matching note, not only the current page.
+    #[serde(default)]
+    pub total_word_count: usize,
+    #[serde(default)]
+    pub total_char_count: usize,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +100,11 @@ pub struct NoteCreateInput {
     pub title: String,
     pub description: Option<String>,
     pub x_username: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// Without it a note whose title matches an existing one (ignoring case) is rejected
+    /// with the [`error::DUPLICATE_TITLE`] code.
+    #[serde(default)]
+    pub allow_duplicate: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,6 +114,7 @@ pub struct NoteUpdateInput {
     pub title: Option<String>,
     pub description: Option<String>,
     pub x_username: Option<String>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -68,7 +135,14 @@ pub struct NoteBulkDeleteResponse {
     pub deleted_count: usize,
 }
 
-fn resolve_notes_dir(app: &AppHandle) -> Result<PathBuf> {
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NoteTagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+pub fn resolve_notes_dir(app: &AppHandle) -> Result<PathBuf> {
     let base_dir = app
         .path()
         .app_local_data_dir()
@@ -86,21 +160,67 @@ async fn notes_file_path(app: &AppHandle) -> Result<PathBuf> {
     Ok(dir.join(NOTES_FILE_NAME))
 }
 
-async fn read_notes(app: &AppHandle) -> Result<Vec<NoteEntry>> {
+/// Copies `notes.json` to `notes/backups/notes-{timestamp}.json`, keeping the newest
+/// [`MAX_NOTES_BACKUPS`] copies.
+pub async fn backup_notes(app: &AppHandle) -> Result<PathBuf> {
     let path = notes_file_path(app).await?;
     if !path.exists() {
-        return Ok(Vec::new());
+        return Err(anyhow!("There is no notes file to back up yet"));
+    }
+    let backup_dir = resolve_notes_dir(app)?.join(NOTES_BACKUP_DIR_NAME);
+    fs::create_dir_all(&backup_dir)
+        .await
+        .with_context(|| format!("create backup directory at {}", backup_dir.display()))?;
+    let backup_path = backup_dir.join(format!(
+        "notes-{}.json",
+        Utc::now().format("%Y%m%d-%H%M%S-%3f")
+    ));
+    fs::copy(&path, &backup_path)
+        .await
+        .with_context(|| format!("back up notes to {}", backup_path.display()))?;
+    prune_notes_backups(&backup_dir).await;
+    Ok(backup_path)
+}
+
+/// Имена содержат сортируемую метку времени, поэтому старые копии идут первыми
+async fn prune_notes_backups(backup_dir: &Path) {
+    let Ok(mut read_dir) = fs::read_dir(backup_dir).await else {
+        return;
+    };
+    let mut backups = Vec::new();
+    while let Ok(Some(entry)) = read_dir.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("notes-") && name.ends_with(".json") {
+            backups.push(entry.path());
+        }
     }
-    let contents = fs::read_to_string(&path)
+    backups.sort();
+    let excess = backups.len().saturating_sub(MAX_NOTES_BACKUPS);
+    for stale in backups.into_iter().take(excess) {
+        if let Err(error) = fs::remove_file(&stale).await {
+            eprintln!("[notes] Failed to remove old backup {}: {error}", stale.display());
+        }
+    }
+}
+
+async fn read_notes(app: &AppHandle) -> Result<Vec<NoteEntry>> {
+    let path = notes_file_path(app).await?;
+    let Some(contents) = repair::read_to_string_retrying(&path)
         .await
-        .with_context(|| format!("read notes from {}", path.display()))?;
+        .with_context(|| format!("read notes from {}", path.display()))?
+    else {
+        return Ok(Vec::new());
+    };
     if contents.trim().is_empty() {
         return Ok(Vec::new());
     }
     match serde_json::from_str::<Vec<NoteEntry>>(&contents) {
-        Ok(entries) => Ok(entries),
+        Ok(mut entries) => {
+            entries.iter_mut().for_each(NoteEntry::refresh_counts);
+            Ok(entries)
+        }
         Err(error) => {
-            eprintln!("[notes] Failed to parse notes file: {error}");
+            eprintln!("[notes] Failed to parse notes file (use notes_repair to recover): {error}");
             Ok(Vec::new())
         }
     }
@@ -109,16 +229,214 @@ async fn read_notes(app: &AppHandle) -> Result<Vec<NoteEntry>> {
 async fn write_notes(app: &AppHandle, entries: &[NoteEntry]) -> Result<()> {
     let path = notes_file_path(app).await?;
     let serialized = serde_json::to_string_pretty(entries).context("serialize notes")?;
-    fs::write(&path, serialized)
+    repair::write_atomic(&path, &serialized)
         .await
         .with_context(|| format!("write notes to {}", path.display()))
 }
 
-pub async fn list_notes(app: &AppHandle, page: u32, page_size: u32) -> Result<NoteListResponse> {
+/// Notes outside the trash, in stored order.
+async fn read_active_notes(app: &AppHandle) -> Vec<NoteEntry> {
+    read_notes(app)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| !entry.is_trashed())
+        .collect()
+}
+
+/// Приводит X-handle к единому виду: без пробелов, ведущего `@` и регистра.
+pub fn normalize_username(value: &str) -> String {
+    value.trim().trim_start_matches('@').trim().to_lowercase()
+}
+
+/// Trims, lowercases and dedupes tags, keeping the order of first occurrence.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteSortKey {
+    Created,
+    Updated,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoteSort {
+    pub key: NoteSortKey,
+    pub descending: bool,
+}
+
+impl NoteSort {
+    /// Parses `sort_by` (`created`/`updated`/`title`) and `direction` (`asc`/`desc`).
+    /// Without `sort_by` the stored order is kept. Dates default to newest first, titles to A–Z.
+    pub fn parse(sort_by: Option<&str>, direction: Option<&str>) -> Result<Option<Self>> {
+        let Some(sort_by) = sort_by.map(str::trim).filter(|value| !value.is_empty()) else {
+            return Ok(None);
+        };
+        let key = match sort_by.to_ascii_lowercase().as_str() {
+            "created" => NoteSortKey::Created,
+            "updated" => NoteSortKey::Updated,
+            "title" => NoteSortKey::Title,
+            other => return Err(anyhow!("Unsupported sort key: {other}")),
+        };
+        let descending = match direction.map(|value| value.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") => key != NoteSortKey::Title,
+            Some("asc") => false,
+            Some("desc") => true,
+            Some(other) => return Err(anyhow!("Unsupported sort direction: {other}")),
+        };
+        Ok(Some(Self { key, descending }))
+    }
+
+    fn apply(self, entries: &mut [NoteEntry]) {
+        // Даты сравниваем как моменты времени: у импортированных заметок бывают разные смещения
+        let timestamp = |value: &str| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|parsed| parsed.timestamp_millis())
+                .unwrap_or(i64::MIN)
+        };
+        entries.sort_by(|a, b| {
+            let ordering = match self.key {
+                NoteSortKey::Created => timestamp(&a.created_at).cmp(&timestamp(&b.created_at)),
+                NoteSortKey::Updated => timestamp(&a.updated_at).cmp(&timestamp(&b.updated_at)),
+                NoteSortKey::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// Inclusive bounds on `created_at`; a missing side is open.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoteDateRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl NoteDateRange {
+    /// Parses ISO-8601 bounds (RFC 3339 or `YYYY-MM-DD`); a plain `to` date covers its whole day.
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self> {
+        let bound = |name: &str, value: Option<&str>, end_of_day: bool| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| history::parse_export_bound(value, end_of_day))
+                .transpose()
+                .map_err(|error| anyhow!("{name}: {error}"))
+        };
+        let range = Self {
+            from: bound("created_from", from, false)?,
+            to: bound("created_to", to, true)?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err(anyhow!("created_from must not be later than created_to"));
+            }
+        }
+        Ok(range)
+    }
+
+    fn contains(&self, entry: &NoteEntry) -> bool {
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        // Заметка с нечитаемой датой не может попасть в явно заданный диапазон
+        let Ok(created) = DateTime::parse_from_rfc3339(&entry.created_at) else {
+            return false;
+        };
+        let created = created.with_timezone(&Utc);
+        if self.from.is_some_and(|from| created < from) {
+            return false;
+        }
+        self.to.is_none_or(|to| created <= to)
+    }
+}
+
+/// Pinned notes come first; the sort is stable, so the requested (or stored) order is kept
+/// inside each group. Sorting happens before pagination, so page boundaries stay consistent.
+pub async fn list_notes(
+    app: &AppHandle,
+    page: u32,
+    page_size: u32,
+    sort: Option<NoteSort>,
+    created: NoteDateRange,
+    include_archived: bool,
+) -> Result<NoteListResponse> {
+    let mut entries = read_active_notes(app).await;
+    entries.retain(|entry| (include_archived || !entry.archived) && created.contains(entry));
+    if let Some(sort) = sort {
+        sort.apply(&mut entries);
+    }
+    entries.sort_by_key(|entry| !entry.pinned);
+    Ok(paginate(entries, page, page_size))
+}
+
+pub async fn list_notes_by_username(
+    app: &AppHandle,
+    username: &str,
+    page: u32,
+    page_size: u32,
+) -> Result<NoteListResponse> {
+    let username = normalize_username(username);
+    if username.is_empty() {
+        return Err(CommandError::invalid_input("Username cannot be empty").into());
+    }
+    let entries = read_active_notes(app)
+        .await
+        .into_iter()
+        .filter(|entry| normalize_username(&entry.x_username) == username)
+        .collect();
+    Ok(paginate(entries, page, page_size))
+}
+
+/// Case-insensitive search over title, description and username. The query is split on
+/// whitespace and a note must contain every token; an empty query matches all notes.
+pub async fn search_notes(
+    app: &AppHandle,
+    query: &str,
+    page: u32,
+    page_size: u32,
+) -> Result<NoteListResponse> {
+    let tokens: Vec<String> = query
+        .split_whitespace()
+        .map(|token| token.to_lowercase())
+        .collect();
+    let entries = read_active_notes(app)
+        .await
+        .into_iter()
+        .filter(|entry| {
+            let haystack = format!(
+                "{}\n{}\n{}\n{}",
+                entry.title,
+                entry.description,
+                entry.x_username,
+                entry.tags.join(" ")
+            )
+            .to_lowercase();
+            tokens.iter().all(|token| haystack.contains(token.as_str()))
+        })
+        .collect();
+    Ok(paginate(entries, page, page_size))
+}
+
+fn paginate(entries: Vec<NoteEntry>, page: u32, page_size: u32) -> NoteListResponse {
     let page = page.max(1);
     let page_size = page_size.max(1);
-    let entries = read_notes(app).await.unwrap_or_default();
     let total = entries.len();
+    let total_word_count = entries.iter().map(|entry| entry.word_count).sum();
+    let total_char_count = entries.iter().map(|entry| entry.char_count).sum();
     let start = (page as usize - 1) * page_size as usize;
     let end = usize::min(start + page_size as usize, total);
     let results = if start < total {
@@ -128,27 +446,46 @@ pub async fn list_notes(app: &AppHandle, page: u32, page_size: u32) -> Result<No
     };
     let next_page = if end < total { Some(page + 1) } else { None };
     let previous_page = if page > 1 && start > 0 { Some(page - 1) } else { None };
+    let total_pages = total.div_ceil(page_size as usize).max(1) as u32;
 
-    Ok(NoteListResponse {
+    NoteListResponse {
         count: total,
         next_page,
         previous_page,
+        total_pages,
+        has_more: next_page.is_some(),
         results,
-    })
+        total_word_count,
+        total_char_count,
+    }
+}
+
+/// Rejects a title that an active or archived note already has, ignoring case.
+fn check_unique_title(entries: &[NoteEntry], title: &str) -> Result<()> {
+    let lowered = title.to_lowercase();
+    let exists = entries
+        .iter()
+        .any(|entry| !entry.is_trashed() && entry.title.trim().to_lowercase() == lowered);
+    if exists {
+        return Err(CommandError::new(
+            error::DUPLICATE_TITLE,
+            format!("A note titled \"{title}\" already exists"),
+        )
+        .into());
+    }
+    Ok(())
 }
 
 pub async fn create_note(app: &AppHandle, payload: NoteCreateInput) -> Result<NoteEntry> {
-    let mut entries = match read_notes(app).await {
-        Ok(existing) => existing,
-        Err(error) => {
-            eprintln!("[notes] Failed to read notes before create: {error}");
-            Vec::new()
-        }
-    };
+    // Файл занят дольше ретраев — не перезаписываем его пустым списком
+    let mut entries = read_notes(app).await?;
 
     let trimmed_title = payload.title.trim();
     if trimmed_title.is_empty() {
-        return Err(anyhow!("Title cannot be empty"));
+        return Err(CommandError::invalid_input("Title cannot be empty").into());
+    }
+    if !payload.allow_duplicate {
+        check_unique_title(&entries, trimmed_title)?;
     }
     let description = payload.description.unwrap_or_default();
     let x_username = payload
@@ -156,17 +493,25 @@ pub async fn create_note(app: &AppHandle, payload: NoteCreateInput) -> Result<No
         .unwrap_or_default()
         .trim()
         .to_string();
+    let tags = normalize_tags(&payload.tags.unwrap_or_default());
     let now = Utc::now().to_rfc3339();
 
-    let entry = NoteEntry {
+    let mut entry = NoteEntry {
         id: Uuid::new_v4().to_string(),
         profile: LOCAL_PROFILE_ID.to_string(),
         title: trimmed_title.to_string(),
         description,
         x_username,
+        tags,
+        pinned: false,
+        archived: false,
         created_at: now.clone(),
         updated_at: now,
+        deleted_at: None,
+        word_count: 0,
+        char_count: 0,
     };
+    entry.refresh_counts();
 
     entries.insert(0, entry.clone());
     write_notes(app, &entries).await?;
@@ -174,15 +519,15 @@ pub async fn create_note(app: &AppHandle, payload: NoteCreateInput) -> Result<No
 }
 
 pub async fn update_note(app: &AppHandle, payload: NoteUpdateInput) -> Result<NoteEntry> {
-    let mut entries = read_notes(app).await.unwrap_or_default();
+    let mut entries = read_notes(app).await?;
     let mut updated_entry: Option<NoteEntry> = None;
 
     for entry in &mut entries {
-        if entry.id == payload.id {
+        if entry.id == payload.id && !entry.is_trashed() {
             if let Some(title) = payload.title.as_ref() {
                 let trimmed = title.trim();
                 if trimmed.is_empty() {
-                    return Err(anyhow!("Title cannot be empty"));
+                    return Err(CommandError::invalid_input("Title cannot be empty").into());
                 }
                 entry.title = trimmed.to_string();
             }
@@ -192,36 +537,499 @@ pub async fn update_note(app: &AppHandle, payload: NoteUpdateInput) -> Result<No
             if let Some(x_username) = payload.x_username.as_ref() {
                 entry.x_username = x_username.trim().to_string();
             }
+            if let Some(tags) = payload.tags.as_ref() {
+                entry.tags = normalize_tags(tags);
+            }
             entry.updated_at = Utc::now().to_rfc3339();
+            entry.refresh_counts();
             updated_entry = Some(entry.clone());
             break;
         }
     }
 
-    let updated = updated_entry.ok_or_else(|| anyhow!("Note not found"))?;
+    let updated = updated_entry.ok_or_else(|| CommandError::not_found("Note not found"))?;
     write_notes(app, &entries).await?;
     Ok(updated)
 }
 
-pub async fn delete_note(app: &AppHandle, payload: NoteDeleteInput) -> Result<()> {
-    let mut entries = read_notes(app).await.unwrap_or_default();
-    let before = entries.len();
-    entries.retain(|entry| entry.id != payload.id);
-    if entries.len() == before {
-        return Err(anyhow!("Note not found"));
+pub async fn duplicate_note(app: &AppHandle, id: &str) -> Result<NoteEntry> {
+    let mut entries = read_notes(app).await?;
+    let source = entries
+        .iter()
+        .find(|entry| entry.id == id && !entry.is_trashed())
+        .cloned()
+        .ok_or_else(|| CommandError::not_found("Note not found"))?;
+    let now = Utc::now().to_rfc3339();
+
+    let entry = NoteEntry {
+        id: Uuid::new_v4().to_string(),
+        title: format!("{} (copy)", source.title),
+        pinned: false,
+        archived: false,
+        created_at: now.clone(),
+        updated_at: now,
+        ..source
+    };
+
+    entries.insert(0, entry.clone());
+    write_notes(app, &entries).await?;
+    Ok(entry)
+}
+
+/// Дописывает текст в конец описания через пустую строку, как очередную диктовку.
+pub async fn append_to_note(app: &AppHandle, id: &str, text: &str) -> Result<NoteEntry> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(CommandError::invalid_input("Text cannot be empty").into());
+    }
+    let mut entries = read_notes(app).await?;
+    let entry = entries
+        .iter_mut()
+        .find(|entry| entry.id == id && !entry.is_trashed())
+        .ok_or_else(|| CommandError::not_found("Note not found"))?;
+
+    let existing = entry.description.trim_end();
+    entry.description = if existing.is_empty() {
+        text.to_string()
+    } else {
+        format!("{existing}{NOTE_APPEND_SEPARATOR}{text}")
+    };
+    entry.updated_at = Utc::now().to_rfc3339();
+    entry.refresh_counts();
+    let updated = entry.clone();
+
+    write_notes(app, &entries).await?;
+    Ok(updated)
+}
+
+/// Appends the secondary note to the primary one, unions their tags and moves the secondary
+/// to the trash, so the merge can be undone by restoring it.
+pub async fn merge_notes(app: &AppHandle, primary_id: &str, secondary_id: &str) -> Result<NoteEntry> {
+    if primary_id == secondary_id {
+        return Err(anyhow!("Cannot merge a note with itself"));
     }
+    let mut entries = read_notes(app).await?;
+    let find = |id: &str| {
+        entries
+            .iter()
+            .position(|entry| entry.id == id && !entry.is_trashed())
+            .ok_or_else(|| CommandError::not_found(format!("Note {id} not found")))
+    };
+    let primary_index = find(primary_id)?;
+    let secondary_index = find(secondary_id)?;
+    let now = Utc::now().to_rfc3339();
+
+    let secondary = &mut entries[secondary_index];
+    secondary.deleted_at = Some(now.clone());
+    let secondary_description = secondary.description.trim().to_string();
+    let secondary_tags = secondary.tags.clone();
+
+    let primary = &mut entries[primary_index];
+    let existing = primary.description.trim_end();
+    primary.description = match (existing.is_empty(), secondary_description.is_empty()) {
+        (_, true) => existing.to_string(),
+        (true, false) => secondary_description,
+        (false, false) => format!("{existing}{NOTE_MERGE_SEPARATOR}{secondary_description}"),
+    };
+    let mut tags = primary.tags.clone();
+    tags.extend(secondary_tags);
+    primary.tags = normalize_tags(&tags);
+    primary.updated_at = now;
+    primary.refresh_counts();
+    let merged = primary.clone();
+
+    write_notes(app, &entries).await?;
+    Ok(merged)
+}
+
+pub async fn set_note_pinned(app: &AppHandle, id: &str, pinned: bool) -> Result<NoteEntry> {
+    let mut entries = read_notes(app).await?;
+    let entry = entries
+        .iter_mut()
+        .find(|entry| entry.id == id && !entry.is_trashed())
+        .ok_or_else(|| CommandError::not_found("Note not found"))?;
+    entry.pinned = pinned;
+    let updated = entry.clone();
+    write_notes(app, &entries).await?;
+    Ok(updated)
+}
+
+pub async fn set_note_archived(app: &AppHandle, id: &str, archived: bool) -> Result<NoteEntry> {
+    let mut entries = read_notes(app).await?;
+    let entry = entries
+        .iter_mut()
+        .find(|entry| entry.id == id && !entry.is_trashed())
+        .ok_or_else(|| CommandError::not_found("Note not found"))?;
+    entry.archived = archived;
+    let updated = entry.clone();
+    write_notes(app, &entries).await?;
+    Ok(updated)
+}
+
+/// Moves a note to the trash; it stays in the file until the trash is emptied.
+pub async fn delete_note(app: &AppHandle, payload: NoteDeleteInput) -> Result<()> {
+    let mut entries = read_notes(app).await?;
+    let entry = entries
+        .iter_mut()
+        .find(|entry| entry.id == payload.id && !entry.is_trashed())
+        .ok_or_else(|| CommandError::not_found("Note not found"))?;
+    entry.deleted_at = Some(Utc::now().to_rfc3339());
     write_notes(app, &entries).await?;
     Ok(())
 }
 
 pub async fn bulk_delete_notes(app: &AppHandle, payload: NoteBulkDeleteInput) -> Result<NoteBulkDeleteResponse> {
     if payload.ids.is_empty() {
-        return Err(anyhow!("Ids cannot be empty"));
+        return Err(CommandError::invalid_input("Ids cannot be empty").into());
+    }
+    let mut entries = read_notes(app).await?;
+    let now = Utc::now().to_rfc3339();
+    let mut deleted_count = 0;
+    for entry in entries
+        .iter_mut()
+        .filter(|entry| !entry.is_trashed() && payload.ids.contains(&entry.id))
+    {
+        entry.deleted_at = Some(now.clone());
+        deleted_count += 1;
+    }
+    if deleted_count > 0 {
+        backup_notes(app).await?;
     }
-    let mut entries = read_notes(app).await.unwrap_or_default();
-    let before = entries.len();
-    entries.retain(|entry| !payload.ids.contains(&entry.id));
-    let deleted_count = before.saturating_sub(entries.len());
     write_notes(app, &entries).await?;
     Ok(NoteBulkDeleteResponse {deleted_count})
 }
+
+/// Trashed notes, most recently deleted first.
+pub async fn list_trash(app: &AppHandle, page: u32, page_size: u32) -> Result<NoteListResponse> {
+    let mut entries: Vec<NoteEntry> = read_notes(app)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(NoteEntry::is_trashed)
+        .collect();
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(paginate(entries, page, page_size))
+}
+
+pub async fn restore_note(app: &AppHandle, id: &str) -> Result<NoteEntry> {
+    let mut entries = read_notes(app).await?;
+    let entry = entries
+        .iter_mut()
+        .find(|entry| entry.id == id && entry.is_trashed())
+        .ok_or_else(|| CommandError::not_found("Note not found in trash"))?;
+    entry.deleted_at = None;
+    let restored = entry.clone();
+    write_notes(app, &entries).await?;
+    Ok(restored)
+}
+
+/// Permanently removes every trashed note; returns how many were removed.
+pub async fn empty_trash(app: &AppHandle) -> Result<usize> {
+    let mut entries = read_notes(app).await?;
+    let before = entries.len();
+    entries.retain(|entry| !entry.is_trashed());
+    let removed = before - entries.len();
+    if removed > 0 {
+        backup_notes(app).await?;
+        write_notes(app, &entries).await?;
+    }
+    Ok(removed)
+}
+
+fn api_token(config: &AppConfig) -> Result<String> {
+    let token = if config.auth.access_token.trim().is_empty() {
+        config.auth.access.trim()
+    } else {
+        config.auth.access_token.trim()
+    };
+    if token.is_empty() {
+        return Err(CommandError::new(
+            error::AUTH_MISSING,
+            "Authentication is required to migrate notes to or from the API",
+        )
+        .into());
+    }
+    Ok(token.to_string())
+}
+
+fn remote_string(value: &serde_json::Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|field| field.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn fetch_remote_notes(config: &AppConfig) -> Result<Vec<serde_json::Value>> {
+    let token = api_token(config)?;
+    let client = reqwest::Client::new();
+    let url = format!("{}/{NOTES_API_PATH}", oauth::resolve_api_base(config));
+    let mut notes = Vec::new();
+    let mut page = 1u32;
+    loop {
+        let response = client
+            .get(&url)
+            .bearer_auth(&token)
+            .query(&[("page", page), ("page_size", NOTES_API_PAGE_SIZE)])
+            .send()
+            .await
+            .context("fetch notes from API")?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Failed to fetch notes from API: HTTP {status}"));
+        }
+        let body: serde_json::Value = response.json().await.context("parse notes page")?;
+        if let Some(results) = body.get("results").and_then(|value| value.as_array()) {
+            notes.extend(results.iter().cloned());
+        }
+        if body.get("next").is_none_or(|next| next.is_null()) {
+            break;
+        }
+        page += 1;
+    }
+    Ok(notes)
+}
+
+async fn push_remote_note(client: &reqwest::Client, config: &AppConfig, token: &str, entry: &NoteEntry) -> Result<()> {
+    let url = format!("{}/{NOTES_API_PATH}", oauth::resolve_api_base(config));
+    let response = client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "title": entry.title,
+            "description": entry.description,
+            "x_username": entry.x_username,
+        }))
+        .send()
+        .await
+        .context("create note via API")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("Failed to create note \"{}\" via API: HTTP {status}", entry.title));
+    }
+    Ok(())
+}
+
+/// Copies all notes from the `from` backend into the `to` backend and returns the
+/// number of copied notes. Source notes are left untouched.
+pub async fn migrate_notes(app: &AppHandle, config: &AppConfig, from: &str, to: &str) -> Result<usize> {
+    match (from, to) {
+        ("local", "api") => {
+            let token = api_token(config)?;
+            let client = reqwest::Client::new();
+            let entries: Vec<NoteEntry> = read_notes(app)
+                .await?
+                .into_iter()
+                .filter(|entry| !entry.is_trashed())
+                .collect();
+            // Старые заметки идут первыми, чтобы на сервере сохранился порядок
+            for entry in entries.iter().rev() {
+                push_remote_note(&client, config, &token, entry).await?;
+            }
+            Ok(entries.len())
+        }
+        ("api", "local") => {
+            let remote = fetch_remote_notes(config).await?;
+            let mut entries = read_notes(app).await?;
+            let now = Utc::now().to_rfc3339();
+            let imported: Vec<NoteEntry> = remote
+                .iter()
+                .filter(|note| !remote_string(note, "title").trim().is_empty())
+                .map(|note| {
+                    let created_at = remote_string(note, "created_at");
+                    let updated_at = remote_string(note, "updated_at");
+                    NoteEntry {
+                        id: Uuid::new_v4().to_string(),
+                        profile: LOCAL_PROFILE_ID.to_string(),
+                        title: remote_string(note, "title").trim().to_string(),
+                        description: remote_string(note, "description"),
+                        x_username: remote_string(note, "x_username"),
+                        tags: Vec::new(),
+                        pinned: false,
+                        archived: false,
+                        deleted_at: None,
+                        // Пересчитываются при следующем чтении файла
+                        word_count: 0,
+                        char_count: 0,
+                        created_at: if created_at.is_empty() { now.clone() } else { created_at },
+                        updated_at: if updated_at.is_empty() { now.clone() } else { updated_at },
+                    }
+                })
+                .collect();
+            let count = imported.len();
+            entries.splice(0..0, imported);
+            write_notes(app, &entries).await?;
+            Ok(count)
+        }
+        _ => Ok(0),
+    }
+}
+
+/// Salvages valid entries from a damaged notes file, keeping a backup of the original.
+pub async fn repair_notes(app: &AppHandle) -> Result<RepairReport> {
+    let path = notes_file_path(app).await?;
+    let (_, report) = repair::repair_json_array::<NoteEntry>(&path).await?;
+    Ok(report)
+}
+
+/// Writes all notes into one Markdown file, newest first; returns how many were written.
+pub async fn export_notes_markdown(app: &AppHandle, dest_path: &Path) -> Result<usize> {
+    let parent = dest_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| anyhow!("Destination {} has no parent directory", dest_path.display()))?;
+    if !fs::metadata(parent).await.map(|meta| meta.is_dir()).unwrap_or(false) {
+        return Err(anyhow!("Directory {} does not exist", parent.display()));
+    }
+
+    let mut entries = read_active_notes(app).await;
+    NoteSort {
+        key: NoteSortKey::Created,
+        descending: true,
+    }
+    .apply(&mut entries);
+
+    let mut document = String::new();
+    for entry in &entries {
+        if !document.is_empty() {
+            document.push('\n');
+        }
+        document.push_str(&format!("## {}\n\n", entry.title.trim()));
+        let body = entry.description.trim();
+        if !body.is_empty() {
+            document.push_str(body);
+            document.push_str("\n\n");
+        }
+        let mut footer = format!("Created {} · Updated {}", entry.created_at, entry.updated_at);
+        let username = entry.x_username.trim().trim_start_matches('@');
+        if !username.is_empty() {
+            footer.push_str(&format!(" · @{username}"));
+        }
+        document.push_str(&format!("_{footer}_\n"));
+    }
+
+    fs::write(dest_path, document)
+        .await
+        .with_context(|| format!("write notes export to {}", dest_path.display()))?;
+    Ok(entries.len())
+}
+
+/// Every distinct tag with the number of notes carrying it, most used first.
+pub async fn list_tags(app: &AppHandle, include_archived: bool) -> Result<Vec<NoteTagCount>> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let entries = read_active_notes(app).await;
+    for entry in entries.iter().filter(|entry| include_archived || !entry.archived) {
+        for tag in normalize_tags(&entry.tags) {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<NoteTagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| NoteTagCount { tag, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(tags)
+}
+
+/// Number of notes outside the trash, served from cache while the file is unchanged.
+pub async fn count_notes(app: &AppHandle) -> Result<usize> {
+    let path = notes_file_path(app).await?;
+    COUNT_CACHE
+        .count_where::<NoteTrashMarker>(&path, |marker| marker.deleted_at.is_none())
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, title: &str, created_at: &str, updated_at: &str) -> NoteEntry {
+        NoteEntry {
+            id: id.to_string(),
+            profile: String::new(),
+            title: title.to_string(),
+            description: String::new(),
+            x_username: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            archived: false,
+            created_at: created_at.to_string(),
+            updated_at: updated_at.to_string(),
+            deleted_at: None,
+            word_count: 0,
+            char_count: 0,
+        }
+    }
+
+    /// Created, updated and title orders all differ, so every key gives its own ordering.
+    fn fixture() -> Vec<NoteEntry> {
+        vec![
+            note("a", "beta", "2024-01-02T00:00:00Z", "2024-03-01T00:00:00Z"),
+            note("b", "Alpha", "2024-01-03T00:00:00Z", "2024-02-01T00:00:00Z"),
+            // Другое смещение: 2024-01-01T10:00Z, самая ранняя заметка
+            note("c", "gamma", "2024-01-01T12:00:00+02:00", "2024-04-01T00:00:00Z"),
+        ]
+    }
+
+    fn sorted_ids(sort_by: &str, direction: Option<&str>) -> Vec<String> {
+        let sort = NoteSort::parse(Some(sort_by), direction)
+            .expect("valid sort")
+            .expect("sort requested");
+        let mut entries = fixture();
+        sort.apply(&mut entries);
+        entries.into_iter().map(|entry| entry.id).collect()
+    }
+
+    #[test]
+    fn sorts_by_created() {
+        assert_eq!(sorted_ids("created", Some("asc")), ["c", "a", "b"]);
+        assert_eq!(sorted_ids("created", Some("desc")), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn sorts_by_updated() {
+        assert_eq!(sorted_ids("updated", Some("asc")), ["b", "a", "c"]);
+        assert_eq!(sorted_ids("updated", Some("desc")), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn sorts_by_title_case_insensitively() {
+        assert_eq!(sorted_ids("title", Some("asc")), ["b", "a", "c"]);
+        assert_eq!(sorted_ids("title", Some("desc")), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn default_direction_depends_on_key() {
+        assert_eq!(sorted_ids("created", None), ["b", "a", "c"]);
+        assert_eq!(sorted_ids("updated", None), ["c", "a", "b"]);
+        assert_eq!(sorted_ids("title", None), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn no_sort_keeps_stored_order() {
+        assert!(NoteSort::parse(None, None).unwrap().is_none());
+        assert!(NoteSort::parse(Some("  "), Some("asc")).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_key_and_direction() {
+        assert!(NoteSort::parse(Some("size"), None).is_err());
+        assert!(NoteSort::parse(Some("title"), Some("up")).is_err());
+    }
+
+    #[test]
+    fn duplicate_title_is_rejected_with_code() {
+        let entries = [note("a", "Groceries", "2024-01-01T00:00:00Z", "2024-01-01T00:00:00Z")];
+        let error = check_unique_title(&entries, "groceries").unwrap_err();
+        let command_error = error.downcast_ref::<CommandError>().unwrap();
+        assert_eq!(command_error.code, error::DUPLICATE_TITLE);
+        assert!(check_unique_title(&entries, "Errands").is_ok());
+    }
+
+    #[test]
+    fn trashed_note_title_can_be_reused() {
+        let mut trashed = note("a", "Groceries", "2024-01-01T00:00:00Z", "2024-01-01T00:00:00Z");
+        trashed.deleted_at = Some("2024-01-02T00:00:00Z".to_string());
+        assert!(check_unique_title(&[trashed], "Groceries").is_ok());
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
 
 use crate::constants::{BACKEND_DOMAIN_RU, DEFAULT_BACKEND_DOMAIN};
-use crate::oauth_server;
+use crate::{diagnostics, oauth_server};
+use crate::types::AppConfig;
 
 fn normalize_base(input: Option<String>) -> Option<String> {
     let raw = input?.trim().to_string();
@@ -29,6 +30,14 @@ fn resolve_site_base_by_domain(backend_domain: Option<&str>) -> String {
     format!("https://{resolved_domain}")
 }
 
+/// Base URL of the backend REST API for the configured domain.
+pub fn resolve_api_base(config: &AppConfig) -> String {
+    format!(
+        "{}/api/v1",
+        resolve_site_base_by_domain(Some(config.backend_domain.as_str()))
+    )
+}
+
 /// Проверяет, запущено ли приложение с правами администратора
 #[cfg(target_os = "windows")]
 pub fn is_running_as_admin() -> bool {
@@ -65,9 +74,19 @@ pub fn is_running_as_admin() -> bool {
     false
 }
 
+/// HTTP callback нужен от администратора (deep link блокирует UIPI)
+/// или когда пользователь сам выбрал его из-за сломанных deep link.
+pub fn should_use_http_callback(config: &AppConfig) -> bool {
+    is_running_as_admin() || config.prefer_http_oauth
+}
+
 /// Строит URL для OAuth с учётом режима работы.
-/// При запуске от администратора использует HTTP callback вместо deep link.
-pub fn build_oauth_start_url(provider: &str, backend_domain: Option<&str>) -> Result<String> {
+/// При `use_http_callback` использует HTTP callback вместо deep link.
+pub fn build_oauth_start_url(
+    provider: &str,
+    backend_domain: Option<&str>,
+    use_http_callback: bool,
+) -> Result<String> {
     let provider_lower = provider.to_lowercase();
     let key = format!("OAUTH_PROVIDER_URL_{}", provider_lower.to_uppercase());
     if let Some(override_url) = env(&key) {
@@ -83,16 +102,46 @@ pub fn build_oauth_start_url(provider: &str, backend_domain: Option<&str>) -> Re
     let mut url = url::Url::parse(&base)?;
     url.set_path(&format!("/auth/oauth/{}/start", provider_lower));
     
-    // Если запущено от администратора, используем HTTP callback
-    // потому что deep link не работает из-за UIPI
-    if is_running_as_admin() {
+    // При запуске от администратора deep link не работает из-за UIPI,
+    // а у части пользователей он сломан антивирусом - тогда используем HTTP callback
+    if use_http_callback {
         let callback_url = oauth_server::get_callback_url();
         let encoded_callback = urlencoding::encode(&callback_url);
         url.set_query(Some(&format!("app_auth=winky&redirect_uri={}", encoded_callback)));
-        crate::logging::log_message(&format!("[OAuth] Running as admin, using HTTP callback: {}", callback_url));
+        crate::logging::log_message(&format!("[OAuth] Using HTTP callback: {}", callback_url));
     } else {
         url.set_query(Some("app_auth=winky"));
     }
     
     Ok(url.to_string())
 }
+
+/// Скрывает секреты в OAuth URL: пароль в userinfo и значения query-параметров
+/// вроде `token`, `secret`, `client_secret` или `api_key`.
+pub fn redact_oauth_url(raw: &str) -> String {
+    let Ok(mut url) = url::Url::parse(raw) else {
+        return raw.to_string();
+    };
+    if url.password().is_some() {
+        let _ = url.set_password(Some(diagnostics::REDACTED));
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    let has_secret = pairs.iter().any(|(key, _)| is_secret_param(key));
+    if has_secret {
+        url.query_pairs_mut().clear().extend_pairs(pairs.iter().map(|(key, value)| {
+            if is_secret_param(key) {
+                (key.as_str(), diagnostics::REDACTED)
+            } else {
+                (key.as_str(), value.as_str())
+            }
+        }));
+    }
+    url.to_string()
+}
+
+fn is_secret_param(key: &str) -> bool {
+    diagnostics::is_secret_key(key) || key.to_ascii_lowercase().ends_with("key")
+}
@@ -2,6 +2,7 @@
 //! Используется как fallback когда deep link не работает (например, при запуске от администратора).
 
 use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -14,6 +15,23 @@ use crate::types::{AuthDeepLinkPayload, AuthTokensPayload};
 /// Порт для локального OAuth сервера
 const OAUTH_SERVER_PORT: u16 = 17842;
 
+/// Подробные логи (запросы, параметры, payload) пишутся только при включённой отладке
+static DEBUG_LOGGING: AtomicBool = AtomicBool::new(false);
+
+pub fn set_debug_logging(enable: bool) {
+    DEBUG_LOGGING.store(enable, Ordering::Relaxed);
+    crate::logging::log_message(&format!(
+        "[OAuthServer] Verbose logging {}",
+        if enable { "enabled" } else { "disabled" }
+    ));
+}
+
+fn debug_log(message: &str) {
+    if DEBUG_LOGGING.load(Ordering::Relaxed) {
+        crate::logging::log_message(message);
+    }
+}
+
 /// HTML страница успешной авторизации
 const SUCCESS_HTML: &str = r#"<!DOCTYPE html>
 <html>
@@ -173,7 +191,7 @@ pub async fn start_oauth_server(
 ) -> anyhow::Result<()> {
     let mut running = state.running.lock().await;
     if *running {
-        crate::logging::log_message("[OAuthServer] Server already running");
+        debug_log("[OAuthServer] Server already running");
         return Ok(());
     }
     *running = true;
@@ -191,20 +209,20 @@ pub async fn start_oauth_server(
     let queue_clone = queue.clone();
     
     tokio::spawn(async move {
-        crate::logging::log_message("[OAuthServer] Server task started");
+        debug_log("[OAuthServer] Server task started");
         loop {
             // Проверяем флаг перед accept
             {
                 let running = state_clone.running.lock().await;
                 if !*running {
-                    crate::logging::log_message("[OAuthServer] Server stopped, exiting loop");
+                    debug_log("[OAuthServer] Server stopped, exiting loop");
                     break;
                 }
             }
 
             match listener.accept().await {
                 Ok((mut stream, addr)) => {
-                    crate::logging::log_message(&format!("[OAuthServer] New connection from {}", addr));
+                    debug_log(&format!("[OAuthServer] New connection from {}", addr));
                     let app = app_clone.clone();
                     let queue = queue_clone.clone();
                     
@@ -213,28 +231,28 @@ pub async fn start_oauth_server(
                         match stream.read(&mut buffer).await {
                             Ok(n) => {
                                 let request = String::from_utf8_lossy(&buffer[..n]);
-                                crate::logging::log_message(&format!("[OAuthServer] Received request ({} bytes)", n));
+                                debug_log(&format!("[OAuthServer] Received request ({} bytes)", n));
                                 for line in request.lines().take(5) {
-                                    crate::logging::log_message(&format!("[OAuthServer]   {}", line));
+                                    debug_log(&format!("[OAuthServer]   {}", line));
                                 }
                                 
                                 // Парсим HTTP запрос
                                 if let Some(path) = parse_request_path(&request) {
-                                    crate::logging::log_message(&format!("[OAuthServer] Parsed path: {}", path));
+                                    debug_log(&format!("[OAuthServer] Parsed path: {}", path));
                                     if path.starts_with("/oauth/callback") {
-                                        crate::logging::log_message("[OAuthServer] Processing OAuth callback");
+                                        debug_log("[OAuthServer] Processing OAuth callback");
                                         let (html, payload) = handle_oauth_callback(&path);
                                         
                                         // Отправляем payload в приложение
                                         if let Some(payload) = payload.clone() {
-                                            crate::logging::log_message(&format!("[OAuthServer] Enqueueing payload: {:?}", payload));
+                                            debug_log(&format!("[OAuthServer] Enqueueing payload: {:?}", payload));
                                             queue.enqueue(payload.clone()).await;
                                             match app.emit("auth:deep-link", payload) {
-                                                Ok(_) => crate::logging::log_message("[OAuthServer] Event emitted successfully"),
+                                                Ok(_) => debug_log("[OAuthServer] Event emitted successfully"),
                                                 Err(e) => crate::logging::log_message(&format!("[OAuthServer] Failed to emit event: {}", e)),
                                             }
                                         } else {
-                                            crate::logging::log_message("[OAuthServer] No payload extracted from callback");
+                                            debug_log("[OAuthServer] No payload extracted from callback");
                                         }
                                         
                                         // Отправляем HTTP ответ
@@ -246,16 +264,16 @@ pub async fn start_oauth_server(
                                         if let Err(e) = stream.write_all(response.as_bytes()).await {
                                             crate::logging::log_message(&format!("[OAuthServer] Failed to write response: {}", e));
                                         } else {
-                                            crate::logging::log_message("[OAuthServer] Response sent successfully");
+                                            debug_log("[OAuthServer] Response sent successfully");
                                         }
                                     } else {
-                                        crate::logging::log_message("[OAuthServer] Path not /oauth/callback, returning 404");
+                                        debug_log("[OAuthServer] Path not /oauth/callback, returning 404");
                                         // 404 для других путей
                                         let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
                                         let _ = stream.write_all(response.as_bytes()).await;
                                     }
                                 } else {
-                                    crate::logging::log_message("[OAuthServer] Failed to parse request path");
+                                    debug_log("[OAuthServer] Failed to parse request path");
                                 }
                             }
                             Err(e) => {
@@ -270,7 +288,7 @@ pub async fn start_oauth_server(
                 }
             }
         }
-        crate::logging::log_message("[OAuthServer] Server task ended");
+        debug_log("[OAuthServer] Server task ended");
     });
 
     Ok(())
@@ -296,12 +314,12 @@ fn parse_request_path(request: &str) -> Option<String> {
 
 /// Обрабатывает OAuth callback и возвращает HTML и payload
 fn handle_oauth_callback(path: &str) -> (String, Option<AuthDeepLinkPayload>) {
-    crate::logging::log_message(&format!("[OAuthServer] Handling callback, path: {}", path));
+    debug_log(&format!("[OAuthServer] Handling callback, path: {}", path));
     
     // Парсим query параметры
     let query_start = path.find('?').map(|i| i + 1).unwrap_or(path.len());
     let query = &path[query_start..];
-    crate::logging::log_message(&format!("[OAuthServer] Query string: {}", query));
+    debug_log(&format!("[OAuthServer] Query string: {}", query));
     
     let mut payload_str: Option<String> = None;
     
@@ -309,12 +327,12 @@ fn handle_oauth_callback(path: &str) -> (String, Option<AuthDeepLinkPayload>) {
     for param in query.split('&') {
         let mut parts = param.splitn(2, '=');
         if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
-            crate::logging::log_message(&format!("[OAuthServer] Query param: {} = {}", key, value));
+            debug_log(&format!("[OAuthServer] Query param: {} = {}", key, value));
             if key == "payload" {
                 match urlencoding::decode(value) {
                     Ok(decoded) => {
                         payload_str = Some(decoded.into_owned());
-                        crate::logging::log_message("[OAuthServer] Found payload parameter");
+                        debug_log("[OAuthServer] Found payload parameter");
                     }
                     Err(e) => {
                         crate::logging::log_message(&format!("[OAuthServer] Failed to decode payload: {}", e));
@@ -325,10 +343,10 @@ fn handle_oauth_callback(path: &str) -> (String, Option<AuthDeepLinkPayload>) {
     }
     
     if let Some(payload_json) = payload_str {
-        crate::logging::log_message(&format!("[OAuthServer] Payload JSON: {}", payload_json));
+        debug_log(&format!("[OAuthServer] Payload JSON: {}", payload_json));
         match parse_payload(&payload_json) {
             Ok(payload) => {
-                crate::logging::log_message("[OAuthServer] Payload parsed successfully");
+                debug_log("[OAuthServer] Payload parsed successfully");
                 let html = SUCCESS_HTML.to_string();
                 (html, Some(payload))
             }
@@ -339,12 +357,12 @@ fn handle_oauth_callback(path: &str) -> (String, Option<AuthDeepLinkPayload>) {
             }
         }
     } else {
-        crate::logging::log_message("[OAuthServer] No payload parameter found in query string");
+        debug_log("[OAuthServer] No payload parameter found in query string");
         // Пробуем распарсить весь path как URL и извлечь данные оттуда
         if let Ok(url) = url::Url::parse(&format!("http://127.0.0.1{}", path)) {
-            crate::logging::log_message("[OAuthServer] Trying to parse as URL");
+            debug_log("[OAuthServer] Trying to parse as URL");
             for (key, value) in url.query_pairs() {
-                crate::logging::log_message(&format!("[OAuthServer] URL param: {} = {}", key, value));
+                debug_log(&format!("[OAuthServer] URL param: {} = {}", key, value));
                 if key == "payload" {
                     payload_str = Some(value.into_owned());
                     break;
@@ -354,7 +372,7 @@ fn handle_oauth_callback(path: &str) -> (String, Option<AuthDeepLinkPayload>) {
             if let Some(payload_json) = payload_str {
                 match parse_payload(&payload_json) {
                     Ok(payload) => {
-                        crate::logging::log_message("[OAuthServer] Payload parsed from URL successfully");
+                        debug_log("[OAuthServer] Payload parsed from URL successfully");
                         let html = SUCCESS_HTML.to_string();
                         return (html, Some(payload));
                     }
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use tauri::{AppHandle, Emitter};
 
+use crate::llm_streams;
+
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -243,8 +245,16 @@ pub async fn chat_completions_stream(
     let mut full_text = String::new();
     let mut buffer = String::new();
     let mut stream = response.bytes_stream();
-
-    while let Some(chunk) = stream.next().await {
+    let registration = llm_streams::register(stream_id, "ollama:stream");
+
+    loop {
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = registration.cancelled() => return Ok(full_text),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
         let chunk = chunk.map_err(|e| anyhow!("Ollama stream error: {}", e))?;
         let text = String::from_utf8_lossy(&chunk);
         buffer.push_str(&text);
@@ -6,6 +6,9 @@ use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
+use crate::{llm_streams, rate_limit};
+use crate::error::CommandError;
+
 const OPENAI_CHAT_COMPLETIONS_URL: &str = "https://api.openai.com/v1/chat/completions";
 
 pub async fn chat_completions(api_key: &str, body: Value) -> Result<Value> {
@@ -14,6 +17,7 @@ pub async fn chat_completions(api_key: &str, body: Value) -> Result<Value> {
         return Err(anyhow!("OpenAI API key is missing."));
     }
 
+    rate_limit::acquire("openai").await?;
     let client = reqwest::Client::new();
     let response = client
         .post(OPENAI_CHAT_COMPLETIONS_URL)
@@ -50,10 +54,12 @@ pub async fn chat_completions_stream(
     if let Value::Object(map) = &mut body {
         map.insert("stream".into(), Value::Bool(true));
     } else {
-        return Err(anyhow!("Invalid OpenAI request body."));
+        return Err(CommandError::invalid_input("Invalid OpenAI request body.").into());
     }
 
+    rate_limit::acquire("openai").await?;
     let client = reqwest::Client::new();
+    let mut timing = llm_streams::StreamTiming::start();
     let response = client
         .post(OPENAI_CHAT_COMPLETIONS_URL)
         .header(AUTHORIZATION, format!("Bearer {}", token))
@@ -73,8 +79,16 @@ pub async fn chat_completions_stream(
     let mut full_text = String::new();
     let mut buffer = String::new();
     let mut stream = response.bytes_stream();
+    let registration = llm_streams::register(stream_id, "openai:stream");
 
-    while let Some(chunk) = stream.next().await {
+    loop {
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = registration.cancelled() => return Ok(full_text),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
         let chunk = chunk.map_err(|e| anyhow!("OpenAI stream error: {}", e))?;
         let text = String::from_utf8_lossy(&chunk);
         buffer.push_str(&text);
@@ -91,10 +105,7 @@ pub async fn chat_completions_stream(
             }
             let data = line.trim_start_matches("data:").trim();
             if data == "[DONE]" {
-                let _ = app.emit(
-                    "openai:stream",
-                    serde_json::json!({"streamId": stream_id, "done": true}),
-                );
+                let _ = app.emit("openai:stream", timing.done_payload(stream_id));
                 return Ok(full_text);
             }
             let parsed: Value = match serde_json::from_str(data) {
@@ -108,6 +119,7 @@ pub async fn chat_completions_stream(
                 .and_then(|value| value.get("content"))
                 .and_then(|value| value.as_str());
             if let Some(delta) = delta {
+                timing.mark_delta();
                 full_text.push_str(delta);
                 let _ = app.emit(
                     "openai:stream",
@@ -117,9 +129,6 @@ pub async fn chat_completions_stream(
         }
     }
 
-    let _ = app.emit(
-        "openai:stream",
-        serde_json::json!({"streamId": stream_id, "done": true}),
-    );
+    let _ = app.emit("openai:stream", timing.done_payload(stream_id));
     Ok(full_text)
 }
@@ -0,0 +1,163 @@
+//! Lightweight process inspection via platform tools (netstat / lsof / ps / PowerShell).
+//!
+//! `sysinfo` is deliberately not used: the port lookup and process listing here already rely on
+//! these tools, and a single on-demand sample of one PID does not justify a new dependency.
+
+use serde::Serialize;
+use tokio::process::Command;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+#[cfg(windows)]
+const CPU_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ResourceUsage {
+    pub pid: u32,
+    pub cpu_percent: f32,
+    pub memory_mb: f64,
+}
+
+fn command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+    #[cfg(windows)]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+pub(crate) async fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = command(program).args(args).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// PID of the process listening on the given local TCP port.
+#[cfg(windows)]
+pub async fn find_pid_by_port(port: u16) -> Option<u32> {
+    let output = run("netstat", &["-ano", "-p", "TCP"]).await?;
+    let suffix = format!(":{port}");
+    output.lines().find_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 5 || !columns[1].ends_with(&suffix) || columns[3] != "LISTENING" {
+            return None;
+        }
+        columns[4].parse().ok()
+    })
+}
+
+#[cfg(not(windows))]
+pub async fn find_pid_by_port(port: u16) -> Option<u32> {
+    let filter = format!("-iTCP:{port}");
+    let output = run("lsof", &["-nP", &filter, "-sTCP:LISTEN", "-t"]).await?;
+    output.lines().find_map(|line| line.trim().parse().ok())
+}
+
+#[cfg(windows)]
+async fn sample_process(pid: u32) -> Option<(f64, f64)> {
+    let script = format!(
+        "$p = Get-Process -Id {pid} -ErrorAction Stop; \"$($p.WorkingSet64) $($p.TotalProcessorTime.TotalSeconds)\""
+    );
+    let output = run("powershell", &["-NoProfile", "-Command", &script]).await?;
+    let mut parts = output.split_whitespace();
+    let memory_bytes: f64 = parts.next()?.parse().ok()?;
+    let cpu_seconds: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+    Some((memory_bytes, cpu_seconds))
+}
+
+/// CPU is measured over a short sampling interval and normalised to all cores.
+#[cfg(windows)]
+pub async fn resource_usage(pid: u32) -> Option<ResourceUsage> {
+    let (_, cpu_before) = sample_process(pid).await?;
+    tokio::time::sleep(CPU_SAMPLE_INTERVAL).await;
+    let (memory_bytes, cpu_after) = sample_process(pid).await?;
+    let cores = std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1) as f64;
+    let cpu_percent = ((cpu_after - cpu_before) / CPU_SAMPLE_INTERVAL.as_secs_f64() / cores * 100.0).max(0.0);
+    Some(ResourceUsage {
+        pid,
+        cpu_percent: cpu_percent as f32,
+        memory_mb: memory_bytes / (1024.0 * 1024.0),
+    })
+}
+
+/// On Unix `ps` reports CPU as the average over the process lifetime.
+#[cfg(not(windows))]
+pub async fn resource_usage(pid: u32) -> Option<ResourceUsage> {
+    let pid_arg = pid.to_string();
+    let output = run("ps", &["-o", "rss=", "-o", "%cpu=", "-p", &pid_arg]).await?;
+    let mut parts = output.split_whitespace();
+    let rss_kb: f64 = parts.next()?.parse().ok()?;
+    let cpu_percent: f32 = parts.next()?.replace(',', ".").parse().ok()?;
+    Some(ResourceUsage {
+        pid,
+        cpu_percent,
+        memory_mb: rss_kb / 1024.0,
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub command_line: String,
+}
+
+/// Processes whose command line mentions `needle` (case-insensitive), excluding this app.
+#[cfg(windows)]
+pub async fn list_processes_matching(needle: &str) -> Vec<ProcessEntry> {
+    let script = "Get-CimInstance Win32_Process | Where-Object { $_.CommandLine } | \
+                  ForEach-Object { \"$($_.ProcessId)`t$($_.CommandLine)\" }";
+    let output = run("powershell", &["-NoProfile", "-Command", script])
+        .await
+        .unwrap_or_default();
+    filter_processes(&output, '\t', needle)
+}
+
+#[cfg(not(windows))]
+pub async fn list_processes_matching(needle: &str) -> Vec<ProcessEntry> {
+    let output = run("ps", &["-eo", "pid=,args="]).await.unwrap_or_default();
+    filter_processes(&output, ' ', needle)
+}
+
+fn filter_processes(output: &str, separator: char, needle: &str) -> Vec<ProcessEntry> {
+    let needle = needle.to_lowercase();
+    let own_pid = std::process::id();
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, command_line) = line.trim().split_once(separator)?;
+            let pid: u32 = pid.trim().parse().ok()?;
+            let command_line = command_line.trim().to_string();
+            if pid == own_pid || !command_line.to_lowercase().contains(&needle) {
+                return None;
+            }
+            Some(ProcessEntry { pid, command_line })
+        })
+        .collect()
+}
+
+/// Terminates the process. On Windows its child processes go with it (`taskkill /T`);
+/// on Unix only the process itself gets SIGTERM, since its process group may also hold
+/// the server that is being kept alive.
+pub async fn kill_process(pid: u32) -> anyhow::Result<()> {
+    let pid_arg = pid.to_string();
+    let status = if cfg!(windows) {
+        command("taskkill")
+            .args(["/PID", &pid_arg, "/T", "/F"])
+            .status()
+            .await?
+    } else {
+        command("kill").args(["-TERM", &pid_arg]).status().await?
+    };
+    if !status.success() {
+        return Err(anyhow::anyhow!("Failed to terminate process {pid} ({status})"));
+    }
+    Ok(())
+}
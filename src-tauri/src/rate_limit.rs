@@ -0,0 +1,96 @@
+//! Client-side token-bucket limiter for outgoing provider requests (requests per minute).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+
+use crate::error::{self, CommandError};
+use crate::logging;
+
+pub const PROVIDERS: [&str; 2] = ["openai", "gemini"];
+/// Request waits in the queue at most this long, otherwise it is rejected.
+const MAX_QUEUE_WAIT: Duration = Duration::from_secs(30);
+
+struct Bucket {
+    per_minute: u32,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            tokens: f64::from(per_minute),
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        let capacity = f64::from(self.per_minute);
+        self.tokens = (self.tokens + elapsed * capacity / 60.0).min(capacity);
+        self.refilled_at = now;
+    }
+
+    /// Takes a token, or returns how long to wait for the next one.
+    fn try_take(&mut self) -> Option<Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        let missing = 1.0 - self.tokens;
+        Some(Duration::from_secs_f64(missing * 60.0 / f64::from(self.per_minute)))
+    }
+}
+
+static BUCKETS: Lazy<Mutex<HashMap<String, Bucket>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Applies limits from the config; a provider without a limit (or with 0) is unlimited.
+pub fn set_limits(limits: &HashMap<String, u32>) {
+    let mut buckets = BUCKETS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    buckets.retain(|provider, bucket| limits.get(provider) == Some(&bucket.per_minute));
+    for (provider, per_minute) in limits {
+        if *per_minute > 0 && !buckets.contains_key(provider) {
+            buckets.insert(provider.clone(), Bucket::new(*per_minute));
+        }
+    }
+}
+
+/// Waits for a free slot for `provider`; fails when the wait would exceed the queue limit.
+pub async fn acquire(provider: &str) -> Result<()> {
+    let mut waited = Duration::ZERO;
+    loop {
+        let wait = {
+            let mut buckets = BUCKETS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let Some(bucket) = buckets.get_mut(provider) else {
+                return Ok(());
+            };
+            match bucket.try_take() {
+                None => return Ok(()),
+                Some(wait) => (wait, bucket.per_minute),
+            }
+        };
+        let (wait, per_minute) = wait;
+        if waited + wait > MAX_QUEUE_WAIT {
+            let message = format!(
+                "Rate limit for {provider} ({per_minute} requests/min) reached; try again in {}s",
+                wait.as_secs().max(1)
+            );
+            return Err(CommandError::new(error::RATE_LIMITED, message).into());
+        }
+        if waited.is_zero() {
+            logging::log_message(&format!(
+                "[rate_limit] {provider} limit reached, queuing request for {}ms",
+                wait.as_millis()
+            ));
+        }
+        tokio::time::sleep(wait).await;
+        waited += wait;
+    }
+}
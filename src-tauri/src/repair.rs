@@ -0,0 +1,208 @@
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+const READ_ATTEMPTS: u32 = 4;
+/// Doubles after every failed attempt: 50, 100, 200 ms.
+const READ_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RepairReport {
+    pub was_corrupt: bool,
+    pub recovered: usize,
+    pub discarded: usize,
+    pub backup_path: Option<String>,
+}
+
+/// Reads a data file, retrying briefly while another process (antivirus, sync client,
+/// editor) holds a lock on it. `Ok(None)` means the file does not exist.
+pub async fn read_to_string_retrying(path: &Path) -> Result<Option<String>> {
+    let mut attempt = 1;
+    loop {
+        match fs::read_to_string(path).await {
+            Ok(contents) => return Ok(Some(contents)),
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            // Битый UTF-8 повтором не исправить
+            Err(error) if error.kind() != ErrorKind::InvalidData && attempt < READ_ATTEMPTS => {
+                eprintln!(
+                    "[storage] {} is busy (attempt {attempt}/{READ_ATTEMPTS}): {error}",
+                    path.display()
+                );
+                sleep(READ_RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+                attempt += 1;
+            }
+            Err(error) => {
+                return Err(anyhow::Error::new(error)
+                    .context(format!("{} is locked or unreadable", path.display())))
+            }
+        }
+    }
+}
+
+/// Writes `contents` to a temp file beside `path` and renames it over the target,
+/// so a crash mid-write leaves either the old file or the new one, never a truncated mix.
+pub async fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("data");
+    // Свой временный файл на каждую запись: параллельные записи одного файла не мешают друг другу
+    let temp_path = path.with_file_name(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        Uuid::new_v4().simple()
+    ));
+    let result = async {
+        let mut file = fs::File::create(&temp_path)
+            .await
+            .with_context(|| format!("create {}", temp_path.display()))?;
+        file.write_all(contents.as_bytes())
+            .await
+            .with_context(|| format!("write {}", temp_path.display()))?;
+        // Без fsync rename может оказаться на диске раньше данных
+        file.sync_all()
+            .await
+            .with_context(|| format!("sync {}", temp_path.display()))?;
+        drop(file);
+        fs::rename(&temp_path, path)
+            .await
+            .with_context(|| format!("replace {}", path.display()))
+    }
+    .await;
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path).await;
+    }
+    result
+}
+
+/// Splits a (possibly truncated or damaged) JSON array into the raw text of its
+/// top-level objects, so each element can be parsed independently.
+fn top_level_objects(contents: &str) -> Vec<&str> {
+    let mut objects = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, ch) in contents.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    start = Some(index);
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(begin) = start.take() {
+                        objects.push(&contents[begin..=index]);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+/// Best-effort recovery of a JSON array file: valid entries are kept, broken ones
+/// are dropped, and the original file is backed up next to it before rewriting.
+pub async fn repair_json_array<T>(path: &Path) -> Result<(Vec<T>, RepairReport)>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if !path.exists() {
+        return Ok((
+            Vec::new(),
+            RepairReport {
+                was_corrupt: false,
+                recovered: 0,
+                discarded: 0,
+                backup_path: None,
+            },
+        ));
+    }
+    let contents = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("read {}", path.display()))?;
+    if contents.trim().is_empty() {
+        return Ok((
+            Vec::new(),
+            RepairReport {
+                was_corrupt: false,
+                recovered: 0,
+                discarded: 0,
+                backup_path: None,
+            },
+        ));
+    }
+    if let Ok(entries) = serde_json::from_str::<Vec<T>>(&contents) {
+        let recovered = entries.len();
+        return Ok((
+            entries,
+            RepairReport {
+                was_corrupt: false,
+                recovered,
+                discarded: 0,
+                backup_path: None,
+            },
+        ));
+    }
+
+    let mut entries = Vec::new();
+    let mut discarded = 0;
+    for raw in top_level_objects(&contents) {
+        match serde_json::from_str::<T>(raw) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => discarded += 1,
+        }
+    }
+
+    let stem = path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("data");
+    let backup_path = path.with_file_name(format!(
+        "{stem}.corrupt-{}.json",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::copy(path, &backup_path)
+        .await
+        .with_context(|| format!("back up corrupt file to {}", backup_path.display()))?;
+    let serialized = serde_json::to_string_pretty(&entries).context("serialize repaired entries")?;
+    write_atomic(path, &serialized)
+        .await
+        .with_context(|| format!("write repaired file to {}", path.display()))?;
+
+    let recovered = entries.len();
+    Ok((
+        entries,
+        RepairReport {
+            was_corrupt: true,
+            recovered,
+            discarded,
+            backup_path: Some(backup_path.to_string_lossy().to_string()),
+        },
+    ))
+}
@@ -2,6 +2,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::{path::BaseDirectory, AppHandle, Manager};
 
+const SOUNDS_DIR_NAME: &str = "sounds";
+const SOUND_EXTENSIONS: [&str; 4] = ["wav", "mp3", "ogg", "flac"];
+const BENCHMARK_DIR_NAME: &str = "benchmark";
+const BENCHMARK_CLIP_NAME: &str = "sample.wav";
+
 fn to_platform_string(path: &Path) -> String {
     #[cfg(target_os = "windows")]
     {
@@ -21,49 +26,105 @@ fn normalize_existing_path(path: &Path) -> Option<String> {
     Some(to_platform_string(&resolved))
 }
 
-fn candidate_paths(app: &AppHandle, sound_name: &str) -> Vec<PathBuf> {
+fn candidate_dirs(app: &AppHandle, dir_name: &str) -> Vec<PathBuf> {
     let mut candidates: Vec<PathBuf> = Vec::new();
-    let relative = format!("sounds/{}", sound_name);
 
-    if let Ok(resolved) = app.path().resolve(&relative, BaseDirectory::Resource) {
+    if let Ok(resolved) = app.path().resolve(dir_name, BaseDirectory::Resource) {
         candidates.push(resolved);
     }
 
     if let Ok(resource_dir) = app.path().resource_dir() {
-        candidates.push(resource_dir.join("sounds").join(sound_name));
-        candidates.push(resource_dir.join("resources").join("sounds").join(sound_name));
+        candidates.push(resource_dir.join(dir_name));
+        candidates.push(resource_dir.join("resources").join(dir_name));
         if let Some(parent) = resource_dir.parent() {
-            candidates.push(parent.join("resources").join("sounds").join(sound_name));
-            candidates.push(parent.join("Resources").join("sounds").join(sound_name));
+            candidates.push(parent.join("resources").join(dir_name));
+            candidates.push(parent.join("Resources").join(dir_name));
         }
     }
 
     if let Ok(current_exe) = std::env::current_exe() {
         if let Some(exe_dir) = current_exe.parent() {
-            candidates.push(exe_dir.join("sounds").join(sound_name));
-            candidates.push(exe_dir.join("resources").join("sounds").join(sound_name));
+            candidates.push(exe_dir.join(dir_name));
+            candidates.push(exe_dir.join("resources").join(dir_name));
             if let Some(parent) = exe_dir.parent() {
-                candidates.push(parent.join("resources").join("sounds").join(sound_name));
-                candidates.push(parent.join("Resources").join("sounds").join(sound_name));
+                candidates.push(parent.join("resources").join(dir_name));
+                candidates.push(parent.join("Resources").join(dir_name));
             }
         }
     }
 
     if let Ok(current_dir) = std::env::current_dir() {
-        candidates.push(current_dir.join("resources").join("sounds").join(sound_name));
-        candidates.push(current_dir.join("src-tauri").join("resources").join("sounds").join(sound_name));
+        candidates.push(current_dir.join("resources").join(dir_name));
+        candidates.push(current_dir.join("src-tauri").join("resources").join(dir_name));
     }
 
     candidates
 }
 
+fn candidate_paths(app: &AppHandle, dir_name: &str, file_name: &str) -> Vec<PathBuf> {
+    candidate_dirs(app, dir_name)
+        .into_iter()
+        .map(|dir| dir.join(file_name))
+        .collect()
+}
+
 pub fn resolve_sound_path(app: &AppHandle, sound_name: &str) -> Option<String> {
-    candidate_paths(app, sound_name)
+    candidate_paths(app, SOUNDS_DIR_NAME, sound_name)
         .iter()
         .find_map(|path| normalize_existing_path(path))
 }
 
+/// Lists every location checked for a sound, in lookup order and without duplicates.
+pub fn sound_search_paths(app: &AppHandle, sound_name: &str) -> Vec<String> {
+    let mut searched: Vec<String> = Vec::new();
+    for path in candidate_paths(app, SOUNDS_DIR_NAME, sound_name) {
+        let display = to_platform_string(&path);
+        if !searched.contains(&display) {
+            searched.push(display);
+        }
+    }
+    searched
+}
+
+/// Names of all bundled sound files found in any of the resource locations, sorted.
+pub fn list_bundled_sounds(app: &AppHandle) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for dir in candidate_dirs(app, SOUNDS_DIR_NAME) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let is_audio = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| SOUND_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+                .unwrap_or(false);
+            if !is_audio {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                if !names.iter().any(|existing| existing == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names
+}
+
 pub fn read_sound_file(app: &AppHandle, sound_name: &str) -> Option<Vec<u8>> {
     let path = resolve_sound_path(app, sound_name)?;
     fs::read(path).ok()
 }
+
+pub fn read_benchmark_clip(app: &AppHandle) -> Option<Vec<u8>> {
+    let path = candidate_paths(app, BENCHMARK_DIR_NAME, BENCHMARK_CLIP_NAME)
+        .iter()
+        .find_map(|path| normalize_existing_path(path))?;
+    fs::read(path).ok()
+}
@@ -0,0 +1,84 @@
+//! Языки распознавания, доступные в каждом режиме, для выпадающего списка в настройках.
+
+use serde::Serialize;
+
+/// Every language of the Whisper tokenizer, in its order (roughly by training data size).
+const WHISPER_LANGUAGES: [(&str, &str); 100] = [
+    ("en", "English"), ("zh", "Chinese"), ("de", "German"), ("es", "Spanish"),
+    ("ru", "Russian"), ("ko", "Korean"), ("fr", "French"), ("ja", "Japanese"),
+    ("pt", "Portuguese"), ("tr", "Turkish"), ("pl", "Polish"), ("ca", "Catalan"),
+    ("nl", "Dutch"), ("ar", "Arabic"), ("sv", "Swedish"), ("it", "Italian"),
+    ("id", "Indonesian"), ("hi", "Hindi"), ("fi", "Finnish"), ("vi", "Vietnamese"),
+    ("he", "Hebrew"), ("uk", "Ukrainian"), ("el", "Greek"), ("ms", "Malay"),
+    ("cs", "Czech"), ("ro", "Romanian"), ("da", "Danish"), ("hu", "Hungarian"),
+    ("ta", "Tamil"), ("no", "Norwegian"), ("th", "Thai"), ("ur", "Urdu"),
+    ("hr", "Croatian"), ("bg", "Bulgarian"), ("lt", "Lithuanian"), ("la", "Latin"),
+    ("mi", "Maori"), ("ml", "Malayalam"), ("cy", "Welsh"), ("sk", "Slovak"),
+    ("te", "Telugu"), ("fa", "Persian"), ("lv", "Latvian"), ("bn", "Bengali"),
+    ("sr", "Serbian"), ("az", "Azerbaijani"), ("sl", "Slovenian"), ("kn", "Kannada"),
+    ("et", "Estonian"), ("mk", "Macedonian"), ("br", "Breton"), ("eu", "Basque"),
+    ("is", "Icelandic"), ("hy", "Armenian"), ("ne", "Nepali"), ("mn", "Mongolian"),
+    ("bs", "Bosnian"), ("kk", "Kazakh"), ("sq", "Albanian"), ("sw", "Swahili"),
+    ("gl", "Galician"), ("mr", "Marathi"), ("pa", "Punjabi"), ("si", "Sinhala"),
+    ("km", "Khmer"), ("sn", "Shona"), ("yo", "Yoruba"), ("so", "Somali"),
+    ("af", "Afrikaans"), ("oc", "Occitan"), ("ka", "Georgian"), ("be", "Belarusian"),
+    ("tg", "Tajik"), ("sd", "Sindhi"), ("gu", "Gujarati"), ("am", "Amharic"),
+    ("yi", "Yiddish"), ("lo", "Lao"), ("uz", "Uzbek"), ("fo", "Faroese"),
+    ("ht", "Haitian Creole"), ("ps", "Pashto"), ("tk", "Turkmen"), ("nn", "Nynorsk"),
+    ("mt", "Maltese"), ("sa", "Sanskrit"), ("lb", "Luxembourgish"), ("my", "Myanmar"),
+    ("bo", "Tibetan"), ("tl", "Tagalog"), ("mg", "Malagasy"), ("as", "Assamese"),
+    ("tt", "Tatar"), ("haw", "Hawaiian"), ("ln", "Lingala"), ("ha", "Hausa"),
+    ("ba", "Bashkir"), ("jw", "Javanese"), ("su", "Sundanese"), ("yue", "Cantonese"),
+];
+
+/// Languages OpenAI lists as supported by its transcription models (word error rate below 50%).
+const API_LANGUAGE_CODES: [&str; 57] = [
+    "af", "ar", "hy", "az", "be", "bs", "bg", "ca", "zh", "hr", "cs", "da", "nl", "en", "et",
+    "fi", "fr", "gl", "de", "el", "he", "hi", "hu", "is", "id", "it", "ja", "kn", "kk", "ko",
+    "lv", "lt", "mk", "ms", "mr", "mi", "ne", "no", "fa", "pl", "pt", "ro", "ru", "sr", "sk",
+    "sl", "es", "sw", "sv", "tl", "ta", "th", "tr", "uk", "ur", "vi", "cy",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SpeechLanguage {
+    /// ISO 639-1 code (ISO 639-3 for `haw` and `yue`) as passed in the `language` field.
+    pub code: String,
+    pub name: String,
+}
+
+fn language_name(code: &str) -> Option<&'static str> {
+    WHISPER_LANGUAGES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, name)| *name)
+}
+
+fn to_languages<'a>(codes: impl Iterator<Item = &'a str>) -> Vec<SpeechLanguage> {
+    let mut languages: Vec<SpeechLanguage> = codes
+        .map(|code| SpeechLanguage {
+            code: code.to_string(),
+            name: language_name(code).unwrap_or(code).to_string(),
+        })
+        .collect();
+    languages.sort_by(|a, b| a.name.cmp(&b.name));
+    languages
+}
+
+pub fn api() -> Vec<SpeechLanguage> {
+    to_languages(API_LANGUAGE_CODES.into_iter())
+}
+
+/// Languages of the local server: what it reports in `/info`, or the full Whisper set
+/// when it is not running or does not report them.
+pub fn local(reported: &[String]) -> Vec<SpeechLanguage> {
+    let reported: Vec<String> = reported
+        .iter()
+        .map(|code| code.trim().to_lowercase())
+        .filter(|code| !code.is_empty())
+        .collect();
+    if reported.is_empty() {
+        return to_languages(WHISPER_LANGUAGES.iter().map(|(code, _)| *code));
+    }
+    to_languages(reported.iter().map(String::as_str))
+}
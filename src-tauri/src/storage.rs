@@ -0,0 +1,104 @@
+//! Учёт места на диске, занимаемого приложением, по категориям.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::task::spawn_blocking;
+
+use crate::local_speech::FastWhisperManager;
+use crate::{history, logging, notes};
+
+pub const STORAGE_CATEGORIES: [&str; 5] = ["config", "notes", "history_audio", "whisper_install", "logs"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StorageCategoryUsage {
+    pub category: String,
+    pub path: Option<String>,
+    pub size_bytes: u64,
+    pub clearable: bool,
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_file() {
+        return metadata.len();
+    }
+    if !metadata.is_dir() {
+        return 0;
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn category_path(app: &AppHandle, manager: &FastWhisperManager, category: &str) -> Result<Option<PathBuf>> {
+    let path = match category {
+        "config" => app.path().app_config_dir().ok(),
+        "notes" => notes::resolve_notes_dir(app).ok(),
+        "history_audio" => history::resolve_history_audio_dir(app).ok(),
+        "whisper_install" => Some(manager.repo_path(app)),
+        "logs" => logging::get_log_file_path(app),
+        other => return Err(anyhow!("Unknown storage category: {other}")),
+    };
+    Ok(path)
+}
+
+fn is_clearable(category: &str) -> bool {
+    // Конфиг и заметки - пользовательские данные, а не кэш
+    matches!(category, "history_audio" | "whisper_install" | "logs")
+}
+
+pub async fn usage(app: &AppHandle, manager: &FastWhisperManager) -> Result<Vec<StorageCategoryUsage>> {
+    let mut result = Vec::with_capacity(STORAGE_CATEGORIES.len());
+    for category in STORAGE_CATEGORIES {
+        let path = category_path(app, manager, category)?;
+        let size_bytes = match path.clone() {
+            Some(path) => spawn_blocking(move || dir_size(&path)).await?,
+            None => 0,
+        };
+        result.push(StorageCategoryUsage {
+            category: category.to_string(),
+            path: path.map(|value| value.to_string_lossy().to_string()),
+            size_bytes,
+            clearable: is_clearable(category),
+        });
+    }
+    Ok(result)
+}
+
+pub async fn clear(app: &AppHandle, manager: &Arc<FastWhisperManager>, category: &str) -> Result<()> {
+    if !STORAGE_CATEGORIES.contains(&category) {
+        return Err(anyhow!("Unknown storage category: {category}"));
+    }
+    if !is_clearable(category) {
+        return Err(anyhow!("Storage category {category} cannot be cleared"));
+    }
+    match category {
+        "history_audio" => {
+            let dir = history::resolve_history_audio_dir(app)?;
+            if tokio::fs::metadata(&dir).await.is_ok() {
+                tokio::fs::remove_dir_all(&dir).await?;
+            }
+        }
+        "whisper_install" => {
+            manager.uninstall(app).await?;
+        }
+        "logs" => {
+            logging::clear_log_file()?;
+        }
+        _ => {}
+    }
+    logging::log_message(&format!("[storage] Cleared category {category}"));
+    Ok(())
+}
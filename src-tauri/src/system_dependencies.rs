@@ -0,0 +1,57 @@
+//! Detection of external tools Winky relies on (git, ollama, ffmpeg).
+
+use serde::Serialize;
+
+use crate::process_info;
+
+/// Tool name and the flag that prints its version.
+const DEPENDENCIES: [(&str, &str); 3] = [("git", "--version"), ("ollama", "--version"), ("ffmpeg", "-version")];
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SystemDependency {
+    pub name: String,
+    pub present: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Ищем исполняемый файл через `where`/`which`, не запуская сам инструмент.
+pub(crate) async fn locate(name: &str) -> Option<String> {
+    #[cfg(windows)]
+    let output = process_info::run("where", &[name]).await?;
+    #[cfg(not(windows))]
+    let output = process_info::run("which", &[name]).await?;
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+async fn version(path: &str, flag: &str) -> Option<String> {
+    let output = process_info::run(path, &[flag]).await?;
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+pub async fn check_all() -> Vec<SystemDependency> {
+    let mut dependencies = Vec::with_capacity(DEPENDENCIES.len());
+    for (name, flag) in DEPENDENCIES {
+        let path = locate(name).await;
+        let version = match path.as_deref() {
+            Some(path) => version(path, flag).await,
+            None => None,
+        };
+        dependencies.push(SystemDependency {
+            name: name.to_string(),
+            present: path.is_some(),
+            path,
+            version,
+        });
+    }
+    dependencies
+}
@@ -0,0 +1,158 @@
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+
+/// Печать дельт стрима из разных вызовов не должна перемежаться.
+static TYPING: Mutex<()> = Mutex::new(());
+
+/// Sends the platform paste shortcut (Ctrl+V / Cmd+V) to the focused window.
+#[cfg(target_os = "windows")]
+pub fn send_paste_shortcut() -> Result<()> {
+    use winapi::um::winuser::{keybd_event, KEYEVENTF_KEYUP, VK_CONTROL};
+
+    const VK_V: u8 = 0x56;
+    unsafe {
+        keybd_event(VK_CONTROL as u8, 0, 0, 0);
+        keybd_event(VK_V, 0, 0, 0);
+        keybd_event(VK_V, 0, KEYEVENTF_KEYUP, 0);
+        keybd_event(VK_CONTROL as u8, 0, KEYEVENTF_KEYUP, 0);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn send_paste_shortcut() -> Result<()> {
+    let status = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "tell application \"System Events\" to keystroke \"v\" using command down",
+        ])
+        .status()
+        .map_err(|error| anyhow!("Failed to run osascript: {error}"))?;
+    if !status.success() {
+        return Err(anyhow!(
+            "osascript exited with {status}; grant Winky accessibility access to paste automatically"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn send_paste_shortcut() -> Result<()> {
+    // Wayland-сессии обслуживает wtype, X11 — xdotool
+    let candidates: [(&str, &[&str]); 2] = [
+        ("xdotool", &["key", "--clearmodifiers", "ctrl+v"]),
+        ("wtype", &["-M", "ctrl", "v", "-m", "ctrl"]),
+    ];
+    let mut errors = Vec::new();
+    for (program, args) in candidates {
+        match std::process::Command::new(program).args(args).status() {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => errors.push(format!("{program} exited with {status}")),
+            Err(error) => errors.push(format!("{program}: {error}")),
+        }
+    }
+    Err(anyhow!(
+        "Failed to send paste shortcut ({}); install xdotool or wtype",
+        errors.join(", ")
+    ))
+}
+
+/// Types `text` into the focused window as keystrokes; used to stream LLM deltas.
+pub fn type_text(text: &str) -> Result<()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    let _typing = TYPING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    type_text_native(text)
+}
+
+#[cfg(target_os = "windows")]
+fn type_text_native(text: &str) -> Result<()> {
+    use winapi::um::winuser::{
+        SendInput, INPUT, INPUT_KEYBOARD, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VK_RETURN,
+    };
+
+    let key_input = |vk: u16, scan: u16, flags: u32| {
+        let mut input: INPUT = unsafe { std::mem::zeroed() };
+        input.type_ = INPUT_KEYBOARD;
+        unsafe {
+            let ki = input.u.ki_mut();
+            ki.wVk = vk;
+            ki.wScan = scan;
+            ki.dwFlags = flags;
+        }
+        input
+    };
+
+    let mut inputs: Vec<INPUT> = Vec::new();
+    for unit in text.replace("\r\n", "\n").encode_utf16() {
+        if unit == u16::from(b'\n') {
+            // Символ перевода строки как unicode-ввод многие редакторы игнорируют — жмём Enter
+            inputs.push(key_input(VK_RETURN as u16, 0, 0));
+            inputs.push(key_input(VK_RETURN as u16, 0, KEYEVENTF_KEYUP));
+            continue;
+        }
+        inputs.push(key_input(0, unit, KEYEVENTF_UNICODE));
+        inputs.push(key_input(0, unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP));
+    }
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_mut_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+    if sent as usize != inputs.len() {
+        return Err(anyhow!(
+            "SendInput injected {sent} of {} key events; the target window may be elevated",
+            inputs.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn type_text_native(text: &str) -> Result<()> {
+    let mut args: Vec<String> = vec!["-e".into(), "tell application \"System Events\"".into()];
+    for (index, line) in text.replace("\r\n", "\n").split('\n').enumerate() {
+        if index > 0 {
+            args.extend(["-e".into(), "key code 36".into()]);
+        }
+        if !line.is_empty() {
+            let escaped = line.replace('\\', "\\\\").replace('"', "\\\"");
+            args.extend(["-e".into(), format!("keystroke \"{escaped}\"")]);
+        }
+    }
+    args.extend(["-e".into(), "end tell".into()]);
+    let status = std::process::Command::new("osascript")
+        .args(&args)
+        .status()
+        .map_err(|error| anyhow!("Failed to run osascript: {error}"))?;
+    if !status.success() {
+        return Err(anyhow!(
+            "osascript exited with {status}; grant Winky accessibility access to type automatically"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn type_text_native(text: &str) -> Result<()> {
+    let candidates: [(&str, &[&str]); 2] = [
+        ("xdotool", &["type", "--clearmodifiers", "--"]),
+        ("wtype", &["--"]),
+    ];
+    let mut errors = Vec::new();
+    for (program, args) in candidates {
+        match std::process::Command::new(program).args(args).arg(text).status() {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => errors.push(format!("{program} exited with {status}")),
+            Err(error) => errors.push(format!("{program}: {error}")),
+        }
+    }
+    Err(anyhow!(
+        "Failed to type text ({}); install xdotool or wtype",
+        errors.join(", ")
+    ))
+}
@@ -0,0 +1,307 @@
+//! Транскрибация аудио через OpenAI-совместимый эндпоинт `/v1/audio/transcriptions`.
+//! Локальный fast-fast-whisper и OpenAI принимают одинаковый multipart запрос.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::AUTHORIZATION;
+use reqwest::multipart::{Form, Part};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::fs;
+use tokio::process::Command;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+use crate::error::CommandError;
+use crate::{rate_limit, system_dependencies};
+
+const OPENAI_TRANSCRIPTIONS_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const LOCAL_TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(600);
+const API_TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(120);
+const TRANSCODE_SAMPLE_RATE: &str = "16000";
+/// Сколько файлов пакетной транскрибации обрабатываются одновременно.
+const BATCH_CONCURRENCY: usize = 3;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Путь к ffmpeg ищем один раз за запуск; `None` — ffmpeg не установлен.
+static FFMPEG_PATH: OnceCell<Option<String>> = OnceCell::const_new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechProvider {
+    Local,
+    OpenAi,
+}
+
+impl SpeechProvider {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "api" | "openai" => Ok(Self::OpenAi),
+            other => Err(anyhow!("Unsupported transcription provider: {other}")),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::OpenAi => "openai",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TranscriptionBenchmark {
+    pub provider: String,
+    pub model: String,
+    pub duration_ms: u64,
+    pub chars: usize,
+}
+
+/// Outcome for one file of [`transcribe_batch`]: either `text` or `error` is set.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTranscriptionResult {
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Payload of `transcribe:batch-progress`, emitted once per finished file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTranscriptionProgress {
+    pub path: String,
+    pub completed: usize,
+    pub total: usize,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Определяет MIME тип по сигнатуре файла, по умолчанию webm (формат MediaRecorder).
+pub fn detect_mime_type(audio: &[u8]) -> &'static str {
+    if audio.starts_with(b"RIFF") {
+        "audio/wav"
+    } else if audio.starts_with(b"OggS") {
+        "audio/ogg"
+    } else if audio.starts_with(b"fLaC") {
+        "audio/flac"
+    } else if audio.starts_with(b"ID3") || audio.starts_with(&[0xFF, 0xFB]) {
+        "audio/mpeg"
+    } else {
+        "audio/webm"
+    }
+}
+
+pub async fn ffmpeg_path() -> Option<&'static str> {
+    FFMPEG_PATH
+        .get_or_init(|| system_dependencies::locate("ffmpeg"))
+        .await
+        .as_deref()
+}
+
+/// Converts recorded audio to 16 kHz mono WAV with ffmpeg.
+/// Returns `None` when ffmpeg is not installed or the audio is already WAV.
+pub async fn transcode_to_wav(audio: &[u8]) -> Result<Option<Vec<u8>>> {
+    if detect_mime_type(audio) == "audio/wav" {
+        return Ok(None);
+    }
+    let Some(ffmpeg) = ffmpeg_path().await else {
+        return Ok(None);
+    };
+
+    let temp_dir = std::env::temp_dir();
+    let id = Uuid::new_v4();
+    let extension = file_name_for(detect_mime_type(audio)).trim_start_matches("audio.");
+    let input = temp_dir.join(format!("winky-transcode-{id}.{extension}"));
+    let output = temp_dir.join(format!("winky-transcode-{id}.wav"));
+    fs::write(&input, audio)
+        .await
+        .with_context(|| format!("write transcode input {}", input.display()))?;
+
+    let mut command = Command::new(ffmpeg);
+    command
+        .args(["-hide_banner", "-loglevel", "error", "-y", "-i"])
+        .arg(&input)
+        .args(["-ar", TRANSCODE_SAMPLE_RATE, "-ac", "1", "-f", "wav"])
+        .arg(&output);
+    #[cfg(windows)]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let converted = match command.output().await {
+        Ok(finished) if finished.status.success() => fs::read(&output)
+            .await
+            .with_context(|| format!("read transcoded audio {}", output.display())),
+        Ok(finished) => Err(anyhow!(
+            "ffmpeg exited with {}: {}",
+            finished.status,
+            String::from_utf8_lossy(&finished.stderr).trim()
+        )),
+        Err(error) => Err(anyhow!("Failed to run ffmpeg: {error}")),
+    };
+    let _ = fs::remove_file(&input).await;
+    let _ = fs::remove_file(&output).await;
+    converted.map(Some)
+}
+
+fn file_name_for(mime_type: &str) -> &'static str {
+    if mime_type.contains("wav") {
+        "audio.wav"
+    } else if mime_type.contains("ogg") {
+        "audio.ogg"
+    } else if mime_type.contains("flac") {
+        "audio.flac"
+    } else if mime_type.contains("mpeg") || mime_type.contains("mp3") {
+        "audio.mp3"
+    } else {
+        "audio.webm"
+    }
+}
+
+fn extract_text(payload: &Value) -> String {
+    payload
+        .get("text")
+        .and_then(|value| value.as_str())
+        .or_else(|| payload.as_str())
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+pub async fn transcribe(
+    provider: SpeechProvider,
+    endpoint: &str,
+    api_key: &str,
+    model: &str,
+    audio: Vec<u8>,
+    mime_type: &str,
+) -> Result<String> {
+    if audio.is_empty() {
+        return Err(anyhow!("Audio is empty"));
+    }
+    if model.trim().is_empty() {
+        return Err(anyhow!("Transcription model is missing."));
+    }
+
+    let part = Part::bytes(audio)
+        .file_name(file_name_for(mime_type))
+        .mime_str(mime_type)
+        .map_err(|e| CommandError::invalid_input(format!("Invalid audio MIME type {}: {}", mime_type, e)))?;
+    let form = Form::new()
+        .part("file", part)
+        .text("model", model.trim().to_string())
+        .text("response_format", "json");
+
+    let client = reqwest::Client::new();
+    let request = match provider {
+        SpeechProvider::Local => client
+            .post(endpoint)
+            .multipart(form)
+            .timeout(LOCAL_TRANSCRIBE_TIMEOUT),
+        SpeechProvider::OpenAi => {
+            let token = api_key.trim();
+            if token.is_empty() {
+                return Err(anyhow!("OpenAI API key is missing."));
+            }
+            rate_limit::acquire("openai").await?;
+            client
+                .post(OPENAI_TRANSCRIPTIONS_URL)
+                .header(AUTHORIZATION, format!("Bearer {}", token))
+                .multipart(form)
+                .timeout(API_TRANSCRIBE_TIMEOUT)
+        }
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| anyhow!("Failed to send transcription request: {}", e))?;
+    let status = response.status();
+    let payload = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(anyhow!("Transcription API returned {}: {}", status, payload));
+    }
+
+    let parsed: Value = serde_json::from_str(&payload).unwrap_or(Value::String(payload));
+    Ok(extract_text(&parsed))
+}
+
+pub async fn benchmark(
+    provider: SpeechProvider,
+    endpoint: &str,
+    api_key: &str,
+    model: &str,
+    audio: Vec<u8>,
+) -> Result<TranscriptionBenchmark> {
+    let mime_type = detect_mime_type(&audio);
+    let started = Instant::now();
+    let text = transcribe(provider, endpoint, api_key, model, audio, mime_type).await?;
+    Ok(TranscriptionBenchmark {
+        provider: provider.as_str().to_string(),
+        model: model.trim().to_string(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        chars: text.chars().count(),
+    })
+}
+
+async fn transcribe_file(
+    provider: SpeechProvider,
+    endpoint: &str,
+    api_key: &str,
+    model: &str,
+    path: &str,
+    transcode: bool,
+) -> Result<String> {
+    let audio = fs::read(path)
+        .await
+        .with_context(|| format!("read audio file {path}"))?;
+    let audio = if transcode && provider == SpeechProvider::Local {
+        transcode_to_wav(&audio).await?.unwrap_or(audio)
+    } else {
+        audio
+    };
+    let mime_type = detect_mime_type(&audio);
+    transcribe(provider, endpoint, api_key, model, audio, mime_type).await
+}
+
+/// Transcribes files with bounded concurrency; a failing file does not stop the batch.
+pub async fn transcribe_batch(
+    provider: SpeechProvider,
+    endpoint: &str,
+    api_key: &str,
+    model: &str,
+    paths: Vec<String>,
+    transcode: bool,
+    on_progress: impl Fn(BatchTranscriptionProgress),
+) -> BTreeMap<String, BatchTranscriptionResult> {
+    let total = paths.len();
+    let mut results = BTreeMap::new();
+    let mut pending = stream::iter(paths)
+        .map(|path| async move {
+            let outcome = transcribe_file(provider, endpoint, api_key, model, &path, transcode).await;
+            (path, outcome)
+        })
+        .buffer_unordered(BATCH_CONCURRENCY);
+
+    while let Some((path, outcome)) = pending.next().await {
+        let result = match outcome {
+            Ok(text) => BatchTranscriptionResult { text: Some(text), error: None },
+            Err(error) => BatchTranscriptionResult { text: None, error: Some(format!("{error:#}")) },
+        };
+        on_progress(BatchTranscriptionProgress {
+            path: path.clone(),
+            completed: results.len() + 1,
+            total,
+            ok: result.error.is_none(),
+            error: result.error.clone(),
+        });
+        results.insert(path, result);
+    }
+    results
+}
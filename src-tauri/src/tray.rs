@@ -1,12 +1,51 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use serde_json::json;
 use tauri::{
     image::Image,
     menu::{MenuBuilder, MenuItemBuilder},
     tray::{MouseButton, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager,
+    AppHandle, Emitter, Manager, WindowEvent,
 };
 use crate::window_open_main;
 
+static CLOSE_TO_TRAY: AtomicBool = AtomicBool::new(true);
+static MINIMIZE_TO_TRAY: AtomicBool = AtomicBool::new(false);
+
+pub fn set_main_window_behavior(close_to_tray: bool, minimize_to_tray: bool) {
+    CLOSE_TO_TRAY.store(close_to_tray, Ordering::Relaxed);
+    MINIMIZE_TO_TRAY.store(minimize_to_tray, Ordering::Relaxed);
+}
+
+/// Applies the close/minimize-to-tray settings to events of the main window.
+pub fn handle_main_window_event(app: &AppHandle, event: &WindowEvent) {
+    match event {
+        WindowEvent::CloseRequested { api, .. } => {
+            if CLOSE_TO_TRAY.load(Ordering::Relaxed) {
+                // Скрываем окно вместо закрытия, чтобы приложение продолжало работать в фоне
+                api.prevent_close();
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            } else {
+                // Оверлей и служебные окна остаются открытыми, поэтому завершаем приложение явно
+                app.exit(0);
+            }
+        }
+        WindowEvent::Resized(_) => {
+            if !MINIMIZE_TO_TRAY.load(Ordering::Relaxed) {
+                return;
+            }
+            if let Some(window) = app.get_webview_window("main") {
+                if window.is_minimized().unwrap_or(false) {
+                    let _ = window.hide();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 fn load_image_from_path(path: &std::path::Path) -> Option<Image<'static>> {
     let img = image::open(path).ok()?;
     let rgba = img.to_rgba8();
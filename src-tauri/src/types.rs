@@ -1,13 +1,22 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::constants::{
     BACKEND_DOMAIN_RU,
+    COMPLETION_BEHAVIORS,
+    DEFAULT_HOTKEY_DEBOUNCE_MS,
+    DEFAULT_HEALTH_POLL_INTERVAL_MS,
     DEFAULT_BACKEND_DOMAIN,
     DEFAULT_LLM_MODEL,
+    DEFAULT_LLM_OUTPUT_FORMAT,
     DEFAULT_MIC_ANCHOR,
+    DEFAULT_OVERLAY_THEME,
     DEFAULT_SPEECH_MODEL,
+    LLM_OUTPUT_FORMATS,
+    OVERLAY_THEMES,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -89,6 +98,27 @@ pub struct WindowPosition {
     pub y: f64,
 }
 
+/// Saved geometry of one app window, in physical pixels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowLayoutEntry {
+    pub label: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MouseTrigger {
+    /// 3 = middle, 4 = back (X1), 5 = forward (X2).
+    pub button: u8,
+    #[serde(default)]
+    pub modifier: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppConfig {
@@ -142,8 +172,70 @@ pub struct AppConfig {
     pub global_transcribe_prompt: Option<String>,
     #[serde(default)]
     pub global_llm_prompt: Option<String>,
+    /// Wraps every action's LLM prompt: prefix goes first, suffix last.
+    #[serde(default)]
+    pub global_prompt_prefix: Option<String>,
+    #[serde(default)]
+    pub global_prompt_suffix: Option<String>,
     #[serde(default)]
     pub selected_microphone_id: Option<String>,
+    #[serde(default = "default_false")]
+    pub prefer_http_oauth: bool,
+    #[serde(default = "default_completion_behavior")]
+    pub completion_behavior: String,
+    #[serde(default)]
+    pub mouse_trigger: Option<MouseTrigger>,
+    #[serde(default = "default_false")]
+    pub duck_audio_while_recording: bool,
+    #[serde(default = "default_hotkey_debounce_ms")]
+    pub hotkey_debounce_ms: u64,
+    #[serde(default)]
+    pub local_speech_install_dir: Option<String>,
+    #[serde(default)]
+    pub speech_language: Option<String>,
+    #[serde(default = "default_overlay_theme")]
+    pub overlay_theme: String,
+    #[serde(default)]
+    pub mic_auto_hide_delay_ms: u64,
+    #[serde(default)]
+    pub window_layout: Vec<WindowLayoutEntry>,
+    #[serde(default = "default_false")]
+    pub prewarm_llm: bool,
+    /// Requests per minute per provider ("openai", "gemini"); missing or 0 means unlimited.
+    #[serde(default)]
+    pub provider_rate_limits: HashMap<String, u32>,
+    #[serde(default = "default_true")]
+    pub close_to_tray: bool,
+    #[serde(default = "default_false")]
+    pub minimize_to_tray: bool,
+    /// Name of the output device for native sound playback; `None` uses the system default.
+    #[serde(default)]
+    pub sound_output_device: Option<String>,
+    /// Convert recordings to 16 kHz mono WAV via ffmpeg (when installed) before local transcription.
+    #[serde(default = "default_true")]
+    pub transcode_local_audio: bool,
+    /// Interval of the background whisper health poller; 0 disables `local-speech:health` events.
+    #[serde(default = "default_health_poll_interval_ms")]
+    pub local_speech_health_poll_ms: u64,
+    /// Keeps the mic overlay above other windows, including fullscreen apps.
+    #[serde(default = "default_true")]
+    pub mic_always_on_top: bool,
+    /// Stops the local speech server after this many idle minutes; 0 disables the timeout.
+    #[serde(default)]
+    pub local_speech_idle_stop_minutes: u64,
+    /// Starts the local speech server on the first transcription instead of at launch.
+    #[serde(default)]
+    pub local_speech_lazy_start: bool,
+    /// Deletes history audio older than this many days, keeping the text; 0 keeps audio forever.
+    #[serde(default)]
+    pub history_audio_retention_days: u32,
+    /// Completed onboarding steps: step id -> RFC3339 time it was first completed.
+    #[serde(default)]
+    pub onboarding_state: HashMap<String, String>,
+    /// `text` or `markdown`: the renderer adds a matching instruction to every LLM prompt
+    /// and strips Markdown from results when plain text is requested.
+    #[serde(default = "default_llm_output_format")]
+    pub llm_output_format: String,
 }
 
 impl Default for AppConfig {
@@ -174,7 +266,32 @@ impl Default for AppConfig {
             trim_silence_on_actions: default_false(),
             global_transcribe_prompt: None,
             global_llm_prompt: None,
+            global_prompt_prefix: None,
+            global_prompt_suffix: None,
             selected_microphone_id: None,
+            prefer_http_oauth: default_false(),
+            completion_behavior: default_completion_behavior(),
+            mouse_trigger: None,
+            duck_audio_while_recording: default_false(),
+            hotkey_debounce_ms: default_hotkey_debounce_ms(),
+            local_speech_install_dir: None,
+            speech_language: None,
+            overlay_theme: default_overlay_theme(),
+            mic_auto_hide_delay_ms: 0,
+            window_layout: Vec::new(),
+            prewarm_llm: default_false(),
+            provider_rate_limits: HashMap::new(),
+            close_to_tray: default_true(),
+            minimize_to_tray: default_false(),
+            sound_output_device: None,
+            transcode_local_audio: default_true(),
+            local_speech_health_poll_ms: default_health_poll_interval_ms(),
+            mic_always_on_top: default_true(),
+            local_speech_idle_stop_minutes: 0,
+            local_speech_lazy_start: false,
+            history_audio_retention_days: 0,
+            onboarding_state: HashMap::new(),
+            llm_output_format: default_llm_output_format(),
         }
     }
 }
@@ -203,6 +320,26 @@ fn default_notes_storage_mode() -> String {
     "api".to_string()
 }
 
+fn default_completion_behavior() -> String {
+    "copy_and_notify".to_string()
+}
+
+fn default_overlay_theme() -> String {
+    DEFAULT_OVERLAY_THEME.to_string()
+}
+
+fn default_llm_output_format() -> String {
+    DEFAULT_LLM_OUTPUT_FORMAT.to_string()
+}
+
+fn default_hotkey_debounce_ms() -> u64 {
+    DEFAULT_HOTKEY_DEBOUNCE_MS
+}
+
+fn default_health_poll_interval_ms() -> u64 {
+    DEFAULT_HEALTH_POLL_INTERVAL_MS
+}
+
 fn default_backend_domain() -> String {
     DEFAULT_BACKEND_DOMAIN.to_string()
 }
@@ -236,6 +373,15 @@ impl AppConfig {
         if self.notes_storage_mode.trim().is_empty() {
             self.notes_storage_mode = default_notes_storage_mode();
         }
+        if !COMPLETION_BEHAVIORS.contains(&self.completion_behavior.as_str()) {
+            self.completion_behavior = default_completion_behavior();
+        }
+        if !OVERLAY_THEMES.contains(&self.overlay_theme.as_str()) {
+            self.overlay_theme = default_overlay_theme();
+        }
+        if !LLM_OUTPUT_FORMATS.contains(&self.llm_output_format.as_str()) {
+            self.llm_output_format = default_llm_output_format();
+        }
         if self.auth.access.is_empty() && !self.auth.access_token.is_empty() {
             self.auth.access = self.auth.access_token.clone();
         }
@@ -265,6 +411,8 @@ pub struct FastWhisperStatus {
     pub last_success_at: Option<i64>,
     pub log_line: Option<String>,
     pub install_dir: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
     pub updated_at: i64,
 }
 
@@ -280,6 +428,7 @@ impl FastWhisperStatus {
             last_success_at: None,
             log_line: None,
             install_dir: None,
+            base_url: None,
             updated_at: Utc::now().timestamp_millis(),
         }
     }
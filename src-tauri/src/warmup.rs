@@ -0,0 +1,137 @@
+//! Одновременный прогрев LLM и распознавания речи перед сессией записи.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::local_speech::FastWhisperManager;
+use crate::types::AppConfig;
+use crate::{logging, oauth, ollama};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+const OPENAI_ORIGIN: &str = "https://api.openai.com";
+const GEMINI_ORIGIN: &str = "https://generativelanguage.googleapis.com";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendReadiness {
+    /// `llm`, `ollama` or `speech`.
+    pub backend: &'static str,
+    pub ready: bool,
+    /// The backend is not used by the current modes, so nothing was done.
+    pub skipped: bool,
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+impl BackendReadiness {
+    fn skipped(backend: &'static str, message: &str) -> Self {
+        Self {
+            backend,
+            ready: true,
+            skipped: true,
+            message: message.to_string(),
+            duration_ms: 0,
+        }
+    }
+
+    fn from_outcome(backend: &'static str, started: Instant, outcome: Result<String>) -> Self {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        match outcome {
+            Ok(message) => Self { backend, ready: true, skipped: false, message, duration_ms },
+            Err(error) => Self {
+                backend,
+                ready: false,
+                skipped: false,
+                message: format!("{error:#}"),
+                duration_ms,
+            },
+        }
+    }
+}
+
+/// Origin of the API provider that serves the configured model, as in the renderer's prewarm.
+fn llm_origin(config: &AppConfig) -> String {
+    let model = config.llm.model.as_str();
+    if model.starts_with("winky-") {
+        let api_base = oauth::resolve_api_base(config);
+        return url::Url::parse(&api_base)
+            .map(|parsed| parsed.origin().ascii_serialization())
+            .unwrap_or(api_base);
+    }
+    if model.starts_with("gemini") {
+        GEMINI_ORIGIN.to_string()
+    } else {
+        OPENAI_ORIGIN.to_string()
+    }
+}
+
+async fn ping_llm(config: &AppConfig) -> BackendReadiness {
+    if config.llm.mode != "api" {
+        return BackendReadiness::skipped("llm", "LLM runs locally");
+    }
+    let origin = llm_origin(config);
+    let started = Instant::now();
+    // Любой HTTP-ответ означает, что DNS/TLS установлены и соединение в пуле
+    let outcome = reqwest::Client::new()
+        .head(&origin)
+        .timeout(PING_TIMEOUT)
+        .send()
+        .await
+        .map(|response| format!("{origin} answered {}", response.status()))
+        .map_err(|error| anyhow!("{origin} is unreachable: {error}"));
+    BackendReadiness::from_outcome("llm", started, outcome)
+}
+
+async fn warm_ollama(config: &AppConfig) -> BackendReadiness {
+    if config.llm.mode != "local" {
+        return BackendReadiness::skipped("ollama", "LLM runs via API");
+    }
+    let model = config.llm.model.trim().to_string();
+    let started = Instant::now();
+    let outcome = ollama::warmup_model(&model)
+        .await
+        .map(|_| format!("Model {model} is ready"));
+    BackendReadiness::from_outcome("ollama", started, outcome)
+}
+
+async fn start_speech(app: &AppHandle, manager: Arc<FastWhisperManager>, config: &AppConfig) -> BackendReadiness {
+    if config.speech.mode != "local" {
+        return BackendReadiness::skipped("speech", "Speech is transcribed via API");
+    }
+    let started = Instant::now();
+    let outcome = manager.start_if_auto_enabled(app).await.and_then(|status| {
+        if status.running {
+            Ok(status.message)
+        } else {
+            Err(anyhow!(status.error.unwrap_or(status.message)))
+        }
+    });
+    BackendReadiness::from_outcome("speech", started, outcome)
+}
+
+/// Prepares every backend the current config uses, concurrently.
+pub async fn warmup_all(
+    app: &AppHandle,
+    manager: Arc<FastWhisperManager>,
+    config: &AppConfig,
+) -> Vec<BackendReadiness> {
+    let (llm, ollama, speech) = tokio::join!(
+        ping_llm(config),
+        warm_ollama(config),
+        start_speech(app, manager, config),
+    );
+    let results = vec![llm, ollama, speech];
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|result| !result.ready)
+        .map(|result| result.backend)
+        .collect();
+    if !failed.is_empty() {
+        logging::log_message(&format!("[warmup] Not ready: {}", failed.join(", ")));
+    }
+    results
+}
@@ -0,0 +1,106 @@
+//! Snapshot and restore of the geometry and visibility of all app windows.
+
+use anyhow::{anyhow, Context, Result};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize};
+
+use crate::constants::MIC_WINDOW_MARGIN;
+use crate::error::CommandError;
+use crate::logging;
+use crate::types::WindowLayoutEntry;
+
+pub fn capture(app: &AppHandle) -> Result<Vec<WindowLayoutEntry>> {
+    let mut entries = Vec::new();
+    for (label, window) in app.webview_windows() {
+        let position = window
+            .outer_position()
+            .with_context(|| format!("read position of window {label}"))?;
+        let size = window
+            .outer_size()
+            .with_context(|| format!("read size of window {label}"))?;
+        entries.push(WindowLayoutEntry {
+            label,
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            visible: window.is_visible().unwrap_or(true),
+        });
+    }
+    entries.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(entries)
+}
+
+/// Applies saved geometry to the windows that are currently open.
+/// Returns labels of restored windows; windows that are not open are skipped.
+pub fn apply(app: &AppHandle, entries: &[WindowLayoutEntry]) -> Result<Vec<String>> {
+    let mut restored = Vec::new();
+    for entry in entries {
+        let Some(window) = app.get_webview_window(&entry.label) else {
+            logging::log_message(&format!(
+                "[window_layout] Window {} is not open, skipping",
+                entry.label
+            ));
+            continue;
+        };
+        window
+            .set_size(PhysicalSize::new(entry.width, entry.height))
+            .with_context(|| format!("resize window {}", entry.label))?;
+        window
+            .set_position(PhysicalPosition::new(entry.x, entry.y))
+            .with_context(|| format!("move window {}", entry.label))?;
+        if entry.visible {
+            window.show()
+        } else {
+            window.hide()
+        }
+        .with_context(|| format!("change visibility of window {}", entry.label))?;
+        restored.push(entry.label.clone());
+    }
+    Ok(restored)
+}
+
+/// Moves a window onto the monitor with the given index (in `available_monitors` order),
+/// placing it in the `anchor` corner. Returns the new position in physical pixels,
+/// the same units the mic window reports through its move events.
+pub fn move_to_monitor(
+    app: &AppHandle,
+    label: &str,
+    monitor_index: usize,
+    anchor: &str,
+) -> Result<(f64, f64)> {
+    let window = app
+        .get_webview_window(label)
+        .ok_or_else(|| CommandError::not_found(format!("Window {label} not found")))?;
+    let monitors = window.available_monitors().context("enumerate monitors")?;
+    let monitor = monitors.get(monitor_index).ok_or_else(|| {
+        anyhow!(
+            "Monitor index {monitor_index} is out of range, {} monitor(s) available",
+            monitors.len()
+        )
+    })?;
+    let size = window
+        .outer_size()
+        .with_context(|| format!("read size of window {label}"))?;
+
+    let origin = monitor.position();
+    let area = monitor.size();
+    let margin = (MIC_WINDOW_MARGIN * monitor.scale_factor()).round() as i32;
+    let left = origin.x + margin;
+    let top = origin.y + margin;
+    let right = origin.x + area.width as i32 - size.width as i32 - margin;
+    let bottom = origin.y + area.height as i32 - size.height as i32 - margin;
+    let (x, y) = match anchor {
+        "top-right" => (right, top),
+        "bottom-left" => (left, bottom),
+        "bottom-right" => (right, bottom),
+        _ => (left, top),
+    };
+
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .with_context(|| format!("move window {label}"))?;
+    logging::log_message(&format!(
+        "[window_layout] Moved window {label} to monitor {monitor_index} ({anchor})"
+    ));
+    Ok((x as f64, y as f64))
+}
@@ -11,7 +11,7 @@ import {useConfigController} from './app/hooks/useConfigController';
 import {useNavigationSync} from './app/hooks/useNavigationSync';
 import {useToastBridge} from './app/hooks/useToastBridge';
 import {useWindowChrome} from './app/hooks/useWindowChrome';
-import {configBridge, groupsBridge} from './services/winkyBridge';
+import {authBridge, groupsBridge} from './services/winkyBridge';
 import {Slide, ToastContainer, toast} from 'react-toastify';
 import 'react-toastify/dist/ReactToastify.css';
 import './styles/ReactToastify.sass';
@@ -63,12 +63,7 @@ const AppContent: React.FC = () => {
     const {isDark} = useThemeMode();
     const toastTheme = isDark ? 'dark' : 'colored';
     const clearAuthTokens = useCallback(async () => {
-        await configBridge.setAuth({
-            access: '',
-            refresh: null,
-            accessToken: '',
-            refreshToken: ''
-        });
+        await authBridge.logout();
     }, []);
 
     const showToast = useCallback(
@@ -123,6 +118,7 @@ const AppContent: React.FC = () => {
         const shouldWarmup =
             Boolean(config?.launchOnSystemStartup) &&
             Boolean(config?.autoStartLocalSpeechServer) &&
+            !config?.localSpeechLazyStart &&
             config?.speech.mode === SPEECH_MODES.LOCAL;
 
         if (!shouldWarmup) {
@@ -238,6 +234,7 @@ const AppContent: React.FC = () => {
         };
     }, [
         config?.autoStartLocalSpeechServer,
+        config?.localSpeechLazyStart,
         config?.launchOnSystemStartup,
         config?.speech.mode,
         config?.speech.model
@@ -243,7 +243,7 @@ export function AuthProvider({children}: AuthProviderProps) {
                     refresh: payload.tokens.refresh ?? null,
                     accessToken: payload.tokens.access,
                     refreshToken: payload.tokens.refresh ?? ''
-                }).then((updatedConfig) => {
+                }, payload.provider).then((updatedConfig) => {
                     console.log('[auth] Tokens saved to config successfully', {
                         hasAccess: !!(updatedConfig.auth.access || updatedConfig.auth.accessToken),
                         setupCompleted: updatedConfig.setupCompleted
@@ -336,7 +336,7 @@ export function AuthProvider({children}: AuthProviderProps) {
                     refresh: tokens.refresh ?? null,
                     accessToken: tokens.access,
                     refreshToken: tokens.refresh ?? ''
-                }).catch((err) => {
+                }, 'email').catch((err) => {
                     console.warn('[auth] Failed to save tokens to config', err);
                 });
             }
@@ -494,13 +494,8 @@ export function AuthProvider({children}: AuthProviderProps) {
         authClient.clearTokens();
 
         // Очищаем токены в config
-        if (configBridge) {
-            configBridge.setAuth({
-                access: '',
-                refresh: null,
-                accessToken: '',
-                refreshToken: ''
-            }).catch((err) => {
+        if (appAuthBridge) {
+            appAuthBridge.logout().catch((err) => {
                 console.warn('[auth] Failed to clear tokens in config', err);
             });
         }
@@ -533,6 +528,18 @@ export function AuthProvider({children}: AuthProviderProps) {
         return unsubscribe;
     }, [signOut, status]);
 
+    // Выход в другом окне: сбрасываем пользователя и здесь, не дожидаясь 401
+    useEffect(() => {
+        return appAuthBridge.onAuthChanged((event) => {
+            if (event.authenticated) {
+                return;
+            }
+            authClient.clearTokens();
+            setUser(null);
+            setStatus('unauthenticated');
+        });
+    }, []);
+
     const reloadUser = useCallback(async () => {
         console.log('[auth] reloadUser called');
         if (!authClient.hasTokens()) {
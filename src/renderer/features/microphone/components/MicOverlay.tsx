@@ -13,8 +13,10 @@ import MicActionOrbit from './MicActionOrbit';
 import MicContextField from './MicContextField';
 import MicGroupSelector from './MicGroupSelector';
 import {resetInteractive} from '../../../utils/interactive';
-import {resourcesBridge} from '../../../services/winkyBridge';
+import {listen} from '@tauri-apps/api/event';
+import {micBridge, resourcesBridge} from '../../../services/winkyBridge';
 import {SYSTEM_GROUP_ID} from '@shared/constants';
+import {resolveLlmWarmupOrigin, startLlmPrewarm} from '../../../services/llm/prewarm';
 
 
 interface MicOverlayProps {
@@ -78,6 +80,63 @@ const MicOverlay: React.FC<MicOverlayProps> = ({contextTextRef: contextTextRefPr
         contextTextRef.current = text;
     }, [contextTextRef]);
 
+    const duckAudio = config?.duckAudioWhileRecording === true;
+    useEffect(() => {
+        if (!isMicOverlay || !duckAudio || !isRecording) {
+            return;
+        }
+        resourcesBridge.duckOthers(true).catch((error) => {
+            console.warn('[MicOverlay] Failed to duck other applications:', error);
+        });
+        return () => {
+            resourcesBridge.duckOthers(false).catch((error) => {
+                console.warn('[MicOverlay] Failed to restore other applications volume:', error);
+            });
+        };
+    }, [isMicOverlay, duckAudio, isRecording]);
+
+    const llmWarmupOrigin = config?.prewarmLlm === true ? resolveLlmWarmupOrigin(config) : null;
+    useEffect(() => {
+        if (!isMicOverlay || !llmWarmupOrigin) {
+            return;
+        }
+        return startLlmPrewarm(llmWarmupOrigin);
+    }, [isMicOverlay, llmWarmupOrigin]);
+
+    // Отложенное скрытие: бэкенд шлёт mic:auto-hide, если запись не возобновилась за заданное время
+    const autoHideDelayMs = config?.micAutoHideDelayMs ?? 0;
+    const autoHidePendingRef = useRef(false);
+    useEffect(() => {
+        if (!isMicOverlay || autoHideDelayMs <= 0) {
+            return;
+        }
+        const unlistenPromise = listen('mic:auto-hide', () => {
+            if (isRecordingRef.current) {
+                return;
+            }
+            if (processingRef.current) {
+                autoHidePendingRef.current = true;
+                return;
+            }
+            void micBridge.hide({reason: 'auto-hide'});
+        });
+        return () => {
+            autoHidePendingRef.current = false;
+            unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
+        };
+    }, [isMicOverlay, autoHideDelayMs, isRecordingRef, processingRef]);
+
+    useEffect(() => {
+        if (isRecording) {
+            autoHidePendingRef.current = false;
+            return;
+        }
+        if (!processing && autoHidePendingRef.current) {
+            autoHidePendingRef.current = false;
+            void micBridge.hide({reason: 'auto-hide'});
+        }
+    }, [isRecording, processing]);
+
     useEffect(() => {
         const enabled = config?.completionSoundEnabled !== false;
         setCompletionEnabled(enabled);
@@ -231,7 +290,11 @@ const MicOverlay: React.FC<MicOverlayProps> = ({contextTextRef: contextTextRefPr
             ) : (
                 <audio ref={completionSoundRef} />
             )}
-            <div className="relative h-full w-full" style={{transform: `translateY(${overlayOffset}px)`}}>
+            <div
+                className="relative h-full w-full"
+                data-overlay-theme={config?.overlayTheme ?? 'default'}
+                style={{transform: `translateY(${overlayOffset}px)`}}
+            >
                 {/* Group selector at the top */}
                 {groups.filter((g) => !g.is_system && g.id !== SYSTEM_GROUP_ID).length > 1 && (
                     <div
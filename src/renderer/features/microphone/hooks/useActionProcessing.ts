@@ -1,9 +1,11 @@
 import {useCallback, type RefObject} from 'react';
-import type {ActionConfig, ActionHistoryEntry, AppConfig} from '@shared/types';
+import type {ActionConfig, ActionHistoryEntry, AppConfig, CompletionBehavior} from '@shared/types';
 import {getSiteBaseUrl, LLM_MODES, LLM_WINKY_API_MODELS, SPEECH_WINKY_API_MODELS} from '@shared/constants';
 import {createNoteForMode, deriveNoteTitle, resolveNotesStorageMode} from '../../../services/notesService';
 import {clipboardBridge, historyBridge, llmBridge, resourcesBridge, speechBridge, windowBridge} from '../../../services/winkyBridge';
 import {trimSilenceFromAudioBlob, isAudioSilent} from '../services/audioProcessing';
+import {buildLlmPrompt, formatLlmResult} from '../../../services/llm/prompt';
+import {createStreamTyper} from '../../../services/streamTyper';
 import {winkyTranscribe, winkyLLMStream} from '../../../services/winkyAiApi';
 import {
     createLocalChatId,
@@ -275,6 +277,9 @@ export const useActionProcessing = ({
                 .join('\n\n')
                 .trim() || undefined;
 
+            // Язык действия важнее глобального: например, для «перевести на английский»
+            const transcriptionLanguage = action.language?.trim() || config.speechLanguage?.trim() || undefined;
+
             let transcription = '';
 
             // Выполняем транскрипцию только если аудио не тихое
@@ -295,7 +300,7 @@ export const useActionProcessing = ({
                 // Winky модели используют собственный API для транскрибации
                 if (isWinkySpeechModel(config.speech.model) && authToken) {
                     try {
-                        const result = await winkyTranscribe(audioData, authToken, {mimeType});
+                        const result = await winkyTranscribe(audioData, authToken, {mimeType, language: transcriptionLanguage});
                         transcription = result.text;
                     } catch (error: any) {
                         if (error?.response?.status === 402) {
@@ -310,11 +315,13 @@ export const useActionProcessing = ({
                         openaiKey: config.apiKeys.openai,
                         googleKey: config.apiKeys.google,
                         accessToken: authToken,
-                        prompt: transcriptionPrompt
+                        prompt: transcriptionPrompt,
+                        language: transcriptionLanguage
                     }, {
                         signal: abortController?.signal,
                         uiTimeoutMs: TRANSCRIBE_UI_TIMEOUT_MS,
-                        mimeType
+                        mimeType,
+                        transcode: config.transcodeLocalAudio !== false
                     });
                 }
 
@@ -362,15 +369,14 @@ export const useActionProcessing = ({
             const needsLLM = Boolean(action.prompt && action.prompt.trim());
             const llmModel = action.llm_model?.trim() || config.llm.model;
             const useWinkyLLM = isWinkyLLMModel(llmModel) && authToken;
+            // paste_stream: дельты LLM печатаются в активное окно по мере генерации
+            const streamTyper = needsLLM && action.auto_copy_result && config.completionBehavior === 'paste_stream'
+                ? createStreamTyper()
+                : null;
 
             // Для Winky LLM моделей - используем чаты
             if (needsLLM && useWinkyLLM) {
-                const actionLlmPrompt = action.prompt?.trim() || '';
-                const globalLlmPrompt = config.globalLlmPrompt?.trim() || '';
-                const llmPrompt = [globalLlmPrompt, actionLlmPrompt]
-                    .filter(p => p.length > 0)
-                    .join('\n\n')
-                    .trim();
+                const llmPrompt = buildLlmPrompt(action, config);
 
                 const fullPrompt = llmPrompt ? `${llmPrompt}\n\n${llmInput}` : llmInput;
                 const modelLevel = llmModel === 'winky-high' ? 'high' : llmModel === 'winky-mid' ? 'mid' : 'low';
@@ -469,6 +475,7 @@ export const useActionProcessing = ({
                         authToken,
                         (chunk) => {
                             streamedResponse += chunk;
+                            streamTyper?.push(chunk);
                             if (action.show_results && historyEntry) {
                                 scheduleHistoryUpdate({
                                     transcription: transcriptionForOutput,
@@ -485,11 +492,16 @@ export const useActionProcessing = ({
                     await historyUpdatePromise;
                     await flushHistoryUpdate();
 
-                    const finalResponse = result.content?.trim().length ? result.content : streamedResponse;
+                    const finalResponse = formatLlmResult(
+                        result.content?.trim().length ? result.content : streamedResponse,
+                        config
+                    );
                     const resultText = finalResponse.trim().length > 0 ? finalResponse : transcriptionForOutput;
 
                     if (action.auto_copy_result) {
-                        await copyWithRetries({
+                        await deliverResult({
+                            behavior: config.completionBehavior,
+                            typedStream: await streamTyper?.finish(),
                             text: finalResponse,
                             showToast,
                             successMessage: 'Response copied.',
@@ -535,6 +547,7 @@ export const useActionProcessing = ({
 
                     await playCompletionSound({action: completionAction, config, audioRef: completionSoundRef, debug: true});
                 } catch (error: any) {
+                    streamTyper?.cancel();
                     clearHistoryUpdateTimer();
                     await historyUpdatePromise;
                     await flushHistoryUpdate();
@@ -627,7 +640,8 @@ export const useActionProcessing = ({
                     }
                 }
                 if (action.auto_copy_result) {
-                    await copyWithRetries({
+                    await deliverResult({
+                        behavior: config.completionBehavior,
                         text: responseText,
                         showToast,
                         successMessage: 'Result copied.',
@@ -660,12 +674,7 @@ export const useActionProcessing = ({
             };
 
             if (needsLLM && action.show_results) {
-                const actionLlmPrompt = action.prompt?.trim() || '';
-                const globalLlmPrompt = config.globalLlmPrompt?.trim() || '';
-                const llmPrompt = [globalLlmPrompt, actionLlmPrompt]
-                    .filter(p => p.length > 0)
-                    .join('\n\n')
-                    .trim();
+                const llmPrompt = buildLlmPrompt(action, config);
                 const startedAt = new Date().toISOString();
                 const assistantStartedAt = new Date(Date.now() + 1).toISOString();
                 const localChatId = createLocalChatId();
@@ -727,6 +736,7 @@ export const useActionProcessing = ({
                         {
                             onChunk: (chunk) => {
                                 streamedResponse += chunk;
+                                streamTyper?.push(chunk);
                                 updateLocalChatMessage(localChatId, assistantMessageId, {
                                     content: streamedResponse,
                                     provider: chatMeta.provider,
@@ -744,7 +754,7 @@ export const useActionProcessing = ({
                         }
                     );
 
-                    const finalResponse = response?.trim().length ? response : streamedResponse;
+                    const finalResponse = formatLlmResult(response?.trim().length ? response : streamedResponse, config);
                     const resultText = finalResponse.trim().length > 0 ? finalResponse : transcriptionForOutput;
 
                     updateLocalChatMessage(localChatId, assistantMessageId, {
@@ -762,7 +772,9 @@ export const useActionProcessing = ({
                     });
 
                     if (action.auto_copy_result) {
-                        await copyWithRetries({
+                        await deliverResult({
+                            behavior: config.completionBehavior,
+                            typedStream: await streamTyper?.finish(),
                             text: finalResponse ?? '',
                             showToast,
                             successMessage: 'Response copied.',
@@ -774,6 +786,7 @@ export const useActionProcessing = ({
                     clearContext();
                     await playCompletionSound({action: completionAction, config, audioRef: completionSoundRef, debug: true});
                 } catch (error) {
+                    streamTyper?.cancel();
                     updateLocalChatMessage(localChatId, assistantMessageId, {
                         content: streamedResponse || 'Failed to generate response.',
                         provider: chatMeta.provider,
@@ -792,9 +805,10 @@ export const useActionProcessing = ({
             }
 
             let streamedResponse = '';
-            const onChunk = action.show_results
+            const onChunk = action.show_results || streamTyper
                 ? (chunk: string) => {
                     streamedResponse += chunk;
+                    streamTyper?.push(chunk);
                     if (historyEntry) {
                         scheduleHistoryUpdate({
                             transcription: transcriptionForOutput,
@@ -808,12 +822,7 @@ export const useActionProcessing = ({
                 : undefined;
 
             // Используем объединенный запрос (транскрипция + текст из поля) для LLM
-            const actionLlmPrompt = action.prompt?.trim() || '';
-            const globalLlmPrompt = config.globalLlmPrompt?.trim() || '';
-            const llmPrompt = [globalLlmPrompt, actionLlmPrompt]
-                .filter(p => p.length > 0)
-                .join('\n\n')
-                .trim();
+            const llmPrompt = buildLlmPrompt(action, config);
 
             try {
                 if (action.show_results) {
@@ -843,12 +852,14 @@ export const useActionProcessing = ({
                 await historyUpdatePromise;
                 await flushHistoryUpdate();
 
-                const finalResponse = response?.trim().length ? response : streamedResponse;
+                const finalResponse = formatLlmResult(response?.trim().length ? response : streamedResponse, config);
                 const trimmedResponse = finalResponse?.trim() || '';
                 const resultText = trimmedResponse.length > 0 ? finalResponse : transcriptionForOutput;
 
                 if (action.auto_copy_result) {
-                    await copyWithRetries({
+                    await deliverResult({
+                        behavior: config.completionBehavior,
+                        typedStream: await streamTyper?.finish(),
                         text: finalResponse ?? '',
                         showToast,
                         successMessage: 'Response copied.',
@@ -890,6 +901,7 @@ export const useActionProcessing = ({
 
                 await playCompletionSound({action: completionAction, config, audioRef: completionSoundRef, debug: true});
             } catch (error) {
+                streamTyper?.cancel();
                 clearHistoryUpdateTimer();
                 await historyUpdatePromise;
                 await flushHistoryUpdate();
@@ -982,13 +994,48 @@ type CopyWithRetriesParams = {
     showToast: ToastFn;
     successMessage: string;
     failureMessage: string;
+    notify?: boolean;
+};
+
+type DeliverResultParams = CopyWithRetriesParams & {
+    behavior?: CompletionBehavior;
+    /** Outcome of paste_stream typing; `undefined` when the result was not streamed. */
+    typedStream?: boolean;
+};
+
+const deliverResult = async ({behavior = 'copy_and_notify', typedStream, ...params}: DeliverResultParams): Promise<boolean> => {
+    if (behavior === 'paste_stream') {
+        if (typedStream !== undefined) {
+            // Текст уже напечатан по ходу генерации, буфер обмена — запасная копия;
+            // если печать сорвалась, сообщаем, что результат можно вставить вручную
+            return copyWithRetries({...params, notify: !typedStream});
+        }
+        behavior = 'paste';
+    }
+    if (behavior !== 'paste') {
+        return copyWithRetries({...params, notify: behavior === 'copy_and_notify'});
+    }
+    const payload = params.text?.trim() ?? '';
+    if (!payload) {
+        console.warn('[useActionProcessing] Nothing to paste, skipping insert');
+        return false;
+    }
+    try {
+        await clipboardBridge.insertText(payload);
+        return true;
+    } catch (error) {
+        // Если вставка не удалась, текст всё равно остаётся в буфере обмена
+        console.error('[useActionProcessing] Failed to paste result, falling back to clipboard', error);
+        return copyWithRetries({...params, notify: true});
+    }
 };
 
 const copyWithRetries = async ({
                                    text,
                                    showToast,
                                    successMessage,
-                                   failureMessage
+                                   failureMessage,
+                                   notify = true
                                }: CopyWithRetriesParams): Promise<boolean> => {
     const payload = text?.trim() ?? '';
     if (!payload) {
@@ -1002,7 +1049,9 @@ const copyWithRetries = async ({
         }
         const copied = await clipboardBridge.writeText(payload);
         if (copied) {
-            showToast(successMessage, 'success');
+            if (notify) {
+                showToast(successMessage, 'success');
+            }
             return true;
         }
     }
@@ -1038,10 +1087,26 @@ const playCompletionSound = async ({
         return;
     }
     
+    // Собственный звук действия играем через native API: HTML Audio загружен только с completion.wav
+    const actionSound = action.completion_sound?.trim();
+    if (actionSound) {
+        try {
+            await resourcesBridge.playSound(actionSound, volumePreference);
+            if (debug) {
+                console.log('[useActionProcessing] Action sound played via native API:', actionSound);
+            }
+            return;
+        } catch (error) {
+            console.error('[useActionProcessing] Action sound failed, falling back to default:', error);
+        }
+    }
+
     const audio = audioRef.current;
+    // HTML Audio играет только на устройстве по умолчанию, выбранное устройство поддерживает лишь native API
+    const hasOutputDevice = Boolean(config?.soundOutputDevice);
     
     // Сначала пробуем HTML Audio API (поддерживает громкость)
-    if (audio && audio.src) {
+    if (audio && audio.src && !hasOutputDevice) {
         audio.volume = volumePreference;
         try {
             audio.currentTime = 0;
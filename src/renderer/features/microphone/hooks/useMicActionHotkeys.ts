@@ -1,5 +1,5 @@
 import {useEffect, useRef} from 'react';
-import type {ActionConfig} from '@shared/types';
+import type {ActionConfig, ActionTriggeredEvent} from '@shared/types';
 import {actionHotkeysBridge} from '../../../services/winkyBridge';
 
 type MutableRef<T> = {current: T};
@@ -210,7 +210,7 @@ export const useMicActionHotkeys = ({
         if (!isMicOverlay || typeof window === 'undefined') {
             return;
         }
-        const handler = (payload: {actionId?: string}) => {
+        const handler = (payload: Partial<ActionTriggeredEvent>) => {
             if (!payload?.actionId) {
                 return;
             }
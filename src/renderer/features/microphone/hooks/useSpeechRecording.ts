@@ -359,6 +359,20 @@ export const useSpeechRecording = ({config, showToast, isMicOverlay, contextText
                 if (!recorder) {
                     return;
                 }
+                // Без системного разрешения запись идёт «в тишину» — сообщаем об этом заранее
+                const permission = await micBridge.permissionStatus().catch(() => 'undetermined' as const);
+                if (permission === 'denied') {
+                    showToast(
+                        'Microphone access is denied by the system. Allow Winky in the privacy settings that just opened, then try again.',
+                        'error',
+                        {durationMs: 9000}
+                    );
+                    void micBridge.requestPermission().catch(() => {});
+                    return;
+                }
+                if (permission === 'undetermined') {
+                    await micBridge.requestPermission().catch(() => 'undetermined');
+                }
                 const stream = await recorder.startRecording();
                 setIsRecording(true);
                 isRecordingRef.current = true;
@@ -401,6 +415,7 @@ export const useSpeechRecording = ({config, showToast, isMicOverlay, contextText
                 
                 if (errorName === 'NotAllowedError' || errorName === 'PermissionDeniedError' || 
                     errorMessage.includes('Permission denied') || errorMessage.includes('NotAllowedError')) {
+                    void micBridge.requestPermission().catch(() => {});
                     showToast(
                         'Microphone access is blocked. Click the mic again and confirm the system prompt. If no dialog appears, enable access in Windows: Settings → Privacy → Microphone.',
                         'error',
@@ -461,14 +476,16 @@ export const useSpeechRecording = ({config, showToast, isMicOverlay, contextText
             resetInteractive();
             // Закрываем микрофон сразу, но только если он действительно должен закрываться
             // Убираем задержку, чтобы избежать проблем с повторным открытием
-            if (isMicOverlay && config?.micHideOnStopRecording !== false) {
+            // При заданной задержке окно скроет обработчик mic:auto-hide
+            const autoHideDelayed = (config?.micAutoHideDelayMs ?? 0) > 0;
+            if (isMicOverlay && config?.micHideOnStopRecording !== false && !autoHideDelayed) {
                 // Используем requestAnimationFrame для гарантии, что состояние обновлено
                 requestAnimationFrame(() => {
                     void micBridge.hide({reason: 'action'});
                 });
             }
         }
-    }, [processing, ensureSpeechServiceOnce, finishRecording, processAction, stopVolumeMonitor, isMicOverlay, config?.micHideOnStopRecording]);
+    }, [processing, ensureSpeechServiceOnce, finishRecording, processAction, stopVolumeMonitor, isMicOverlay, config?.micHideOnStopRecording, config?.micAutoHideDelayMs]);
 
     const groups = useMemo<ActionGroup[]>(() => config?.groups ?? [], [config?.groups]);
     const actions = useMemo<ActionConfig[]>(() => config?.actions ?? [], [config?.actions]);
@@ -108,10 +108,18 @@ export const useSpeechServiceReadiness = ({
             return true;
         }
         
-        // Проверяем, запущен ли локальный сервер
+        // Проверяем, запущен ли локальный сервер; остановленный по простою поднимется здесь
         try {
-            const status = await localSpeechBridge.checkHealth();
-            if (!status.running) {
+            if (config.localSpeechLazyStart) {
+                // Ленивый старт: сервер поднимается в фоне, пока идёт запись, транскрипция дождётся готовности
+                void localSpeechBridge.wake().catch((error) => {
+                    console.warn('[SpeechReadiness] Lazy start of local speech server failed:', error);
+                });
+            } else {
+                await localSpeechBridge.wake();
+            }
+            const status = config.localSpeechLazyStart ? null : await localSpeechBridge.checkHealth();
+            if (status && !status.running) {
                 const message = 'Local speech server is not running. Start the server in Settings before using the microphone.';
                 await openMainWindowWithToast(message);
                 showToast(message, 'error', {durationMs: 6000});
@@ -145,7 +153,7 @@ export const useSpeechServiceReadiness = ({
             return false;
         }
         return true;
-    }, [config?.speech.mode, config?.speech.model, localModelWarmingUp, openMainWindowWithToast, showToast, isMicOverlay]);
+    }, [config?.speech.mode, config?.speech.model, config?.localSpeechLazyStart, localModelWarmingUp, openMainWindowWithToast, showToast, isMicOverlay]);
 
     const ensureLocalLlmReady = useCallback(async (): Promise<boolean> => {
         if (config?.llm.mode !== LLM_MODES.LOCAL) {
@@ -0,0 +1,113 @@
+import {emit} from '@tauri-apps/api/event';
+import type {ActionConfig, AppConfig} from '@shared/types';
+import {LLM_WINKY_API_MODELS} from '@shared/constants';
+import {buildLlmPrompt, formatLlmResult} from './llm/prompt';
+import {processLLM} from './winkyApi';
+import {winkyLLMStream} from './winkyAiApi';
+import {getErrorMessage} from '../utils/errorMessage';
+
+export type ActionLlmOptions = {
+    onChunk?: (chunk: string) => void;
+    signal?: AbortSignal;
+};
+
+export type ActionTestStreamEvent =
+    | {streamId: string; delta: string}
+    | {streamId: string; done: true; result: string}
+    | {streamId: string; done: true; error: string};
+
+export const ACTION_TEST_STREAM_EVENT = 'action:test-stream';
+
+export const findActionOrThrow = (config: AppConfig, actionId: string): ActionConfig => {
+    const action = config.actions.find((item) => item.id === actionId);
+    if (!action) {
+        throw new Error(`Action ${actionId} not found`);
+    }
+    return action;
+};
+
+/** Runs the action's prompt over `input` with the action's (or default) model and returns the full result. */
+export const runActionLlm = async (
+    action: ActionConfig,
+    input: string,
+    config: AppConfig,
+    {onChunk, signal}: ActionLlmOptions = {}
+): Promise<string> => {
+    const llmPrompt = buildLlmPrompt(action, config);
+    const llmModel = action.llm_model?.trim() || config.llm.model;
+    const authToken = config.auth.access || config.auth.accessToken || undefined;
+    let streamed = '';
+    const handleChunk = (chunk: string) => {
+        streamed += chunk;
+        onChunk?.(chunk);
+    };
+
+    let response: string;
+    if ((LLM_WINKY_API_MODELS as readonly string[]).includes(llmModel) && authToken) {
+        const modelLevel = llmModel === 'winky-high' ? 'high' : llmModel === 'winky-mid' ? 'mid' : 'low';
+        const result = await winkyLLMStream(
+            {prompt: llmPrompt ? `${llmPrompt}\n\n${input}` : input, model_level: modelLevel},
+            authToken,
+            handleChunk,
+            signal
+        );
+        response = result.content;
+    } else {
+        response = await processLLM(
+            input,
+            llmPrompt,
+            {
+                mode: config.llm.mode,
+                model: llmModel,
+                openaiKey: config.apiKeys.openai,
+                googleKey: config.apiKeys.google,
+                accessToken: authToken
+            },
+            {onChunk: handleChunk, signal}
+        );
+    }
+    return formatLlmResult((response?.trim().length ? response : streamed).trim(), config);
+};
+
+const createTestStreamId = (): string =>
+    typeof crypto !== 'undefined' && typeof crypto.randomUUID === 'function'
+        ? `test-${crypto.randomUUID()}`
+        : `test-${Date.now()}-${Math.random().toString(16).slice(2)}`;
+
+/**
+ * Runs an action end-to-end over a canned transcription for action authoring.
+ * Chunks are broadcast on `action:test-stream` under a `test-` stream id; history is not touched.
+ */
+export const testAction = async (
+    actionId: string,
+    sampleTranscription: string,
+    config: AppConfig,
+    options: ActionLlmOptions = {}
+): Promise<{streamId: string; result: string}> => {
+    const action = findActionOrThrow(config, actionId);
+    const input = sampleTranscription.trim();
+    if (!input) {
+        throw new Error('Sample transcription is empty.');
+    }
+    const streamId = createTestStreamId();
+    const broadcast = (payload: ActionTestStreamEvent) => {
+        emit(ACTION_TEST_STREAM_EVENT, payload).catch((error) => {
+            console.warn('[actionRunner] Failed to emit test stream event:', error);
+        });
+    };
+
+    try {
+        const result = await runActionLlm(action, input, config, {
+            signal: options.signal,
+            onChunk: (chunk) => {
+                options.onChunk?.(chunk);
+                broadcast({streamId, delta: chunk});
+            }
+        });
+        broadcast({streamId, done: true, result});
+        return {streamId, result};
+    } catch (error) {
+        broadcast({streamId, done: true, error: getErrorMessage(error, 'LLM request failed')});
+        throw error;
+    }
+};
@@ -0,0 +1,40 @@
+import type {AppConfig, WinkyNote} from '@shared/types';
+import {findActionOrThrow, runActionLlm} from './actionRunner';
+import {createNoteForMode, deriveNoteTitle, resolveNotesStorageMode} from './notesService';
+
+export type RunActionToNoteParams = {
+    actionId: string;
+    transcription: string;
+    noteTitle?: string | null;
+    config: AppConfig;
+    onChunk?: (chunk: string) => void;
+    signal?: AbortSignal;
+};
+
+/**
+ * Runs the action's LLM prompt over the transcription, streaming chunks as usual,
+ * and stores the final result as a new note in the active notes storage.
+ */
+export const runActionToNote = async ({
+    actionId,
+    transcription,
+    noteTitle,
+    config,
+    onChunk,
+    signal
+}: RunActionToNoteParams): Promise<WinkyNote> => {
+    const action = findActionOrThrow(config, actionId);
+    const input = transcription.trim();
+    if (!input) {
+        throw new Error('Transcription is empty.');
+    }
+
+    const result = await runActionLlm(action, input, config, {onChunk, signal});
+    if (!result) {
+        throw new Error('LLM returned an empty response.');
+    }
+    return createNoteForMode(resolveNotesStorageMode(config), {
+        title: noteTitle?.trim() || deriveNoteTitle(result),
+        description: result
+    });
+};
@@ -1,11 +1,14 @@
 import axios, {AxiosInstance} from 'axios';
 import type {BaseLLMService} from '../BaseLLMService';
+import {acquireRateLimit, type RateLimitedProvider} from '../rateLimit';
 
 export abstract class ApiLLMBaseService implements BaseLLMService {
     protected accessToken?: string;
     protected readonly model: string;
     protected readonly client: AxiosInstance;
     public supportsStreaming: boolean = false;
+    /** Provider key for the shared client-side rate limiter; null disables limiting. */
+    protected readonly rateLimitProvider: RateLimitedProvider | null = null;
 
     protected constructor(model: string, accessToken?: string) {
         this.model = model;
@@ -66,6 +69,7 @@ export abstract class ApiLLMBaseService implements BaseLLMService {
         const body = this.buildBody(text, prompt);
         const headers = this.buildHeaders();
 
+        await acquireRateLimit(this.rateLimitProvider);
         const {data} = await this.client.post(url, body, {headers});
         return this.extractResult(data);
     }
@@ -89,6 +93,7 @@ export abstract class ApiLLMBaseService implements BaseLLMService {
 
         let fullText = '';
 
+        await acquireRateLimit(this.rateLimitProvider);
         const response = await fetch(url, {
             method: 'POST',
             headers: {
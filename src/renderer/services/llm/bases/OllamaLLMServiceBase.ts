@@ -16,7 +16,7 @@ export abstract class OllamaLLMServiceBase extends LocalLLMBaseService {
         text: string,
         prompt: string,
         onChunk: (chunk: string) => void,
-        _options?: {signal?: AbortSignal}
+        options?: {signal?: AbortSignal}
     ): Promise<string> {
         const messages = this.buildMessages(text, prompt);
         const streamId = typeof crypto !== 'undefined' && typeof crypto.randomUUID === 'function'
@@ -39,6 +39,11 @@ export abstract class OllamaLLMServiceBase extends LocalLLMBaseService {
             }
         });
 
+        const onAbort = () => {
+            void ollamaBridge.cancelStream(streamId).catch(() => undefined);
+        };
+        options?.signal?.addEventListener('abort', onAbort, {once: true});
+
         try {
             const data = await ollamaBridge.chatCompletionsStream(this.model, messages, streamId);
             if (typeof data === 'string' && data.length > fullText.length) {
@@ -50,6 +55,7 @@ export abstract class OllamaLLMServiceBase extends LocalLLMBaseService {
             }
             return typeof data === 'string' ? data : fullText;
         } finally {
+            options?.signal?.removeEventListener('abort', onAbort);
             unlisten();
         }
     }
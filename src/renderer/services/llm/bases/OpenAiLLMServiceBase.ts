@@ -1,4 +1,5 @@
 import ApiLLMBaseService from './ApiLLMBaseService';
+import {acquireRateLimit, type RateLimitedProvider} from '../rateLimit';
 
 type OpenAiChatCompletionChunk = {
     choices?: Array<{
@@ -40,6 +41,8 @@ const extractErrorMessage = async (response: Response): Promise<string> => {
 };
 
 export abstract class OpenAiLLMServiceBase extends ApiLLMBaseService {
+    protected readonly rateLimitProvider: RateLimitedProvider | null = 'openai';
+
     protected constructor(model: string, accessToken?: string) {
         super(model, accessToken);
         this.supportsStreaming = true;
@@ -78,6 +81,7 @@ export abstract class OpenAiLLMServiceBase extends ApiLLMBaseService {
             throw new Error('An OpenAI API key is required to use OpenAI models.');
         }
 
+        await acquireRateLimit(this.rateLimitProvider);
         const response = await fetch(this.buildUrl(), {
             method: 'POST',
             headers: this.getHeaders(token),
@@ -103,6 +107,7 @@ export abstract class OpenAiLLMServiceBase extends ApiLLMBaseService {
             throw new Error('An OpenAI API key is required to use OpenAI models.');
         }
 
+        await acquireRateLimit(this.rateLimitProvider);
         const response = await fetch(this.buildUrl(), {
             method: 'POST',
             headers: this.getHeaders(token),
@@ -1,4 +1,5 @@
 import ApiLLMBaseService from '../../bases/ApiLLMBaseService';
+import {acquireRateLimit, type RateLimitedProvider} from '../../rateLimit';
 
 type GeminiResponsePart = {
     text?: string;
@@ -53,6 +54,8 @@ const extractErrorMessage = async (response: Response): Promise<string> => {
 };
 
 class GeminiLLMService extends ApiLLMBaseService {
+    protected readonly rateLimitProvider: RateLimitedProvider | null = 'gemini';
+
     constructor(model: string, apiKey?: string) {
         super(model, apiKey);
         this.supportsStreaming = true;
@@ -116,6 +119,7 @@ class GeminiLLMService extends ApiLLMBaseService {
             throw new Error('Provide a Google AI API key to use this model.');
         }
 
+        await acquireRateLimit(this.rateLimitProvider);
         const response = await fetch(this.buildStreamUrl(token), {
             method: 'POST',
             headers: {
@@ -0,0 +1,35 @@
+import {getApiBaseUrl, LLM_MODES} from '@shared/constants';
+import type {AppConfig} from '@shared/types';
+
+// Меньше типичного idle-таймаута keep-alive у CDN/балансировщиков (~60 с)
+const PREWARM_INTERVAL_MS = 45_000;
+
+/** Origin of the configured LLM provider, or null when there is nothing remote to warm. */
+export const resolveLlmWarmupOrigin = (config: AppConfig): string | null => {
+    if (config.llm.mode !== LLM_MODES.API) {
+        return null;
+    }
+    const model = String(config.llm.model);
+    if (model.startsWith('winky-')) {
+        return new URL(getApiBaseUrl(config.backendDomain)).origin;
+    }
+    if (model.startsWith('gemini')) {
+        return 'https://generativelanguage.googleapis.com';
+    }
+    return 'https://api.openai.com';
+};
+
+/**
+ * Periodically sends a cheap HEAD request to the provider so DNS/TLS and the pooled
+ * connection stay warm for the first real action. Returns a stop function.
+ */
+export const startLlmPrewarm = (origin: string): (() => void) => {
+    const ping = () => {
+        fetch(origin, {method: 'HEAD', mode: 'no-cors', cache: 'no-store'}).catch((error) => {
+            console.debug('[llmPrewarm] Keep-alive ping failed', {origin, error});
+        });
+    };
+    ping();
+    const intervalId = window.setInterval(ping, PREWARM_INTERVAL_MS);
+    return () => window.clearInterval(intervalId);
+};
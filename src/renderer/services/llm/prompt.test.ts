@@ -0,0 +1,25 @@
+import assert from 'node:assert/strict';
+import {describe, it} from 'node:test';
+
+import {stripMarkdown} from './prompt.ts';
+
+describe('stripMarkdown', () => {
+    it('removes emphasis markers', () => {
+        assert.equal(stripMarkdown('**bold** and *italic* and _also_ and __strong__'), 'bold and italic and also and strong');
+    });
+
+    it('keeps underscores inside snake_case words', () => {
+        assert.equal(stripMarkdown('rename foo_bar_baz to my_file_name.txt'), 'rename foo_bar_baz to my_file_name.txt');
+    });
+
+    it('keeps asterisks inside words and formulas', () => {
+        assert.equal(stripMarkdown('a*b*c equals 2*x*y'), 'a*b*c equals 2*x*y');
+    });
+
+    it('removes headings, list markers, links and code', () => {
+        assert.equal(
+            stripMarkdown('# Title\n\n- item with `code`\n- [link](https://example.com)'),
+            'Title\n\nitem with code\nlink'
+        );
+    });
+});
@@ -0,0 +1,45 @@
+import type {ActionConfig, AppConfig, LlmOutputFormat} from '@shared/types';
+
+const OUTPUT_FORMAT_INSTRUCTIONS: Record<LlmOutputFormat, string> = {
+    none: '',
+    text: 'Answer in plain text only: no Markdown, no headings, bullet markers, bold, italics or code fences.',
+    markdown: 'Format the answer as Markdown where structure helps readability.'
+};
+
+const resolveOutputFormat = (config: AppConfig): LlmOutputFormat =>
+    config.llmOutputFormat === 'text' || config.llmOutputFormat === 'markdown' ? config.llmOutputFormat : 'none';
+
+/**
+ * Full LLM system prompt of an action: global prefix, global prompt, action prompt, global suffix
+ * and the output format instruction, if the user picked a format.
+ */
+export const buildLlmPrompt = (action: ActionConfig, config: AppConfig): string => [
+    config.globalPromptPrefix,
+    config.globalLlmPrompt,
+    action.prompt,
+    config.globalPromptSuffix,
+    OUTPUT_FORMAT_INSTRUCTIONS[resolveOutputFormat(config)]
+]
+    .map(part => part?.trim() || '')
+    .filter(part => part.length > 0)
+    .join('\n\n');
+
+/** Removes common Markdown syntax, keeping the text itself. */
+export const stripMarkdown = (text: string): string => text
+    .replace(/^```[^\n]*\n?/gm, '')
+    .replace(/^#{1,6}\s+/gm, '')
+    .replace(/^\s*>\s?/gm, '')
+    .replace(/^(\s*)[-*+]\s+/gm, '$1')
+    .replace(/^\s*([-*_]\s*){3,}$/gm, '')
+    .replace(/!\[([^\]]*)\]\([^)]*\)/g, '$1')
+    .replace(/\[([^\]]+)\]\([^)]*\)/g, '$1')
+    .replace(/(?<!\w)(\*\*|__)(.+?)\1(?!\w)/g, '$2')
+    .replace(/(?<!\w)(\*|_)(\S(?:.*?\S)?)\1(?!\w)/g, '$2')
+    .replace(/~~(.+?)~~/g, '$1')
+    .replace(/`([^`]+)`/g, '$1')
+    .replace(/\n{3,}/g, '\n\n')
+    .trim();
+
+/** Post-processes a final LLM result: models sometimes ignore the plain text instruction. */
+export const formatLlmResult = (text: string, config: AppConfig): string =>
+    resolveOutputFormat(config) === 'text' ? stripMarkdown(text) : text;
@@ -0,0 +1,14 @@
+import {invoke} from '@tauri-apps/api/core';
+
+export type RateLimitedProvider = 'openai' | 'gemini';
+
+/**
+ * Waits for a slot in the backend token bucket for `provider` (configured in
+ * `providerRateLimits`). Rejects with a RATE_LIMITED error when the queue is too long.
+ */
+export const acquireRateLimit = async (provider: RateLimitedProvider | null): Promise<void> => {
+    if (!provider) {
+        return;
+    }
+    await invoke('rate_limit_acquire', {provider});
+};
@@ -4,6 +4,8 @@ import {
     SPEECH_LOCAL_MODEL_ALIASES,
     SPEECH_LOCAL_MODEL_DETAILS
 } from '@shared/constants';
+import type {FastWhisperStatus} from '@shared/types';
+import {localSpeechBridge} from '../winkyBridge/localSpeechBridge';
 
 const localSpeechClient: AxiosInstance = axios.create({
     baseURL: FAST_WHISPER_BASE_URL,
@@ -69,6 +71,19 @@ localSpeechClient.interceptors.response.use(
     }
 );
 
+// Host/port сервера меняются на лету через local_speech_set_runtime; актуальный адрес приходит в статусе
+const applyLocalSpeechBaseUrl = (status: FastWhisperStatus) => {
+    if (status.baseUrl) {
+        localSpeechClient.defaults.baseURL = status.baseUrl;
+    }
+};
+localSpeechBridge.getStatus().then(applyLocalSpeechBaseUrl).catch(() => {
+    /* ignore: остаётся адрес по умолчанию */
+});
+localSpeechBridge.onStatus(applyLocalSpeechBaseUrl);
+
+export const getLocalSpeechBaseUrl = (): string => localSpeechClient.defaults.baseURL || FAST_WHISPER_BASE_URL;
+
 export type LocalModelDownloadResponse = {
     status: 'downloaded' | 'already_present';
     model: string;
@@ -1,5 +1,5 @@
 import type {AppConfig, WinkyNote} from '@shared/types';
-import {notesBridge} from '../winkyBridge/notesBridge';
+import {notesBridge, type NotesPageResponse} from '../winkyBridge/notesBridge';
 import {emitNotesEvent} from './notesEvents';
 import {
     bulkDeleteNotes as bulkDeleteNotesApi,
@@ -16,6 +16,8 @@ export type NotesPage = {
     count: number;
     nextPage: number | null;
     previousPage: number | null;
+    totalPages: number;
+    hasMore: boolean;
     results: WinkyNote[];
 };
 
@@ -43,17 +45,24 @@ const parsePageFromUrl = (value: string | null): number | null => {
     }
 };
 
-const mapApiResponse = (data: NotesListResponse): NotesPage => ({
+// Пустой список всё равно состоит из одной (пустой) страницы
+const countPages = (count: number, pageSize: number): number => Math.max(1, Math.ceil(count / Math.max(1, pageSize)));
+
+const mapApiResponse = (data: NotesListResponse, pageSize: number): NotesPage => ({
     count: data.count,
     nextPage: parsePageFromUrl(data.next),
     previousPage: parsePageFromUrl(data.previous),
+    totalPages: countPages(data.count, pageSize),
+    hasMore: Boolean(data.next),
     results: data.results
 });
 
-const mapLocalResponse = (data: {count: number; next_page: number | null; previous_page: number | null; results: WinkyNote[]}): NotesPage => ({
+const mapLocalResponse = (data: NotesPageResponse, pageSize: number): NotesPage => ({
     count: data.count,
     nextPage: data.next_page,
     previousPage: data.previous_page,
+    totalPages: data.total_pages ?? countPages(data.count, pageSize),
+    hasMore: data.has_more ?? data.next_page !== null,
     results: data.results
 });
 
@@ -64,20 +73,21 @@ export const fetchNotes = async (
 ): Promise<NotesPage> => {
     if (mode === 'local') {
         const localData = await notesBridge.get(page, pageSize);
-        return mapLocalResponse(localData);
+        return mapLocalResponse(localData, pageSize);
     }
     const apiData = await fetchNotesPage(page, pageSize);
-    return mapApiResponse(apiData);
+    return mapApiResponse(apiData, pageSize);
 };
 
 export const createNoteForMode = async (
     mode: NotesStorageMode,
-    payload: {title: string; description?: string; x_username?: string}
+    payload: {title: string; description?: string; x_username?: string; allow_duplicate?: boolean}
 ): Promise<WinkyNote> => {
     if (mode === 'local') {
         return notesBridge.create(payload);
     }
-    const entry = await createNoteApi(payload);
+    const {allow_duplicate: _allowDuplicate, ...apiPayload} = payload;
+    const entry = await createNoteApi(apiPayload);
     emitNotesEvent({type: 'added', mode, entry});
     return entry;
 };
@@ -95,6 +105,22 @@ export const updateNoteForMode = async (
     return entry;
 };
 
+/** Appends dictated text to the end of a note, separated by a blank line. */
+export const appendToNoteForMode = async (
+    mode: NotesStorageMode,
+    note: WinkyNote,
+    text: string
+): Promise<WinkyNote> => {
+    if (mode === 'local') {
+        return notesBridge.append(note.id, text);
+    }
+    const existing = note.description.trimEnd();
+    const addition = text.trim();
+    return updateNoteForMode(mode, note.id, {
+        description: existing ? `${existing}\n\n${addition}` : addition
+    });
+};
+
 export const deleteNoteForMode = async (mode: NotesStorageMode, noteId: string): Promise<void> => {
     if (mode === 'local') {
         return notesBridge.delete(noteId);
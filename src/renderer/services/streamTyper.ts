@@ -0,0 +1,44 @@
+import {clipboardBridge} from '../winkyBridge/clipboardBridge';
+
+export type StreamTyper = {
+    /** Queues a delta; deltas are typed strictly in order. */
+    push: (delta: string) => void;
+    /** Waits for queued deltas; `true` when everything was typed. */
+    finish: () => Promise<boolean>;
+    /** Stops typing: deltas still in the queue are dropped. */
+    cancel: () => void;
+};
+
+/** Types LLM stream deltas into the focused window as they arrive (`paste_stream`). */
+export const createStreamTyper = (): StreamTyper => {
+    let queue: Promise<void> = Promise.resolve();
+    let cancelled = false;
+    let failed = false;
+
+    return {
+        push: (delta) => {
+            if (!delta || cancelled || failed) {
+                return;
+            }
+            queue = queue.then(async () => {
+                if (cancelled || failed) {
+                    return;
+                }
+                try {
+                    await clipboardBridge.typeText(delta);
+                } catch (error) {
+                    // После сбоя дальше не печатаем, иначе в окне окажется текст с дырами
+                    failed = true;
+                    console.error('[streamTyper] Failed to type delta, stopping', error);
+                }
+            });
+        },
+        finish: async () => {
+            await queue;
+            return !cancelled && !failed;
+        },
+        cancel: () => {
+            cancelled = true;
+        }
+    };
+};
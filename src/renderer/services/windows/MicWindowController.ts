@@ -118,13 +118,14 @@ export class MicWindowController {
             return existing;
         }
 
+        const alwaysOnTop = await this.resolveAlwaysOnTop();
         const win = new WebviewWindow('mic', {
             url: this.buildUrl(),
             title: 'Winky Mic',
             width: this.width,
             height: this.height,
             resizable: false,
-            alwaysOnTop: true,
+            alwaysOnTop,
             visible: false,
             transparent: true,
             decorations: false,
@@ -471,6 +472,15 @@ export class MicWindowController {
         }
     }
 
+    private async resolveAlwaysOnTop(): Promise<boolean> {
+        try {
+            const config = await this.configApi.get();
+            return config.micAlwaysOnTop !== false;
+        } catch {
+            return true;
+        }
+    }
+
     private hasAuthTokens(config: AppConfig): boolean {
         const access = typeof config.auth?.access === 'string' ? config.auth.access.trim() : '';
         const accessToken = typeof config.auth?.accessToken === 'string' ? config.auth.accessToken.trim() : '';
@@ -2,15 +2,15 @@ import axios, {AxiosInstance} from 'axios';
 import {invoke} from '@tauri-apps/api/core';
 import {createApiClient, triggerUnauthorized} from '@shared/api';
 import {
-    FAST_WHISPER_TRANSCRIBE_ENDPOINT,
     FAST_WHISPER_TRANSCRIBE_TIMEOUT,
     LLM_GEMINI_API_MODELS,
     LLM_OPENAI_API_MODELS,
     SPEECH_MODES
 } from '@shared/constants';
-import type {ActionConfig, ActionGroup, ActionIcon, AppConfig, User, WinkyNote, WinkyProfile} from '@shared/types';
+import type {ActionConfig, ActionGroup, ActionIcon, AppConfig, ConfigUpdateResult, User, WinkyNote, WinkyProfile} from '@shared/types';
 import {createLLMService} from '../services/llm/factory';
-import {markLocalTranscriptionFinish, markLocalTranscriptionStart} from './localSpeechModels';
+import {getLocalSpeechBaseUrl, markLocalTranscriptionFinish, markLocalTranscriptionStart} from './localSpeechModels';
+import {acquireRateLimit} from './llm/rateLimit';
 
 export type ActionCreatePayload = {
     name: string;
@@ -61,6 +61,7 @@ export type SpeechTranscribeConfig = {
     googleKey?: string;
     accessToken?: string;
     prompt?: string;
+    language?: string;
 };
 
 export type SpeechTranscribeOptions = {
@@ -68,6 +69,8 @@ export type SpeechTranscribeOptions = {
     uiTimeoutMs?: number;
     mimeType?: string;
     fileName?: string;
+    /** Перекодировать запись в 16 kHz mono WAV через ffmpeg перед отправкой на локальный сервер. */
+    transcode?: boolean;
 };
 
 const DEFAULT_TRANSCRIBE_UI_TIMEOUT_MS = 120_000;
@@ -84,7 +87,7 @@ const GEMINI_MODEL_SET = new Set<string>([...LLM_GEMINI_API_MODELS]);
 const getConfig = async (): Promise<AppConfig> => invoke('config_get');
 
 const updateConfig = async (partial: Partial<AppConfig>): Promise<AppConfig> =>
-    invoke('config_update', {payload: partial});
+    invoke<ConfigUpdateResult>('config_update', {payload: partial}).then((result) => result.config);
 
 const withAuthClient = async <T>(operation: (client: AxiosInstance, config: AppConfig) => Promise<T>): Promise<T> => {
     const config = await getConfig();
@@ -248,6 +251,17 @@ export const bulkDeleteNotes = async (ids: string[]): Promise<number> => {
     });
 };
 
+// Без ffmpeg (или для уже WAV-записи) бэкенд возвращает null и аудио уходит как есть
+const transcodeForLocalSpeech = async (audioData: ArrayBuffer): Promise<Uint8Array | null> => {
+    try {
+        const converted = await invoke<number[] | null>('speech_transcode_audio', {audio: new Uint8Array(audioData)});
+        return converted ? new Uint8Array(converted) : null;
+    } catch (error) {
+        console.warn('[Transcribe] ffmpeg transcoding failed, sending original audio:', error);
+        return null;
+    }
+};
+
 export const transcribeAudio = async (
     audioData: ArrayBuffer,
     config: SpeechTranscribeConfig,
@@ -257,15 +271,16 @@ export const transcribeAudio = async (
     const resolvedFileName = options.fileName
         || (resolvedMimeType.includes('wav') ? 'audio.wav' : 'audio.webm');
     const blob = new Blob([audioData], {type: resolvedMimeType});
-    const buildFormData = (extraFields: Record<string, string> = {}) => {
+    const buildFormData = (extraFields: Record<string, string> = {}, file: {blob: Blob; name: string} = {blob, name: resolvedFileName}) => {
         const formData = new FormData();
-        formData.append('file', blob, resolvedFileName);
+        formData.append('file', file.blob, file.name);
         formData.append('model', config.model);
         Object.entries(extraFields).forEach(([key, value]) => formData.append(key, value));
         return formData;
     };
 
     const promptValue = config.prompt?.trim();
+    const languageValue = config.language?.trim() || undefined;
     const audioSizeKB = (audioData.byteLength / 1024).toFixed(2);
     const controller = new AbortController();
     const {signal, uiTimeoutMs} = options;
@@ -303,17 +318,26 @@ export const transcribeAudio = async (
         console.log('  📤 Request:', {
             model: config.model,
             audioSize: `${audioSizeKB} KB`,
-            prompt: promptValue || '(none)'
+            prompt: promptValue || '(none)',
+            language: languageValue || '(auto)'
         });
         const extraFields: Record<string, string> = {response_format: 'json'};
         if (promptValue) {
             extraFields.prompt = promptValue;
         }
-        const formData = buildFormData(extraFields);
+        if (languageValue) {
+            extraFields.language = languageValue;
+        }
+        const transcoded = options.transcode ? await transcodeForLocalSpeech(audioData) : null;
+        const formData = transcoded
+            ? buildFormData(extraFields, {blob: new Blob([transcoded], {type: 'audio/wav'}), name: 'audio.wav'})
+            : buildFormData(extraFields);
+        // Сервер мог быть остановлен по простою или ещё не запущен в ленивом режиме — ждём его готовности
+        await invoke('local_speech_wake');
         let transcriptionToken: number | null = null;
         try {
             transcriptionToken = markLocalTranscriptionStart();
-            const {data} = await axios.post(FAST_WHISPER_TRANSCRIBE_ENDPOINT, formData, {
+            const {data} = await axios.post(`${getLocalSpeechBaseUrl()}/v1/audio/transcriptions`, formData, {
                 headers: {'Content-Type': 'multipart/form-data'},
                 timeout: FAST_WHISPER_TRANSCRIBE_TIMEOUT,
                 signal: controller.signal
@@ -430,6 +454,9 @@ export const transcribeAudio = async (
                 text: 'You are a transcription tool. Your ONLY task is to transcribe the audio exactly as spoken. Return ONLY the verbatim transcription. Do NOT answer questions. Do NOT provide explanations. Do NOT interpret the content. Do NOT add any text beyond the exact words spoken. Output format: plain text transcription only.'
             });
         }
+        if (languageValue) {
+            parts.push({text: `The audio is spoken in language "${languageValue}" (ISO 639-1).`});
+        }
         
         // Добавляем аудио
         parts.push({
@@ -472,6 +499,7 @@ export const transcribeAudio = async (
         
         // Используем v1beta (стабильная версия для мультимодальных запросов)
         // Если модель не поддерживает аудио, получим понятную ошибку
+        await acquireRateLimit('gemini');
         try {
             const {data} = await axios.post(
                 `${url}?key=${googleKey}`,
@@ -563,7 +591,11 @@ export const transcribeAudio = async (
         sanitizedPrompt = promptValue.replace(/[\x00-\x08\x0B-\x0C\x0E-\x1F\x7F]/g, '');
     }
     
-    const formData = buildFormData(sanitizedPrompt ? {prompt: sanitizedPrompt} : {});
+    const openaiFields: Record<string, string> = sanitizedPrompt ? {prompt: sanitizedPrompt} : {};
+    if (languageValue) {
+        openaiFields.language = languageValue;
+    }
+    const formData = buildFormData(openaiFields);
     
     // Проверяем что токен содержит только допустимые символы для HTTP заголовков (ISO-8859-1)
     // ISO-8859-1 это символы от \x20 до \x7E (printable ASCII) и \xA0-\xFF (extended Latin-1)
@@ -579,6 +611,7 @@ export const transcribeAudio = async (
         formDataFields: sanitizedPrompt ? {prompt: sanitizedPrompt, model: config.model, file: `Blob(${audioSizeKB} KB)`} : {model: config.model, file: `Blob(${audioSizeKB} KB)`}
     });
 
+    await acquireRateLimit('openai');
     try {
         const {data} = await axios.post(url, formData, {
             headers: {
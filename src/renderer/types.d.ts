@@ -18,7 +18,7 @@ declare global {
 
         update(payload: Partial<AppConfig>): Promise<AppConfig>;
 
-        setAuth(tokens: AuthTokens): Promise<AppConfig>;
+        setAuth(tokens: AuthTokens, provider?: string): Promise<AppConfig>;
 
         reset(): Promise<AppConfig>;
 
@@ -29,6 +29,8 @@ declare global {
 
     interface WinkyClipboardAPI {
         writeText(text: string): Promise<boolean>;
+
+        insertText(text: string): Promise<void>;
     }
 
     interface WinkyAuthAPI {
@@ -263,6 +265,8 @@ declare global {
             { type: 'added'; entry: ActionHistoryEntry }
             | { type: 'updated'; entry: ActionHistoryEntry }
             | { type: 'cleared' }
+            | { type: 'repaired'; recovered: number }
+            | { type: 'audio-pruned'; ids: string[] }
         ) => void): () => void;
     }
 
@@ -1,4 +1,10 @@
+import type {CommandErrorCode} from '@shared/types';
+
 export const getErrorMessage = (error: any, fallback: string): string => {
+    if (typeof error === 'string') {
+        return error || fallback;
+    }
+
     if (typeof error?.response?.data?.detail === 'string') {
         return error.response.data.detail;
     }
@@ -18,3 +24,10 @@ export const getErrorMessage = (error: any, fallback: string): string => {
 
     return fallback;
 };
+
+/** Stable error code from a backend command failure, if the command returned one. */
+export const getErrorCode = (error: any): CommandErrorCode | null => {
+    return typeof error?.code === 'string' && typeof error?.message === 'string'
+        ? (error.code as CommandErrorCode)
+        : null;
+};
@@ -256,6 +256,15 @@ const HistoryPage: React.FC = () => {
                 setEntries([]);
                 return;
             }
+            if (event.type === 'repaired') {
+                void loadHistory();
+                return;
+            }
+            if (event.type === 'audio-pruned') {
+                const pruned = new Set(event.ids);
+                setEntries((prev) => prev.map((entry) => (pruned.has(entry.id) ? {...entry, audio_path: null} : entry)));
+                return;
+            }
             if (event.type === 'added') {
                 setEntries((prev) => {
                     if (prev.some((entry) => entry.id === event.entry.id)) {
@@ -12,9 +12,11 @@ import {
     SPEECH_API_MODELS,
     SPEECH_MODES
 } from '@shared/constants';
-import type {BackendDomain} from '@shared/types';
+import type {AudioOutputDevice, BackendDomain, CompletionBehavior} from '@shared/types';
 import ModelConfigForm, {ModelConfigFormData} from '../components/ModelConfigForm';
 import HotkeyInput from '../components/HotkeyInput';
+import {configBridge, resourcesBridge} from '../services/winkyBridge';
+import {getErrorMessage} from '../utils/errorMessage';
 import BackendDomainSelect from '../components/BackendDomainSelect';
 
 const DEFAULT_MIC_HOTKEY = 'Alt+Q';
@@ -41,6 +43,7 @@ const SettingsPage: React.FC = () => {
     const [micShowOnLaunch, setMicShowOnLaunch] = useState(false);
     const [launchOnSystemStartup, setLaunchOnSystemStartup] = useState(false);
     const [autoStartLocalSpeech, setAutoStartLocalSpeech] = useState(false);
+    const [lazyStartLocalSpeech, setLazyStartLocalSpeech] = useState(false);
     const [completionSoundEnabled, setCompletionSoundEnabled] = useState(true);
     const [completionSoundVolume, setCompletionSoundVolume] = useState(1.0);
     const [showAvatarVideo, setShowAvatarVideo] = useState(true);
@@ -48,6 +51,9 @@ const SettingsPage: React.FC = () => {
     const [trimSilenceOnActions, setTrimSilenceOnActions] = useState(false);
     const [backendDomain, setBackendDomainState] = useState<BackendDomain>(getBackendDomain());
     const [audioDevices, setAudioDevices] = useState<MediaDeviceInfo[]>([]);
+    const [outputDevices, setOutputDevices] = useState<AudioOutputDevice[]>([]);
+    const [globalPromptPrefix, setGlobalPromptPrefix] = useState('');
+    const [globalPromptSuffix, setGlobalPromptSuffix] = useState('');
 
     useEffect(() => {
         if (config) {
@@ -69,10 +75,13 @@ const SettingsPage: React.FC = () => {
             setCompletionSoundVolume(config.completionSoundVolume ?? 1.0);
             setLaunchOnSystemStartup(Boolean(config.launchOnSystemStartup));
             setAutoStartLocalSpeech(Boolean(config.autoStartLocalSpeechServer));
+            setLazyStartLocalSpeech(Boolean(config.localSpeechLazyStart));
             setShowAvatarVideo(config.showAvatarVideo !== false);
             setSaveAudioHistory(Boolean(config.saveAudioHistory));
             setTrimSilenceOnActions(Boolean(config.trimSilenceOnActions));
             setBackendDomainState(config.backendDomain);
+            setGlobalPromptPrefix(config.globalPromptPrefix ?? '');
+            setGlobalPromptSuffix(config.globalPromptSuffix ?? '');
         }
     }, [config]);
 
@@ -87,10 +96,20 @@ const SettingsPage: React.FC = () => {
             }
         };
 
+        const loadOutputDevices = async () => {
+            try {
+                setOutputDevices(await resourcesBridge.listOutputDevices());
+            } catch (error) {
+                console.error('[SettingsPage] Failed to enumerate output devices', error);
+            }
+        };
+
         void loadAudioDevices();
+        void loadOutputDevices();
 
         const handleDeviceChange = () => {
             void loadAudioDevices();
+            void loadOutputDevices();
         };
 
         navigator.mediaDevices.addEventListener('devicechange', handleDeviceChange);
@@ -251,6 +270,25 @@ const SettingsPage: React.FC = () => {
         setCompletionSoundVolume(nextValue);
     };
 
+    const handlePlayTestSound = async () => {
+        try {
+            await resourcesBridge.playTestSound(null, completionSoundVolume);
+        } catch (error) {
+            console.error('[SettingsPage] Failed to play test sound', error);
+            showToast(getErrorMessage(error, 'Failed to play test sound.'), 'error');
+        }
+    };
+
+    const handleClearLogs = async () => {
+        try {
+            await configBridge.clearLogs();
+            showToast('Logs cleared.', 'success');
+        } catch (error) {
+            console.error('[SettingsPage] Failed to clear logs', error);
+            showToast(getErrorMessage(error, 'Failed to clear logs.'), 'error');
+        }
+    };
+
     const handleCompletionSoundToggle = async (event: ChangeEvent<HTMLInputElement>) => {
         const nextValue = event.target.checked;
         const previousValue = completionSoundEnabled;
@@ -308,6 +346,17 @@ const SettingsPage: React.FC = () => {
         }
     };
 
+    const handleAutoHideDelayChange = async (event: ChangeEvent<HTMLInputElement>) => {
+        const parsed = Number.parseInt(event.target.value, 10);
+        const nextValue = Number.isFinite(parsed) ? Math.max(0, parsed) : 0;
+        try {
+            await updateConfig({micAutoHideDelayMs: nextValue});
+        } catch (error) {
+            console.error('[SettingsPage] Failed to update mic auto-hide delay', error);
+            showToast('Failed to update microphone behaviour.', 'error');
+        }
+    };
+
     const handleHideOnStopChange = async (event: ChangeEvent<HTMLInputElement>) => {
         const nextValue = event.target.checked;
         const previousValue = micHideOnStop;
@@ -387,6 +436,25 @@ const SettingsPage: React.FC = () => {
         }
     };
 
+    const handleLocalSpeechLazyStartChange = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.checked;
+        const previousValue = lazyStartLocalSpeech;
+        setLazyStartLocalSpeech(nextValue);
+        try {
+            await updateConfig({localSpeechLazyStart: nextValue});
+            showToast(
+                nextValue
+                    ? 'Local speech server will start on your first dictation.'
+                    : 'Local speech lazy start disabled.',
+                'success'
+            );
+        } catch (error) {
+            console.error('[SettingsPage] Failed to update local speech lazy start', error);
+            setLazyStartLocalSpeech(previousValue);
+            showToast('Failed to update the local speech lazy start setting.', 'error');
+        }
+    };
+
     const handleAvatarVideoToggle = async (event: ChangeEvent<HTMLInputElement>) => {
         const nextValue = event.target.checked;
         const previousValue = showAvatarVideo;
@@ -435,6 +503,49 @@ const SettingsPage: React.FC = () => {
         }
     };
 
+    const handlePrewarmLlmToggle = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.checked;
+        try {
+            await updateConfig({prewarmLlm: nextValue});
+        } catch (error) {
+            console.error('[SettingsPage] Failed to toggle LLM prewarm', error);
+            showToast('Failed to update LLM prewarm setting.', 'error');
+        }
+    };
+
+    const handleGlobalPromptSave = async (field: 'globalPromptPrefix' | 'globalPromptSuffix', value: string) => {
+        const nextValue = value.trim() || null;
+        if (nextValue === (config?.[field] ?? null)) {
+            return;
+        }
+        try {
+            await updateConfig({[field]: nextValue});
+        } catch (error) {
+            console.error('[SettingsPage] Failed to update global prompt wrapper', error);
+            showToast('Failed to update global prompt.', 'error');
+        }
+    };
+
+    const handleCloseToTrayToggle = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.checked;
+        try {
+            await updateConfig({closeToTray: nextValue});
+        } catch (error) {
+            console.error('[SettingsPage] Failed to toggle close to tray', error);
+            showToast('Failed to update close button behavior.', 'error');
+        }
+    };
+
+    const handleMinimizeToTrayToggle = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.checked;
+        try {
+            await updateConfig({minimizeToTray: nextValue});
+        } catch (error) {
+            console.error('[SettingsPage] Failed to toggle minimize to tray', error);
+            showToast('Failed to update minimize button behavior.', 'error');
+        }
+    };
+
     const handleBackendDomainChange = async (nextValue: BackendDomain) => {
         if (nextValue === backendDomain) return;
         const previousValue = backendDomain;
@@ -463,6 +574,28 @@ const SettingsPage: React.FC = () => {
         }
     };
 
+    const handleOutputDeviceChange = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.value;
+        try {
+            await updateConfig({soundOutputDevice: nextValue === 'default' ? null : nextValue});
+            showToast(`Sound output changed to: ${nextValue === 'default' ? 'Default' : nextValue}`, 'success');
+        } catch (error) {
+            console.error('[SettingsPage] Failed to update sound output device', error);
+            showToast('Failed to update sound output device.', 'error');
+        }
+    };
+
+    const handleCompletionBehaviorChange = async (event: ChangeEvent<HTMLInputElement>) => {
+        const nextValue = event.target.value as CompletionBehavior;
+        try {
+            await updateConfig({completionBehavior: nextValue});
+            showToast('Completion behavior updated.', 'success');
+        } catch (error) {
+            console.error('[SettingsPage] Failed to update completion behavior', error);
+            showToast('Failed to update completion behavior.', 'error');
+        }
+    };
+
     if (!isAuthorized) {
         return (
             <div className="fccc mx-auto h-full w-full max-w-md gap-4 px-8 py-12 text-center">
@@ -540,6 +673,22 @@ const SettingsPage: React.FC = () => {
                     </Typography>
                 </div>
 
+                <div className={'fc gap-2'}>
+                    <FormControlLabel
+                        control={
+                            <Checkbox
+                                checked={lazyStartLocalSpeech}
+                                onChange={handleLocalSpeechLazyStartChange}
+                            />
+                        }
+                        label="Start local Whisper server on first dictation"
+                    />
+                    <Typography sx={{mt: -1}} variant="caption" color="text.secondary">
+                        The server stays off until you dictate in Local speech mode; the first transcription waits
+                        while it starts.
+                    </Typography>
+                </div>
+
                 <div className={'fc gap-2'}>
                     <FormControlLabel
                         control={
@@ -617,6 +766,21 @@ const SettingsPage: React.FC = () => {
                         </Typography>
                     </div>
 
+                    <div className={'fc gap-2'}>
+                        <TextField
+                            type="number"
+                            label="Auto-hide delay (ms)"
+                            value={config?.micAutoHideDelayMs ?? 0}
+                            onChange={handleAutoHideDelayChange}
+                            inputProps={{min: 0, step: 100}}
+                            size="small"
+                        />
+                        <Typography variant="caption" color="text.secondary">
+                            Hide the mic overlay this long after recording stops, unless you start recording again.
+                            Set to 0 to follow the option above.
+                        </Typography>
+                    </div>
+
                     <div className={'fc gap-2'}>
                         <FormControlLabel
                             control={<Checkbox checked={micShowOnLaunch} onChange={handleShowOnLaunchChange}/>}
@@ -656,6 +820,51 @@ const SettingsPage: React.FC = () => {
                     <Typography variant="caption" color="text.secondary">
                         Adjust the volume of the sound that plays when an action completes. Set to 0% to disable.
                     </Typography>
+                    <Button variant="outlined" size="small" sx={{alignSelf: 'flex-start', mt: 1}} onClick={handlePlayTestSound}>
+                        Play test sound
+                    </Button>
+                </div>
+
+                <div className={'fc gap-2'}>
+                    <Typography variant="body2" fontWeight={600} color="text.primary">
+                        Sound Output Device
+                    </Typography>
+                    <TextField
+                        select
+                        value={config?.soundOutputDevice || 'default'}
+                        onChange={handleOutputDeviceChange}
+                        fullWidth
+                    >
+                        <MenuItem value="default">Default</MenuItem>
+                        {outputDevices.map(device => (
+                            <MenuItem key={device.id} value={device.name}>
+                                {device.name}
+                            </MenuItem>
+                        ))}
+                    </TextField>
+                    <Typography variant="caption" color="text.secondary">
+                        Select where completion sounds are played.
+                    </Typography>
+                </div>
+
+                <div className={'fc gap-2'}>
+                    <Typography variant="body2" fontWeight={600} color="text.primary">
+                        After an action completes
+                    </Typography>
+                    <TextField
+                        select
+                        value={config?.completionBehavior ?? 'copy_and_notify'}
+                        onChange={handleCompletionBehaviorChange}
+                        fullWidth
+                    >
+                        <MenuItem value="copy_and_notify">Copy to clipboard and notify</MenuItem>
+                        <MenuItem value="copy">Copy to clipboard silently</MenuItem>
+                        <MenuItem value="paste">Paste into the active window</MenuItem>
+                        <MenuItem value="paste_stream">Type into the active window as it is generated</MenuItem>
+                    </TextField>
+                    <Typography variant="caption" color="text.secondary">
+                        Applies to actions with "Copy result to clipboard" enabled.
+                    </Typography>
                 </div>
             </Box>
 
@@ -703,6 +912,60 @@ const SettingsPage: React.FC = () => {
                     </Typography>
                 </div>
 
+                <div className={'fc gap-2'}>
+                    <FormControlLabel
+                        control={<Checkbox checked={config?.prewarmLlm === true} onChange={handlePrewarmLlmToggle}/>}
+                        label="Keep LLM connection warm"
+                    />
+                    <Typography sx={{mt: -1}} variant="caption" color="text.secondary">
+                        Periodically pings the LLM provider while the mic overlay is open so the first action responds faster.
+                    </Typography>
+                </div>
+
+                <div className={'fc gap-2'}>
+                    <TextField
+                        label="Global prompt prefix"
+                        value={globalPromptPrefix}
+                        onChange={(event) => setGlobalPromptPrefix(event.target.value)}
+                        onBlur={() => void handleGlobalPromptSave('globalPromptPrefix', globalPromptPrefix)}
+                        multiline
+                        minRows={2}
+                        size="small"
+                    />
+                    <TextField
+                        label="Global prompt suffix"
+                        value={globalPromptSuffix}
+                        onChange={(event) => setGlobalPromptSuffix(event.target.value)}
+                        onBlur={() => void handleGlobalPromptSave('globalPromptSuffix', globalPromptSuffix)}
+                        multiline
+                        minRows={2}
+                        size="small"
+                    />
+                    <Typography variant="caption" color="text.secondary">
+                        Added before and after the prompt of every action, e.g. "Always respond in English".
+                    </Typography>
+                </div>
+
+                <div className={'fc gap-2'}>
+                    <FormControlLabel
+                        control={<Checkbox checked={config?.closeToTray !== false} onChange={handleCloseToTrayToggle}/>}
+                        label="Close to tray"
+                    />
+                    <Typography sx={{mt: -1}} variant="caption" color="text.secondary">
+                        When disabled, the window close button quits Winky.
+                    </Typography>
+                </div>
+
+                <div className={'fc gap-2'}>
+                    <FormControlLabel
+                        control={<Checkbox checked={config?.minimizeToTray === true} onChange={handleMinimizeToTrayToggle}/>}
+                        label="Minimize to tray"
+                    />
+                    <Typography sx={{mt: -1}} variant="caption" color="text.secondary">
+                        Hides the window from the taskbar when it is minimized.
+                    </Typography>
+                </div>
+
                 <div className={'fc gap-2'}>
                     <Typography variant="body2" fontWeight={600} color="text.primary">
                         Microphone Device
@@ -724,6 +987,18 @@ const SettingsPage: React.FC = () => {
                         Select which microphone to use for recording. Changes take effect immediately.
                     </Typography>
                 </div>
+
+                <div className={'fc gap-2'}>
+                    <Typography variant="body2" fontWeight={600} color="text.primary">
+                        Logs
+                    </Typography>
+                    <Button variant="outlined" size="small" sx={{alignSelf: 'flex-start'}} onClick={handleClearLogs}>
+                        Clear logs
+                    </Button>
+                    <Typography variant="caption" color="text.secondary">
+                        Start from a clean log before reproducing an issue for a bug report.
+                    </Typography>
+                </div>
             </Box>
         </div>
     );
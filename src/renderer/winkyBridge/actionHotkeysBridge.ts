@@ -1,8 +1,12 @@
 import {invoke} from '@tauri-apps/api/core';
+import type {AppConfig} from '@shared/types';
 
 export const actionHotkeysBridge = {
     register: (hotkeys: Array<{id: string; accelerator: string}>) =>
         invoke('action_hotkeys_register', {hotkeys}),
     clear: () => invoke('action_hotkeys_clear'),
-    setRecordingActive: (active: boolean) => invoke('hotkeys_set_recording_active', {active})
+    setRecordingActive: (active: boolean) => invoke('hotkeys_set_recording_active', {active}),
+    reserved: (): Promise<Array<{accelerator: string; description: string}>> => invoke('hotkeys_reserved'),
+    /** Restores the default mic hotkey and removes every action hotkey; resolves with the saved config. */
+    resetAll: (): Promise<AppConfig> => invoke('hotkeys_reset')
 };
@@ -1,4 +1,5 @@
-import type {ActionConfig, ActionIcon} from '@shared/types';
+import {invoke} from '@tauri-apps/api/core';
+import type {ActionConfig, ActionIcon, AppConfig} from '@shared/types';
 import {
     createAction,
     updateAction,
@@ -11,7 +12,12 @@ import {
 export const actionsBridge = {
     create: (payload: ActionCreatePayload): Promise<ActionConfig[]> => createAction(payload),
     update: (id: string, payload: ActionUpdatePayload): Promise<ActionConfig[]> => updateAction(id, payload),
-    delete: (id: string): Promise<ActionConfig[]> => deleteAction(id)
+    delete: (id: string): Promise<ActionConfig[]> => deleteAction(id),
+    getLanguage: (id: string): Promise<string | null> => invoke('actions_get_language', {id}),
+    setLanguage: (id: string, language: string | null): Promise<AppConfig> =>
+        invoke('actions_set_language', {id, language}),
+    setCompletionSound: (id: string, sound: string | null): Promise<AppConfig> =>
+        invoke('actions_set_completion_sound', {id, sound})
 };
 
 export const iconsBridge = {
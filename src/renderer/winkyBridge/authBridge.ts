@@ -1,6 +1,6 @@
 import {invoke} from '@tauri-apps/api/core';
 import {listen, type UnlistenFn} from '@tauri-apps/api/event';
-import type {AuthDeepLinkPayload} from '@shared/types';
+import type {AppConfig, AuthChangedEvent, AuthDeepLinkPayload} from '@shared/types';
 
 const listeners = new Set<(payload: AuthDeepLinkPayload) => void>();
 let unlisten: UnlistenFn | null = null;
@@ -22,5 +22,33 @@ export const authBridge = {
         return () => listeners.delete(callback);
     },
     consumePendingOAuthPayloads: (): Promise<AuthDeepLinkPayload[]> => invoke('auth_consume_pending'),
-    isRunningAsAdmin: (): Promise<boolean> => invoke('auth_is_admin')
+    peekPendingOAuthPayloads: (): Promise<AuthDeepLinkPayload[]> => invoke('auth_peek_pending'),
+    isRunningAsAdmin: (): Promise<boolean> => invoke('auth_is_admin'),
+    setOAuthDebug: (enable: boolean): Promise<void> => invoke('oauth_debug', {enable}),
+    getOAuthStartUrl: (provider: string): Promise<string> => invoke('oauth_start_url', {provider}),
+    logout: (): Promise<AppConfig> => invoke('auth_logout'),
+    onAuthChanged: (callback: (event: AuthChangedEvent) => void): (() => void) => {
+        let stopped = false;
+        const unlistenPromise = listen<AuthChangedEvent>('auth:changed', (event) => {
+            if (!stopped) {
+                callback(event.payload);
+            }
+        }).catch((error) => {
+            console.warn('[authBridge] Failed to subscribe to auth changes:', error);
+            return null;
+        });
+
+        return () => {
+            stopped = true;
+            unlistenPromise
+                .then((unlistenFn) => {
+                    if (typeof unlistenFn === 'function') {
+                        unlistenFn();
+                    }
+                })
+                .catch(() => {
+                    /* ignore */
+                });
+        };
+    }
 };
@@ -1,3 +1,4 @@
+import {invoke} from '@tauri-apps/api/core';
 import {writeText as writeClipboardText} from '@tauri-apps/plugin-clipboard-manager';
 
 export const clipboardBridge = {
@@ -26,5 +27,7 @@ export const clipboardBridge = {
 
         console.error('[clipboardBridge] All clipboard methods failed');
         return false;
-    }
+    },
+    insertText: (text: string): Promise<void> => invoke('insert_text', {text}),
+    typeText: (text: string): Promise<void> => invoke('type_text', {text})
 };
@@ -1,14 +1,35 @@
 import {invoke} from '@tauri-apps/api/core';
 import {listen} from '@tauri-apps/api/event';
-import type {AppConfig} from '@shared/types';
+import type {AppConfig, ConfigIntegrity, ConfigUpdateResult, OverlayTheme, SystemDependency} from '@shared/types';
 
 export const configBridge = {
     get: (): Promise<AppConfig> => invoke('config_get'),
-    update: (payload: Partial<AppConfig>): Promise<AppConfig> => invoke('config_update', {payload}),
-    setAuth: (tokens: AppConfig['auth']): Promise<AppConfig> => invoke('config_set_auth', {tokens}),
+    update: (payload: Partial<AppConfig>): Promise<AppConfig> =>
+        invoke<ConfigUpdateResult>('config_update', {payload}).then((result) => result.config),
+    updateDetailed: (payload: Partial<AppConfig>): Promise<ConfigUpdateResult> => invoke('config_update', {payload}),
+    setAuth: (tokens: AppConfig['auth'], provider?: string): Promise<AppConfig> =>
+        invoke('config_set_auth', {tokens, provider}),
     reset: (): Promise<AppConfig> => invoke('config_reset'),
+    markOnboardingStep: (id: string): Promise<Record<string, string>> => invoke('onboarding_mark_step', {id}),
+    resetOnboarding: (): Promise<void> => invoke('onboarding_reset'),
     path: (): Promise<string> => invoke('config_path'),
+    verifyIntegrity: (): Promise<ConfigIntegrity> => invoke('config_verify_integrity'),
+    fieldDefault: <T = unknown>(path: string): Promise<T> => invoke('config_field_default', {path}),
+    systemDependencies: (): Promise<SystemDependency[]> => invoke('system_dependencies'),
+    exportDiagnostics: (dir: string): Promise<string> => invoke('diagnostics_bundle', {path: dir}),
     getLogFilePath: (): Promise<string> => invoke('get_log_file_path'),
+    clearLogs: (): Promise<void> => invoke('logs_clear'),
+    rotateLogs: (): Promise<void> => invoke('logs_rotate'),
+    overlayThemes: (): Promise<OverlayTheme[]> => invoke('overlay_themes'),
+    setOverlayTheme: (theme: OverlayTheme): Promise<AppConfig> => invoke('overlay_set_theme', {theme}),
+    onOverlayThemeChanged: (callback: (theme: OverlayTheme) => void) => {
+        const unlistenPromise = listen<{theme: OverlayTheme}>('overlay:theme-changed', (event) =>
+            callback(event.payload.theme)
+        );
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
+        };
+    },
     subscribe: (callback: (config: AppConfig) => void): (() => void) => {
         let stopped = false;
         const unlistenPromise = listen<AppConfig>('config:updated', (event) => {
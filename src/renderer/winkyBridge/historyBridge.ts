@@ -2,10 +2,26 @@ import {invoke} from '@tauri-apps/api/core';
 import {listen} from '@tauri-apps/api/event';
 import type {ActionHistoryEntry} from '@shared/types';
 
+export type RepairReport = {
+    was_corrupt: boolean;
+    recovered: number;
+    discarded: number;
+    backup_path: string | null;
+};
+
 export type HistoryUpdateEvent =
     | { type: 'added'; entry: ActionHistoryEntry }
     | { type: 'updated'; entry: ActionHistoryEntry }
-    | { type: 'cleared' };
+    | { type: 'cleared' }
+    | { type: 'repaired'; recovered: number }
+    | { type: 'audio-pruned'; ids: string[] };
+
+export type HistoryPageResponse = {
+    count: number;
+    next_page: number | null;
+    previous_page: number | null;
+    results: ActionHistoryEntry[];
+};
 
 type HistoryAddPayload = {
     action_id: string;
@@ -33,6 +49,11 @@ type HistoryUpdatePayload = {
 
 export const historyBridge = {
     get: (): Promise<ActionHistoryEntry[]> => invoke('history_get'),
+    /** Newest first; `query` matches action name, transcription, LLM response and result text. */
+    list: (page = 1, pageSize = 20, query?: string): Promise<HistoryPageResponse> =>
+        invoke('history_list', {page, pageSize, query: query?.trim() || undefined}),
+    repair: (): Promise<RepairReport> => invoke('history_repair'),
+    count: (): Promise<number> => invoke('history_count'),
     add: (payload: HistoryAddPayload): Promise<ActionHistoryEntry> =>
         invoke('history_add', {payload}),
     update: (payload: HistoryUpdatePayload): Promise<ActionHistoryEntry> =>
@@ -43,7 +64,20 @@ export const historyBridge = {
     },
     readAudio: (audioPath: string): Promise<Uint8Array> =>
         invoke('history_read_audio', {payload: {audioPath}}),
+    exportAudio: (dir: string, ids?: string[]): Promise<string[]> =>
+        invoke('history_export_audio', {dir, ids: ids ?? null}),
+    exportMarkdown: (
+        options: {actionId?: string | null; since?: string | null; until?: string | null} = {}
+    ): Promise<string> =>
+        invoke('history_export_markdown', {
+            actionId: options.actionId ?? null,
+            since: options.since ?? null,
+            until: options.until ?? null
+        }),
     clear: (): Promise<void> => invoke('history_clear'),
+    pruneAudio: (): Promise<string[]> => invoke('history_prune_audio'),
+    retranscribe: (id: string, provider: 'local' | 'openai', model?: string): Promise<ActionHistoryEntry> =>
+        invoke('retranscribe_history', {id, provider, model}),
     subscribe: (callback: (event: HistoryUpdateEvent) => void): (() => void) => {
         let stopped = false;
         const unlistenPromise = listen<HistoryUpdateEvent>('history:updated', (event) => {
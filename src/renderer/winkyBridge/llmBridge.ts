@@ -1,4 +1,8 @@
+import {invoke} from '@tauri-apps/api/core';
+import type {AppConfig, BackendReadiness, LLMMode, LLMModel, LlmOutputFormat, WinkyNote} from '@shared/types';
 import {processLLM} from '../services/winkyApi';
+import {runActionToNote} from '../services/actionToNote';
+import {testAction} from '../services/actionRunner';
 
 export const llmBridge = {
     process: (
@@ -6,5 +10,25 @@ export const llmBridge = {
         prompt: string,
         config: {mode: string; model: string; openaiKey?: string; googleKey?: string; accessToken?: string},
         options?: { onChunk?: (chunk: string) => void; signal?: AbortSignal }
-    ) => processLLM(text, prompt, config, options)
+    ) => processLLM(text, prompt, config, options),
+    runActionToNote: (
+        actionId: string,
+        transcription: string,
+        noteTitle: string | null,
+        config: AppConfig,
+        options?: { onChunk?: (chunk: string) => void; signal?: AbortSignal }
+    ): Promise<WinkyNote> => runActionToNote({actionId, transcription, noteTitle, config, ...options}),
+    testAction: (
+        actionId: string,
+        sampleTranscription: string,
+        config: AppConfig,
+        options?: { onChunk?: (chunk: string) => void; signal?: AbortSignal }
+    ): Promise<{streamId: string; result: string}> => testAction(actionId, sampleTranscription, config, options),
+    setModel: (mode: LLMMode, model: LLMModel | string): Promise<AppConfig> =>
+        invoke('llm_set_model', {mode, model}),
+    cancel: (streamId: string): Promise<boolean> => invoke('llm_cancel', {streamId}),
+    cancelAll: (): Promise<string[]> => invoke('llm_cancel_all'),
+    /** Pings the LLM provider, loads the Ollama model and starts the local speech server, as configured. */
+    warmupAll: (): Promise<BackendReadiness[]> => invoke('warmup_all'),
+    setOutputFormat: (format: LlmOutputFormat): Promise<AppConfig> => invoke('llm_set_output_format', {format})
 };
@@ -2,6 +2,35 @@ import {invoke} from '@tauri-apps/api/core';
 import {listen} from '@tauri-apps/api/event';
 import type {FastWhisperStatus} from '@shared/types';
 
+export type LocalSpeechLogLine = {
+    seq: number;
+    source: string;
+    line: string;
+    timestamp: number;
+};
+
+export type LocalSpeechHealthEvent = {
+    healthy: boolean;
+    previous: boolean;
+    timestamp: number;
+};
+
+export type LocalSpeechRepositoryVerification = {
+    repoPath: string;
+    valid: boolean;
+    issues: Array<{path: string; problem: string}>;
+    suggestion: 'reinstall' | null;
+};
+
+export type LocalSpeechInfo = {
+    model: string | null;
+    device: string | null;
+    computeType: string | null;
+    languages: string[];
+    source: 'info' | 'health';
+    raw: unknown;
+};
+
 export const localSpeechBridge = {
     getStatus: (): Promise<FastWhisperStatus> => invoke('local_speech_get_status'),
     checkHealth: (): Promise<FastWhisperStatus> => invoke('local_speech_check_health'),
@@ -12,6 +41,19 @@ export const localSpeechBridge = {
     reinstall: (targetDir?: string): Promise<FastWhisperStatus> =>
         invoke('local_speech_reinstall', {args: {targetDir}}),
     stop: (): Promise<FastWhisperStatus> => invoke('local_speech_stop'),
+    verify: (): Promise<LocalSpeechRepositoryVerification> => invoke('local_speech_verify'),
+    info: (): Promise<LocalSpeechInfo> => invoke('local_speech_info'),
+    wake: (): Promise<FastWhisperStatus> => invoke('local_speech_wake'),
+    abortInstall: (): Promise<boolean> => invoke('local_speech_abort_install'),
+    setRuntime: (host: string, port: number): Promise<FastWhisperStatus> =>
+        invoke('local_speech_set_runtime', {host, port}),
+    setInstallDir: (dir: string | null, migrate: boolean): Promise<FastWhisperStatus> =>
+        invoke('local_speech_set_install_dir', {dir, migrate}),
+    resourceUsage: (): Promise<{pid: number; cpu_percent: number; memory_mb: number} | null> =>
+        invoke('local_speech_resource_usage'),
+    listProcesses: (): Promise<Array<{pid: number; command_line: string}>> =>
+        invoke('local_speech_list_processes'),
+    killStrays: (): Promise<number[]> => invoke('local_speech_kill_strays'),
     isModelDownloaded: (model: string): Promise<boolean> =>
         invoke('local_speech_check_model_downloaded', {model}),
     onStatus: (callback: (status: FastWhisperStatus) => void) => {
@@ -21,5 +63,21 @@ export const localSpeechBridge = {
         return () => {
             unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
         };
+    },
+    onHealth: (callback: (event: LocalSpeechHealthEvent) => void) => {
+        const unlistenPromise = listen<LocalSpeechHealthEvent>('local-speech:health', (event) =>
+            callback(event.payload)
+        );
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
+        };
+    },
+    onLog: (callback: (line: LocalSpeechLogLine) => void) => {
+        const unlistenPromise = listen<LocalSpeechLogLine>('local-speech:log', (event) =>
+            callback(event.payload)
+        );
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
+        };
     }
 };
@@ -1,3 +1,6 @@
+import {invoke} from '@tauri-apps/api/core';
+import type {AudioSelfTestResult, MicPermissionStatus} from '@shared/types';
+
 export const micBridge = {
     hide(options?: {reason?: string; disableAutoShow?: boolean}): Promise<void> {
         return window.winky?.mic?.hide?.(options) ?? Promise.resolve();
@@ -13,5 +16,16 @@ export const micBridge = {
     },
     moveBy(dx: number, dy: number): Promise<void> {
         return window.winky?.mic?.moveBy?.(dx, dy) ?? Promise.resolve();
+    },
+    permissionStatus(): Promise<MicPermissionStatus> {
+        return invoke('mic_permission_status');
+    },
+    /** Triggers the OS prompt; when access is already denied, opens the privacy settings instead. */
+    requestPermission(): Promise<MicPermissionStatus> {
+        return invoke('mic_request_permission');
+    },
+    /** Records a short clip (200–10000 ms) and reports whether the microphone picked up any sound. */
+    selfTest(durationMs = 2000, device?: string): Promise<AudioSelfTestResult> {
+        return invoke('audio_self_test', {device: device ?? null, durationMs});
     }
 };
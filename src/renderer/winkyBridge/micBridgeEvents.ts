@@ -1,5 +1,6 @@
 import {emit, listen} from '@tauri-apps/api/event';
 import {MicWindowController} from '../services/windows/MicWindowController';
+import type {MicShortcutEvent} from '@shared/types';
 
 export type MicBridgeEventsDeps = {
     micController: MicWindowController;
@@ -33,9 +34,10 @@ export const attachMicBridgeEvents = ({micController, currentWindowKind, openMai
         let micShortcutHandling = false;
         let lastShortcutAt = 0;
         const SHORTCUT_COOLDOWN_MS = 120;
-        void listen('mic:shortcut', () => {
+        void listen<Partial<MicShortcutEvent>>('mic:shortcut', (event) => {
             const now = Date.now();
             if (micShortcutHandling || now - lastShortcutAt < SHORTCUT_COOLDOWN_MS) {
+                console.debug('[micBridgeEvents] Ignoring repeated mic shortcut', event.payload);
                 return;
             }
             micShortcutHandling = true;
@@ -1,43 +1,110 @@
 import {invoke} from '@tauri-apps/api/core';
 import {listen} from '@tauri-apps/api/event';
-import type {WinkyNote} from '@shared/types';
+import type {CommandErrorCode, WinkyNote} from '@shared/types';
+import type {RepairReport} from './historyBridge';
 
 export type NotesPageResponse = {
     count: number;
     next_page: number | null;
     previous_page: number | null;
+    total_pages?: number;
+    has_more?: boolean;
     results: WinkyNote[];
+    total_word_count?: number;
+    total_char_count?: number;
 };
 
 export type NotesUpdateEvent =
     | { type: 'added'; entry: WinkyNote; mode?: 'local' | 'api' }
     | { type: 'updated'; entry: WinkyNote; mode?: 'local' | 'api' }
     | { type: 'deleted'; id: string; mode?: 'local' | 'api' }
-    | { type: 'bulk-deleted'; ids: string[]; mode?: 'local' | 'api' };
+    | { type: 'bulk-deleted'; ids: string[]; mode?: 'local' | 'api' }
+    | { type: 'trash-emptied'; count: number; mode?: 'local' | 'api' };
+
+export type NotesSort = {
+    sortBy: 'created' | 'updated' | 'title';
+    direction?: 'asc' | 'desc';
+};
+
+/** Inclusive `created_at` bounds: RFC 3339 or `YYYY-MM-DD` (a plain end date covers the whole day). */
+export type NotesDateRange = {
+    createdFrom?: string;
+    createdTo?: string;
+};
+
+export type NotesListFilter = NotesDateRange & {
+    /** Archived notes are hidden unless this is set. */
+    includeArchived?: boolean;
+};
+
+export type NoteTagCount = {
+    tag: string;
+    count: number;
+};
 
 type NoteCreatePayload = {
     title: string;
     description?: string;
     x_username?: string;
+    tags?: string[];
+    /** Without it a case-insensitive title match fails with the `NOTE_DUPLICATE_TITLE_ERROR` code. */
+    allow_duplicate?: boolean;
 };
 
+/** Error code (see `getErrorCode`) of a create rejected for a duplicate title. */
+export const NOTE_DUPLICATE_TITLE_ERROR: CommandErrorCode = 'DUPLICATE_TITLE';
+
 type NoteUpdatePayload = {
     id: string;
     title?: string;
     description?: string;
     x_username?: string;
+    tags?: string[];
 };
 
 export const notesBridge = {
-    get: (page = 1, pageSize = 20): Promise<NotesPageResponse> =>
-        invoke('notes_get', {args: {page, pageSize}}),
+    get: (page = 1, pageSize = 20, sort?: NotesSort, filter?: NotesListFilter): Promise<NotesPageResponse> =>
+        invoke('notes_get', {
+            args: {
+                page,
+                pageSize,
+                sortBy: sort?.sortBy,
+                direction: sort?.direction,
+                createdFrom: filter?.createdFrom,
+                createdTo: filter?.createdTo,
+                includeArchived: filter?.includeArchived
+            }
+        }),
+    getByUsername: (username: string, page = 1, pageSize = 20): Promise<NotesPageResponse> =>
+        invoke('notes_get_by_username', {username, args: {page, pageSize}}),
+    search: (query: string, page = 1, pageSize = 20): Promise<NotesPageResponse> =>
+        invoke('notes_search', {query, args: {page, pageSize}}),
+    listTags: (includeArchived = false): Promise<NoteTagCount[]> => invoke('notes_list_tags', {includeArchived}),
+    exportMarkdown: (destPath: string): Promise<number> => invoke('notes_export_markdown', {destPath}),
+    count: (): Promise<number> => invoke('notes_count'),
     create: (payload: NoteCreatePayload): Promise<WinkyNote> =>
         invoke('notes_create', {payload}),
     update: (payload: NoteUpdatePayload): Promise<WinkyNote> =>
         invoke('notes_update', {payload}),
+    duplicate: (id: string): Promise<WinkyNote> => invoke('notes_duplicate', {id}),
+    append: (id: string, text: string): Promise<WinkyNote> => invoke('notes_append', {id, text}),
+    /** Appends the secondary note to the primary one and moves the secondary to the trash. */
+    merge: (primaryId: string, secondaryId: string): Promise<WinkyNote> =>
+        invoke('notes_merge', {primaryId, secondaryId}),
     delete: (id: string): Promise<void> => invoke('notes_delete', {payload: {id}}),
     bulkDelete: (ids: string[]): Promise<{deleted_count: number}> =>
         invoke('notes_bulk_delete', {payload: {ids}}),
+    setPinned: (id: string, pinned: boolean): Promise<WinkyNote> => invoke('notes_set_pinned', {id, pinned}),
+    setArchived: (id: string, archived: boolean): Promise<WinkyNote> => invoke('notes_set_archived', {id, archived}),
+    listTrash: (page = 1, pageSize = 20): Promise<NotesPageResponse> =>
+        invoke('notes_list_trash', {args: {page, pageSize}}),
+    restore: (id: string): Promise<WinkyNote> => invoke('notes_restore', {id}),
+    emptyTrash: (): Promise<number> => invoke('notes_empty_trash'),
+    backup: (): Promise<string> => invoke('notes_backup'),
+    repair: (): Promise<RepairReport> => invoke('notes_repair'),
+    getStorageMode: (): Promise<'api' | 'local'> => invoke('notes_get_storage_mode'),
+    setStorageMode: (mode: 'api' | 'local', migrate: boolean): Promise<{mode: 'api' | 'local'; migrated: number}> =>
+        invoke('notes_set_storage_mode', {mode, migrate}),
     subscribe: (callback: (event: NotesUpdateEvent) => void): (() => void) => {
         let stopped = false;
         const unlistenPromise = listen<NotesUpdateEvent>('notes:updated', (event) => {
@@ -12,5 +12,6 @@ export const ollamaBridge = {
     chatCompletions: (model: string, messages: ChatMessage[]): Promise<any> =>
         invoke('ollama_chat_completions', {model, messages}),
     chatCompletionsStream: (model: string, messages: ChatMessage[], streamId: string): Promise<string> =>
-        invoke('ollama_chat_completions_stream', {model, messages, streamId})
+        invoke('ollama_chat_completions_stream', {model, messages, streamId}),
+    cancelStream: (streamId: string): Promise<boolean> => invoke('llm_cancel', {streamId})
 };
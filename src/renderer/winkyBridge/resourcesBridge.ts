@@ -1,6 +1,14 @@
 import {invoke} from '@tauri-apps/api/core';
+import type {AudioOutputDevice} from '@shared/types';
 
 export const resourcesBridge = {
     getSoundData: (soundName: string): Promise<Uint8Array> => invoke('resources_sound_data', {soundName}),
-    playSound: (soundName: string): Promise<void> => invoke('resources_play_sound', {soundName})
+    playSound: (soundName: string, volume?: number): Promise<void> =>
+        invoke('resources_play_sound', {soundName, volume}),
+    playTestSound: (name?: string | null, volume?: number | null): Promise<void> =>
+        invoke('play_test_sound', {name: name ?? null, volume: volume ?? null}),
+    resolveSound: (name: string): Promise<string> => invoke('sound_resolve', {name}),
+    listBundledSounds: (): Promise<string[]> => invoke('sounds_bundled'),
+    duckOthers: (enable: boolean): Promise<void> => invoke('audio_duck_others', {enable}),
+    listOutputDevices: (): Promise<AudioOutputDevice[]> => invoke('audio_output_devices')
 };
@@ -1,9 +1,47 @@
+import {invoke} from '@tauri-apps/api/core';
+import {listen} from '@tauri-apps/api/event';
+import type {AppConfig, TranscribeMode} from '@shared/types';
 import {transcribeAudio, SpeechTranscribeConfig, SpeechTranscribeOptions} from '../services/winkyApi';
 
+export type BatchTranscriptionResult = {
+    text: string | null;
+    error: string | null;
+};
+
+export type BatchTranscriptionProgress = {
+    path: string;
+    completed: number;
+    total: number;
+    ok: boolean;
+    error: string | null;
+};
+
+export type SpeechLanguage = {
+    code: string;
+    name: string;
+};
+
 export const speechBridge = {
     transcribe: (
         audioData: ArrayBuffer,
         config: SpeechTranscribeConfig,
         options?: SpeechTranscribeOptions
-    ) => transcribeAudio(audioData, config, options)
+    ) => transcribeAudio(audioData, config, options),
+    setMode: (mode: TranscribeMode): Promise<AppConfig> => invoke('speech_set_mode', {mode}),
+    /** Languages the active speech mode can transcribe, sorted by name. */
+    supportedLanguages: (): Promise<SpeechLanguage[]> => invoke('speech_supported_languages'),
+    transcribeBatch: (
+        paths: string[],
+        provider: 'local' | 'openai',
+        model?: string
+    ): Promise<Record<string, BatchTranscriptionResult>> =>
+        invoke('transcribe_batch', {paths, provider, model}),
+    onBatchProgress: (callback: (progress: BatchTranscriptionProgress) => void) => {
+        const unlistenPromise = listen<BatchTranscriptionProgress>('transcribe:batch-progress', (event) =>
+            callback(event.payload)
+        );
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten()).catch(() => {});
+        };
+    }
 };
@@ -3,6 +3,7 @@ import {emit} from '@tauri-apps/api/event';
 import {ResultWindowManager, type ResultPayload} from '@renderer/services/windows';
 import {AuxWindowController} from '@renderer/services/windows';
 import {getCurrentWindow} from '@tauri-apps/api/window';
+import type {AppConfig} from '@shared/types';
 
 const resultWindowManager = new ResultWindowManager();
 const currentWindow = getCurrentWindow();
@@ -50,7 +51,14 @@ export const windowControlsBridge = {
         } catch {
             /* ignore */
         }
-    }
+    },
+    saveLayout: (): Promise<AppConfig> => invoke('layout_save'),
+    restoreLayout: (): Promise<string[]> => invoke('layout_restore'),
+    openDownloadPage: (url?: string): Promise<string> => invoke('open_download_page', {url}),
+    setAlwaysOnTop: (label: string, onTop: boolean): Promise<void> =>
+        invoke('window_set_always_on_top', {label, onTop}),
+    moveToMonitor: (label: string, monitorIndex: number): Promise<{x: number; y: number}> =>
+        invoke('window_move_to_monitor', {label, monitorIndex})
 };
 
 export const auxWindowsBridge = {
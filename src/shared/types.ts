@@ -54,6 +54,12 @@ export type AuthDeepLinkPayload =
     error: string;
 };
 
+/** Payload of `auth:changed`; tokens are never included. */
+export interface AuthChangedEvent {
+    authenticated: boolean;
+    provider?: string | null;
+}
+
 export interface User {
     id: number;
     email: string;
@@ -104,10 +110,13 @@ export interface ActionConfig {
     priority?: number;
     show_results?: boolean;
     sound_on_complete?: boolean;
+    completion_sound?: string | null;
     auto_copy_result?: boolean;
     is_active?: boolean;
     is_default?: boolean;
     llm_model?: string | null;
+    enabled?: boolean;
+    language?: string | null;
 }
 
 export interface ActionGroup {
@@ -145,8 +154,14 @@ export interface WinkyNote {
     title: string;
     description: string;
     x_username?: string | null;
+    tags?: string[];
+    pinned?: boolean;
+    archived?: boolean;
     created_at: string;
     updated_at: string;
+    deleted_at?: string | null;
+    word_count?: number;
+    char_count?: number;
 }
 
 export interface WinkyProfile {
@@ -196,7 +211,61 @@ export interface AppConfig {
     trimSilenceOnActions?: boolean;
     globalTranscribePrompt?: string;
     globalLlmPrompt?: string;
+    globalPromptPrefix?: string | null;
+    globalPromptSuffix?: string | null;
     selectedMicrophoneId?: string;
+    completionBehavior?: CompletionBehavior;
+    mouseTrigger?: MouseTrigger | null;
+    duckAudioWhileRecording?: boolean;
+    hotkeyDebounceMs?: number;
+    localSpeechInstallDir?: string | null;
+    speechLanguage?: string | null;
+    overlayTheme?: OverlayTheme;
+    micAutoHideDelayMs?: number;
+    windowLayout?: WindowLayoutEntry[];
+    prewarmLlm?: boolean;
+    providerRateLimits?: Record<string, number>;
+    closeToTray?: boolean;
+    minimizeToTray?: boolean;
+    soundOutputDevice?: string | null;
+    transcodeLocalAudio?: boolean;
+    localSpeechHealthPollMs?: number;
+    micAlwaysOnTop?: boolean;
+    localSpeechIdleStopMinutes?: number;
+    localSpeechLazyStart?: boolean;
+    historyAudioRetentionDays?: number;
+    onboardingState?: Record<string, string>;
+    llmOutputFormat?: LlmOutputFormat;
+}
+
+export interface WindowLayoutEntry {
+    label: string;
+    x: number;
+    y: number;
+    width: number;
+    height: number;
+    visible: boolean;
+}
+
+export type OverlayTheme = 'default' | 'minimal' | 'avatar';
+
+export type LlmOutputFormat = 'none' | 'text' | 'markdown';
+
+export interface MouseTrigger {
+    button: 3 | 4 | 5;
+    modifier?: 'ctrl' | 'alt' | 'shift' | 'super' | null;
+}
+
+export type CompletionBehavior = 'copy' | 'paste' | 'copy_and_notify' | 'paste_stream';
+
+export type MicPermissionStatus = 'granted' | 'denied' | 'undetermined';
+
+export interface AudioSelfTestResult {
+    device: string | null;
+    durationMs: number;
+    peak: number;
+    rms: number;
+    signalDetected: boolean;
 }
 
 export type MicAnchor = 'top-left' | 'top-right' | 'bottom-left' | 'bottom-right';
@@ -220,6 +289,7 @@ export interface FastWhisperStatus {
     lastSuccessAt?: number;
     logLine?: string;
     installDir?: string;
+    baseUrl?: string | null;
     updatedAt: number;
 }
 
@@ -283,3 +353,74 @@ export interface MessageChildrenResponse {
     items: WinkyChatMessage[];
     total: number;
 }
+
+export interface ConfigUpdateResult {
+    config: AppConfig;
+    changedPaths: string[];
+}
+
+export interface AudioOutputDevice {
+    id: string;
+    name: string;
+}
+
+export interface SystemDependency {
+    name: string;
+    present: boolean;
+    path: string | null;
+    version: string | null;
+}
+
+export interface BackendReadiness {
+    backend: 'llm' | 'ollama' | 'speech';
+    ready: boolean;
+    skipped: boolean;
+    message: string;
+    durationMs: number;
+}
+
+/** Payload of `openai:stream` / `gemini:stream`; the `done` event carries latency in ms. */
+export type LlmStreamEvent =
+    | {streamId: string; delta: string}
+    | {streamId: string; done: true; firstTokenMs: number | null; totalMs: number}
+    | {streamId: string; done: true; cancelled: true};
+
+export interface ConfigIntegrity {
+    matches: boolean;
+    expected: string | null;
+    actual: string;
+}
+
+export type CommandErrorCode =
+    | 'AUTH_MISSING'
+    | 'SERVER_DOWN'
+    | 'NETWORK'
+    | 'NOT_FOUND'
+    | 'INVALID_INPUT'
+    | 'IO'
+    | 'INTERNAL'
+    | 'RATE_LIMITED'
+    | 'DUPLICATE_TITLE';
+
+export interface CommandError {
+    code: CommandErrorCode;
+    message: string;
+    details: unknown | null;
+}
+
+export type ShortcutSource = 'shortcut' | 'mic-shortcut' | 'mouse' | 'command';
+
+export interface MicShortcutEvent {
+    /** @deprecated use `source` */
+    reason: ShortcutSource;
+    source: ShortcutSource;
+    accelerator: string | null;
+    timestamp: number;
+}
+
+export interface ActionTriggeredEvent {
+    actionId: string;
+    source: ShortcutSource;
+    accelerator: string | null;
+    timestamp: number;
+}